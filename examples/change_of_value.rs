@@ -1,6 +1,6 @@
 // cargo run --example change_of_value -- --addr "192.168.1.249:47808"
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::services::change_of_value::SubscribeCov,
@@ -28,16 +28,16 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
 
     // subscribe
     let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
-    let request = SubscribeCov::new(1, object_id, false, 5);
+    let request = SubscribeCov::new(1, object_id, true, 5);
     bacnet.subscribe_change_of_value(&mut buf, request).await?;
 
-    // fetch next
-    let result = bacnet.read_change_of_value(&mut buf).await?;
+    // fetch next (confirmed notifications are acknowledged automatically)
+    let result = bacnet.receive_change_of_value(&mut buf).await?;
 
     // print
-    if let Some(notification) = result {
-        println!("{:?}", notification);
-        for property in &notification.values {
+    if let Some(received) = result {
+        println!("{:?}", received);
+        for property in &received.notification.values {
             println!("Value: {:?}", property?)
         }
     }
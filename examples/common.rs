@@ -3,7 +3,6 @@ fn main() {
     // dummy main because this "example" is used for common code for all examples
 }
 
-use core::usize;
 use embedded_bacnet::simple::{Bacnet, BacnetError, NetworkIo};
 use std::io;
 use tokio::net::UdpSocket;
@@ -0,0 +1,94 @@
+// cargo run --example device_server -- --device-id 1234
+// then from another machine: cargo run --example who_is_broadcast
+
+use std::{io::Error, net::UdpSocket};
+
+use clap::Parser;
+use embedded_bacnet::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        services::{i_am::IAm, who_is::WhoIs},
+        unconfirmed::UnconfirmedRequest,
+    },
+    common::{
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        spec::Segmentation,
+    },
+    network_protocol::{
+        data_link::{DataLink, DataLinkFunction},
+        network_pdu::{MessagePriority, NetworkPdu},
+    },
+};
+
+/// A minimal simulated Bacnet device that replies to who_is requests with an i_am.
+/// Use it as a template for building a device simulator.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Device ID of the simulated device e.g. 1234
+    #[arg(short, long)]
+    device_id: u32,
+
+    /// Vendor ID of the simulated device
+    #[arg(short, long, default_value_t = 999)]
+    vendor_id: u16,
+
+    /// Local address to listen on
+    #[arg(short, long, default_value = "0.0.0.0:47808")]
+    bind: String,
+}
+
+fn main() -> Result<(), Error> {
+    simple_logger::init().unwrap();
+    let args = Args::parse();
+    let socket = UdpSocket::bind(&args.bind)?;
+    socket.set_broadcast(true)?;
+    println!("Device {} listening on {}", args.device_id, args.bind);
+
+    let i_am = IAm {
+        device_id: ObjectId::new(ObjectType::ObjectDevice, args.device_id),
+        max_apdu: 1476,
+        segmentation: Segmentation::None,
+        vendor_id: args.vendor_id,
+    };
+
+    let mut buf = vec![0; 1500];
+    let mut reply_buf = vec![0; 1500];
+    let mut writer = Writer::new(&mut reply_buf); // reused for every reply
+
+    loop {
+        let (n, peer) = socket.recv_from(&mut buf)?;
+        let payload = &buf[..n];
+
+        let mut reader = Reader::default();
+        let who_is: WhoIs = match DataLink::decode(&mut reader, payload) {
+            Ok(message) => match message.apdu() {
+                Some(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::WhoIs(x))) => x.clone(),
+                _ => continue, // not for us
+            },
+            Err(e) => {
+                println!("Ignoring undecodable packet from {}: {:?}", peer, e);
+                continue;
+            }
+        };
+
+        if !who_is.matches(args.device_id) {
+            println!("WhoIs from {} is for another device range", peer);
+            continue;
+        }
+
+        writer.reset();
+        encode_i_am(&i_am, &mut writer);
+        socket.send_to(writer.to_bytes(), peer)?;
+        println!("Sent I-Am to {} in reply to {:?}", peer, who_is);
+    }
+}
+
+// an I-Am is usually broadcast but replying to the sender works without a broadcast address
+fn encode_i_am(i_am: &IAm, writer: &mut Writer) {
+    let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(i_am.clone()));
+    let npdu = NetworkPdu::new_apdu(None, None, MessagePriority::Normal, apdu);
+    let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+    data_link.encode(writer);
+}
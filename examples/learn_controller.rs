@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
@@ -10,7 +10,7 @@ use embedded_bacnet::{
         services::{
             read_property::{ReadProperty, ReadPropertyValue},
             read_property_multiple::{
-                PropertyValue, ReadPropertyMultiple, ReadPropertyMultipleObject,
+                PropertyValue, ReadAccessSpecification, ReadPropertyMultiple,
             },
         },
     },
@@ -64,7 +64,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
                 | ObjectType::ObjectAnalogValue
                 | ObjectType::ObjectSchedule
                 | ObjectType::ObjectTrendlog => {
-                    let list = map.entry(item.object_type.clone() as u32).or_insert(vec![]);
+                    let list = map.entry(u32::from(item.object_type)).or_insert(vec![]);
                     list.push(item);
                 }
                 _ => {}
@@ -78,7 +78,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
             ObjectType::ObjectBinaryInput
             | ObjectType::ObjectBinaryOutput
             | ObjectType::ObjectBinaryValue => {
-                for chunk in ids.as_slice().chunks(10).into_iter() {
+                for chunk in ids.as_slice().chunks(10) {
                     let _values = get_multi_binary(&mut bacnet, &mut buf, chunk).await?;
                     println!("{:?}", _values);
                 }
@@ -86,7 +86,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
             ObjectType::ObjectAnalogInput
             | ObjectType::ObjectAnalogOutput
             | ObjectType::ObjectAnalogValue => {
-                for chunk in ids.as_slice().chunks(10).into_iter() {
+                for chunk in ids.as_slice().chunks(10) {
                     let _values = get_multi_analog(&mut bacnet, &mut buf, chunk).await?;
                     println!("{:?}", _values);
                 }
@@ -98,7 +98,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
                 }
             }
             ObjectType::ObjectTrendlog => {
-                for chunk in ids.as_slice().chunks(10).into_iter() {
+                for chunk in ids.as_slice().chunks(10) {
                     let values = get_multi_trend_log(&mut bacnet, &mut buf, chunk).await?;
                     println!("{:?}", values);
                 }
@@ -158,9 +158,9 @@ async fn get_multi_binary(
         PropertyId::PropPresentValue,
         PropertyId::PropStatusFlags,
     ];
-    let items: Vec<ReadPropertyMultipleObject> = object_ids
+    let items: Vec<ReadAccessSpecification> = object_ids
         .iter()
-        .map(|x| ReadPropertyMultipleObject::new(x.clone(), &property_ids))
+        .map(|x| ReadAccessSpecification::new(*x, &property_ids))
         .collect();
     let request = ReadPropertyMultiple::new(&items);
     let result = bacnet.read_property_multiple(buf, request).await?;
@@ -170,12 +170,12 @@ async fn get_multi_binary(
         let obj = obj?;
         let mut x = obj.property_results.into_iter();
         let name = x.next().unwrap()?.value.to_string();
-        let value = match x.next().unwrap()?.value {
+        let value = matches!(
+            x.next().unwrap()?.value,
             PropertyValue::PropValue(ApplicationDataValue::Enumerated(Enumerated::Binary(
                 Binary::On,
-            ))) => true,
-            _ => false,
-        };
+            )))
+        );
         let status = match x.next().unwrap()?.value {
             PropertyValue::PropValue(ApplicationDataValue::BitString(BitString::Status(x))) => x,
             _ => unreachable!(),
@@ -189,7 +189,7 @@ async fn get_multi_binary(
         });
     }
 
-    return Ok(items);
+    Ok(items)
 }
 
 async fn get_multi_analog(
@@ -204,9 +204,9 @@ async fn get_multi_analog(
         PropertyId::PropStatusFlags,
     ];
 
-    let items: Vec<ReadPropertyMultipleObject> = object_ids
+    let items: Vec<ReadAccessSpecification> = object_ids
         .iter()
-        .map(|x| ReadPropertyMultipleObject::new(x.clone(), &property_ids))
+        .map(|x| ReadAccessSpecification::new(*x, &property_ids))
         .collect();
 
     let request = ReadPropertyMultiple::new(&items);
@@ -241,7 +241,7 @@ async fn get_multi_analog(
         })
     }
 
-    return Ok(items);
+    Ok(items)
 }
 
 async fn get_multi_trend_log(
@@ -251,9 +251,9 @@ async fn get_multi_trend_log(
 ) -> Result<Vec<TrendLogValue>, BacnetError<MySocket>> {
     let property_ids = [PropertyId::PropObjectName, PropertyId::PropRecordCount];
 
-    let items: Vec<ReadPropertyMultipleObject> = object_ids
+    let items: Vec<ReadAccessSpecification> = object_ids
         .iter()
-        .map(|x| ReadPropertyMultipleObject::new(x.clone(), &property_ids))
+        .map(|x| ReadAccessSpecification::new(*x, &property_ids))
         .collect();
 
     let request = ReadPropertyMultiple::new(&items);
@@ -277,7 +277,7 @@ async fn get_multi_trend_log(
         })
     }
 
-    return Ok(items);
+    Ok(items)
 }
 
 async fn get_multi_schedule(
@@ -286,10 +286,7 @@ async fn get_multi_schedule(
     object_id: &ObjectId,
 ) -> Result<Vec<ScheduleValue>, BacnetError<MySocket>> {
     let property_ids = [PropertyId::PropObjectName, PropertyId::PropWeeklySchedule];
-    let objects = [ReadPropertyMultipleObject::new(
-        object_id.clone(),
-        &property_ids,
-    )];
+    let objects = [ReadAccessSpecification::new(*object_id, &property_ids)];
     let request = ReadPropertyMultiple::new(&objects);
     let result = bacnet.read_property_multiple(buf, request).await?;
 
@@ -317,5 +314,5 @@ async fn get_multi_schedule(
         });
     }
 
-    return Ok(items);
+    Ok(items)
 }
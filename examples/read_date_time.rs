@@ -0,0 +1,64 @@
+// cargo run --example read_date_time -- --addr "192.168.1.249:47808" --device-id 79079
+
+use clap::Parser;
+use common::MySocket;
+use embedded_bacnet::{
+    application_protocol::{
+        primitives::data_value::ApplicationDataValue,
+        services::read_property::{ReadProperty, ReadPropertyValue},
+    },
+    common::{
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+    },
+    simple::BacnetError,
+};
+
+mod common;
+
+/// A Bacnet Client example to read the date and time of a controller with two ReadProperty requests
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// IP address with port e.g. "192.168.1.249:47808"
+    #[arg(short, long)]
+    addr: String,
+
+    /// Device ID of the controller e.g. 79079
+    #[arg(short, long)]
+    device_id: u32,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), BacnetError<MySocket>> {
+    // setup
+    let args = Args::parse();
+    let mut bacnet = common::get_bacnet_socket(&args.addr).await?;
+    let mut buf = vec![0; 1500];
+    let object_id = ObjectId::new(ObjectType::ObjectDevice, args.device_id);
+
+    // fetch date
+    let request = ReadProperty::new(object_id, PropertyId::PropLocalDate);
+    let result = bacnet.read_property(&mut buf, request).await?;
+    match result.property_value {
+        ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Date(date)) => {
+            println!("Date: {}-{:02}-{:02}", date.year, date.month, date.day)
+        }
+        x => println!("Unexpected value type returned: {:?}", x),
+    }
+
+    // fetch time
+    let request = ReadProperty::new(object_id, PropertyId::PropLocalTime);
+    let result = bacnet.read_property(&mut buf, request).await?;
+    match result.property_value {
+        ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Time(time)) => {
+            println!(
+                "Time: {:02}:{:02}:{:02}",
+                time.hour, time.minute, time.second
+            )
+        }
+        x => println!("Unexpected value type returned: {:?}", x),
+    }
+
+    Ok(())
+}
@@ -1,10 +1,10 @@
 // cargo run --example read_property_multiple -- --addr "0.0.0.0:47808"
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::services::read_property_multiple::{
-        ReadPropertyMultiple, ReadPropertyMultipleObject,
+        ReadAccessSpecification, ReadPropertyMultiple,
     },
     common::{
         object_id::{ObjectId, ObjectType},
@@ -39,7 +39,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
         PropertyId::PropUnits,
         PropertyId::PropStatusFlags,
     ];
-    let objects = [ReadPropertyMultipleObject::new(object_id, &property_ids)];
+    let objects = [ReadAccessSpecification::new(object_id, &property_ids)];
     let request = ReadPropertyMultiple::new(&objects);
     let result = bacnet.read_property_multiple(&mut buf, request).await?;
 
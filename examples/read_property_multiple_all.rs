@@ -1,10 +1,10 @@
 // cargo run --example read_property_multiple_all -- --addr "0.0.0.0:47808"
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::services::read_property_multiple::{
-        ReadPropertyMultiple, ReadPropertyMultipleObject,
+        ReadAccessSpecification, ReadPropertyMultiple,
     },
     common::{
         object_id::{ObjectId, ObjectType},
@@ -33,7 +33,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
     let mut buf = vec![0; 1500];
 
     // fetch all property values for an analog input 4
-    let objects = [ReadPropertyMultipleObject::new(
+    let objects = [ReadAccessSpecification::new(
         ObjectId::new(ObjectType::ObjectAnalogInput, 1),
         &[PropertyId::PropAll],
     )];
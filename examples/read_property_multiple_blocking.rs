@@ -1,9 +1,9 @@
 // cargo run --example read_property_multiple_blocking --features="is_sync" -- --addr "0.0.0.0:47808"
 
-use clap::{command, Parser};
+use clap::Parser;
 use embedded_bacnet::{
     application_protocol::services::read_property_multiple::{
-        ReadPropertyMultiple, ReadPropertyMultipleObject,
+        ReadAccessSpecification, ReadPropertyMultiple,
     },
     common::{
         object_id::{ObjectId, ObjectType},
@@ -68,7 +68,7 @@ fn main() -> Result<(), BacnetError<MySocket>> {
         PropertyId::PropUnits,
         PropertyId::PropStatusFlags,
     ];
-    let objects = [ReadPropertyMultipleObject::new(object_id, &property_ids)];
+    let objects = [ReadAccessSpecification::new(object_id, &property_ids)];
     let request = ReadPropertyMultiple::new(&objects);
     let result = bacnet.read_property_multiple(&mut buf, request)?;
 
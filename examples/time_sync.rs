@@ -1,13 +1,13 @@
 // cargo run --example time_sync -- --addr "192.168.1.249:47808" --device-id 79079
 
 use chrono::{Datelike, Local, Timelike};
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
         primitives::data_value::{Date, Time},
         services::{
-            read_property_multiple::{ReadPropertyMultiple, ReadPropertyMultipleObject},
+            read_property_multiple::{ReadAccessSpecification, ReadPropertyMultiple},
             time_synchronization::TimeSynchronization,
         },
     },
@@ -51,15 +51,9 @@ async fn set_time_to_now(
     buf: &mut [u8],
 ) -> Result<(), BacnetError<MySocket>> {
     let now = Local::now();
-    let wday = now.weekday().num_days_from_sunday() as u8; // sunday = 0
 
     // encode packet
-    let date = Date {
-        year: now.year() as u16,
-        month: now.month() as u8,
-        day: now.day() as u8,
-        wday,
-    };
+    let date = Date::from_ymd(now.year() as u16, now.month() as u8, now.day() as u8);
     let time = Time {
         hour: now.hour() as u8,
         minute: now.minute() as u8,
@@ -81,7 +75,7 @@ async fn request_date_time(
 
     let object_id = ObjectId::new(ObjectType::ObjectDevice, device_id);
     let property_ids = [PropertyId::PropLocalDate, PropertyId::PropLocalTime];
-    let rpm = ReadPropertyMultipleObject::new(object_id, &property_ids);
+    let rpm = ReadAccessSpecification::new(object_id, &property_ids);
     let objects = [rpm];
     let request = ReadPropertyMultiple::new(&objects);
     let result = bacnet.read_property_multiple(buf, request).await?;
@@ -1,13 +1,13 @@
 // cargo run --example update_schedule -- --addr "192.168.1.249:47808"
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::{get_bacnet_socket, MySocket};
 use embedded_bacnet::{
     application_protocol::{
         primitives::data_value::{ApplicationDataValue, ApplicationDataValueWrite},
         services::{
             read_property_multiple::{
-                PropertyValue, ReadPropertyMultiple, ReadPropertyMultipleObject,
+                PropertyValue, ReadAccessSpecification, ReadPropertyMultiple,
             },
             write_property::WriteProperty,
         },
@@ -67,7 +67,7 @@ async fn decode_weekly_schedule(
     // fetch
     let object_id = ObjectId::new(ObjectType::ObjectSchedule, 1);
     let property_ids = [PropertyId::PropObjectName, PropertyId::PropWeeklySchedule];
-    let rpm = ReadPropertyMultipleObject::new(object_id, &property_ids);
+    let rpm = ReadAccessSpecification::new(object_id, &property_ids);
     let objects = [rpm];
     let request = ReadPropertyMultiple::new(&objects);
     let result = bacnet.read_property_multiple(buf, request).await?;
@@ -1,6 +1,6 @@
 // cargo run --example who_is -- --addr "192.168.1.249:47808"
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{application_protocol::services::who_is::WhoIs, simple::BacnetError};
 
@@ -23,7 +23,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
     let mut buf = vec![0; 1500];
 
     // fetch
-    let request = WhoIs {};
+    let request = WhoIs::new();
     let result = bacnet.who_is(&mut buf, request).await?;
 
     // print
@@ -11,7 +11,7 @@ use embedded_bacnet::{
     common::io::{Reader, Writer},
     network_protocol::{
         data_link::{DataLink, DataLinkFunction},
-        network_pdu::{DestinationAddress, MessagePriority, NetworkMessage, NetworkPdu},
+        network_pdu::{DestinationAddress, MessagePriority, NetworkPdu},
     },
 };
 
@@ -53,11 +53,10 @@ fn main() -> Result<(), Error> {
     let socket = UdpSocket::bind(format!("0.0.0.0:{}", 0xBAC1))?;
     socket.set_broadcast(true)?;
 
-    let who_is = WhoIs {};
+    let who_is = WhoIs::new();
     let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::WhoIs(who_is));
     let dst = Some(DestinationAddress::new(0xffff, None));
-    let message = NetworkMessage::Apdu(apdu);
-    let npdu = NetworkPdu::new(None, dst, false, MessagePriority::Normal, message);
+    let npdu = NetworkPdu::new_apdu(None, dst, MessagePriority::Normal, apdu);
     let data_link = DataLink::new(DataLinkFunction::OriginalBroadcastNpdu, Some(npdu));
 
     let mut buffer = vec![0; 1500];
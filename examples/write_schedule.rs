@@ -0,0 +1,74 @@
+// cargo run --example write_schedule -- --addr "192.168.1.249:47808"
+
+use clap::Parser;
+use common::MySocket;
+use embedded_bacnet::{
+    application_protocol::{primitives::data_value::Time, services::write_property::WriteProperty},
+    common::{
+        daily_schedule::WeeklySchedule,
+        object_id::{ObjectId, ObjectType},
+        time_value::{SimpleApplicationDataValue, TimeValue},
+    },
+    simple::BacnetError,
+};
+
+mod common;
+
+/// A Bacnet Client example to replace the weekly schedule of schedule #1
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// IP address with port e.g. "192.168.1.249:47808"
+    #[arg(short, long)]
+    addr: String,
+}
+
+#[tokio::main]
+#[allow(clippy::useless_conversion)]
+async fn main() -> Result<(), BacnetError<MySocket>> {
+    // setup
+    let args = Args::parse();
+    let mut bacnet = common::get_bacnet_socket(&args.addr).await?;
+    let mut buf = vec![0; 1500];
+
+    // 21 degrees from 7am and 16 degrees from 6pm on weekdays, nothing scheduled on the weekend
+    let weekday = [
+        TimeValue {
+            time: time(7, 0),
+            value: SimpleApplicationDataValue::Real(21.0),
+        },
+        TimeValue {
+            time: time(18, 0),
+            value: SimpleApplicationDataValue::Real(16.0),
+        },
+    ];
+    let weekend: &[TimeValue] = &[];
+
+    // into() so that this works with and without the alloc feature
+    let weekly_schedule = WeeklySchedule::new(
+        weekday[..].into(),
+        weekday[..].into(),
+        weekday[..].into(),
+        weekday[..].into(),
+        weekday[..].into(),
+        weekend.into(),
+        weekend.into(),
+    );
+
+    // write
+    let object_id = ObjectId::new(ObjectType::ObjectSchedule, 1);
+    let request = WriteProperty::new_weekly_schedule(object_id, weekly_schedule);
+    bacnet.write_property(&mut buf, request).await?;
+    println!("Write successful");
+
+    Ok(())
+}
+
+fn time(hour: u8, minute: u8) -> Time {
+    Time {
+        hour,
+        minute,
+        second: 0,
+        hundredths: 0,
+    }
+}
@@ -0,0 +1,206 @@
+// Feeds arbitrary bytes to DataLink::decode and walks everything that is decoded lazily (lists are
+// only decoded when iterated) so that every decoder is reached. Decoding must never panic, it can
+// only fail with an Error.
+#![no_main]
+
+use embedded_bacnet::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{ComplexAckService, ConfirmedRequestService},
+        primitives::data_value::{ApplicationDataValue, ApplicationDataValueWrite},
+        services::{
+            read_property::ReadPropertyValue,
+            read_property_multiple::{PropertyValue, ReadPropertyMultiple},
+        },
+        unconfirmed::UnconfirmedRequest,
+    },
+    common::{daily_schedule::WeeklySchedule, error::Error, io::Reader},
+    network_protocol::{
+        data_link::DataLink,
+        network_pdu::{NetworkMessage, NetworkPdu},
+    },
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut reader = Reader::default();
+    if let Ok(data_link) = DataLink::decode(&mut reader, data) {
+        if let Some(npdu) = &data_link.npdu {
+            let _ = walk_npdu(npdu);
+        }
+    }
+
+    for data_link in DataLink::decode_all(data) {
+        match data_link {
+            Ok(DataLink {
+                npdu: Some(npdu), ..
+            }) => {
+                let _ = walk_npdu(&npdu);
+            }
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+
+    // without the bacnet/ip header (e.g. other data links)
+    let mut reader = Reader::default();
+    if let Ok(npdu) = NetworkPdu::decode(&mut reader, data) {
+        let _ = walk_npdu(&npdu);
+    }
+
+    // a bare apdu (e.g. the reassembled segments of a segmented message)
+    let mut reader = Reader::default();
+    if let Ok(apdu) = ApplicationPdu::decode(&mut reader, data) {
+        let _ = walk_apdu(&apdu);
+    }
+});
+
+fn walk_npdu(npdu: &NetworkPdu) -> Result<(), Error> {
+    match &npdu.network_message {
+        NetworkMessage::Apdu(apdu) => walk_apdu(apdu),
+        NetworkMessage::InitRtTable(table) | NetworkMessage::InitRtTableAck(table) => {
+            for entry in table {
+                entry?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn walk_apdu(apdu: &ApplicationPdu) -> Result<(), Error> {
+    match apdu {
+        ApplicationPdu::ConfirmedRequest(request) => match &request.service {
+            ConfirmedRequestService::ReadPropertyMultiple(x) => walk_read_property_multiple(x),
+            ConfirmedRequestService::CovNotification(x) => {
+                for result in &x.values {
+                    walk_application_data_value(&result?.value)?;
+                }
+                Ok(())
+            }
+            ConfirmedRequestService::WriteProperty(x) => match &x.value {
+                ApplicationDataValueWrite::WeeklySchedule(x) => walk_weekly_schedule(x),
+                _ => Ok(()),
+            },
+            ConfirmedRequestService::VtClose(x) => {
+                for id in x {
+                    id?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        },
+        ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::CovNotification(x)) => {
+            for result in &x.values {
+                walk_application_data_value(&result?.value)?;
+            }
+            Ok(())
+        }
+        ApplicationPdu::ComplexAck(ack) => match &ack.service {
+            ComplexAckService::ReadProperty(x) => walk_read_property_value(&x.property_value),
+            ComplexAckService::ReadPropertyMultiple(x)
+            | ComplexAckService::ReadPropertyConditional(x) => {
+                for object in x {
+                    for result in &object?.property_results {
+                        walk_property_value(&result?.value)?;
+                    }
+                }
+                Ok(())
+            }
+            ComplexAckService::ReadRange(x) => {
+                for item in &x.item_data {
+                    item?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+fn walk_read_property_value(value: &ReadPropertyValue) -> Result<(), Error> {
+    match value {
+        ReadPropertyValue::ObjectIdList(x) => {
+            for object_id in x {
+                object_id?;
+            }
+        }
+        ReadPropertyValue::ApplicationDataValue(x) => walk_application_data_value(x)?,
+        ReadPropertyValue::ApplicationDataValueList(x) => {
+            for value in x {
+                walk_application_data_value(&value?)?;
+            }
+        }
+        ReadPropertyValue::AddressBindingList(x) => {
+            for binding in x {
+                binding?;
+            }
+        }
+        ReadPropertyValue::CovSubscriptionList(x) => {
+            for subscription in x {
+                subscription?;
+            }
+        }
+        ReadPropertyValue::GroupMembers(x) => walk_read_property_multiple(x)?,
+        ReadPropertyValue::EventTimeStamps(_) | ReadPropertyValue::Scale(_) => {}
+    }
+    Ok(())
+}
+
+fn walk_property_value(value: &PropertyValue) -> Result<(), Error> {
+    match value {
+        PropertyValue::PropValue(x) => walk_application_data_value(x)?,
+        PropertyValue::PropValueList(x) => {
+            for value in x {
+                walk_application_data_value(&value?)?;
+            }
+        }
+        PropertyValue::RecipientList(x) => {
+            for destination in x {
+                destination?;
+            }
+        }
+        PropertyValue::CovSubscriptionList(x) => {
+            for subscription in x {
+                subscription?;
+            }
+        }
+        PropertyValue::GroupMembers(x) => walk_read_property_multiple(x)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn walk_read_property_multiple(rpm: &ReadPropertyMultiple) -> Result<(), Error> {
+    for object in rpm {
+        for property_id in &object?.property_ids {
+            property_id?;
+        }
+    }
+    Ok(())
+}
+
+fn walk_application_data_value(value: &ApplicationDataValue) -> Result<(), Error> {
+    match value {
+        ApplicationDataValue::WeeklySchedule(x) => walk_weekly_schedule(x),
+        _ => Ok(()),
+    }
+}
+
+fn walk_weekly_schedule(schedule: &WeeklySchedule) -> Result<(), Error> {
+    for day in [
+        &schedule.monday,
+        &schedule.tuesday,
+        &schedule.wednesday,
+        &schedule.thursday,
+        &schedule.friday,
+        &schedule.saturday,
+        &schedule.sunday,
+    ] {
+        for time_value in day {
+            time_value?;
+        }
+    }
+    Ok(())
+}
@@ -4,7 +4,9 @@ use crate::common::{
 };
 
 use super::{
-    confirmed::{ComplexAck, ConfirmedBacnetError, ConfirmedRequest, SegmentAck, SimpleAck},
+    confirmed::{
+        Abort, ComplexAck, ConfirmedBacnetError, ConfirmedRequest, Reject, SegmentAck, SimpleAck,
+    },
     segment::Segment,
     unconfirmed::UnconfirmedRequest,
 };
@@ -20,7 +22,8 @@ pub enum ApplicationPdu<'a> {
     Error(ConfirmedBacnetError),
     Segment(Segment<'a>),
     SegmentAck(SegmentAck),
-    // add more here (see ApduType)
+    Reject(Reject),
+    Abort(Abort),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -99,6 +102,20 @@ pub enum MaxAdpu {
     _1476 = 0x05, // default
 }
 
+impl MaxAdpu {
+    // the largest apdu (in bytes) that this value represents
+    pub const fn size(&self) -> usize {
+        match self {
+            Self::_0 => 50,
+            Self::_128 => 128,
+            Self::_206 => 206,
+            Self::_480 => 480,
+            Self::_1024 => 1024,
+            Self::_1476 => 1476,
+        }
+    }
+}
+
 impl From<u8> for MaxAdpu {
     fn from(value: u8) -> Self {
         match value {
@@ -130,17 +147,48 @@ impl<'a> ApplicationPdu<'a> {
             Self::SimpleAck(ack) => ack.encode(writer),
             Self::SegmentAck(ack) => ack.encode(writer),
             Self::Segment(segment) => segment.encode(writer),
+            Self::Reject(reject) => reject.encode(writer),
+            Self::Abort(abort) => abort.encode(writer),
             Self::Error(_) => todo!(),
         };
     }
 
+    // the invoke id of the confirmed request this pdu belongs to (None for unconfirmed requests)
+    pub fn invoke_id(&self) -> Option<u8> {
+        match self {
+            Self::ConfirmedRequest(x) => Some(x.invoke_id),
+            Self::UnconfirmedRequest(_) => None,
+            Self::ComplexAck(x) => Some(x.invoke_id),
+            Self::SimpleAck(x) => Some(x.invoke_id),
+            Self::Error(x) => Some(x.invoke_id),
+            Self::Segment(x) => Some(x.invoke_id),
+            Self::SegmentAck(x) => Some(x.invoke_id),
+            Self::Reject(x) => Some(x.invoke_id),
+            Self::Abort(x) => Some(x.invoke_id),
+        }
+    }
+
+    // the expecting-reply bit of the npdu that carries this pdu
+    // set for confirmed requests and segments of a complex ack (which are answered with a SegmentAck)
+    pub fn expects_reply(&self) -> bool {
+        matches!(self, Self::ConfirmedRequest(_) | Self::Segment(_))
+    }
+
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         let byte0 = reader.read_byte(buf)?;
         let pdu_type: ApduType = (byte0 >> 4).try_into()?;
         let pdu_flags = byte0 & 0x0F;
-        let segmented_message = (pdu_flags & PduFlags::SegmentedMessage as u8) > 0;
+        let segmented_message = (pdu_flags & PduFlags::SegmentedMessage as u8) > 0
+            && matches!(
+                pdu_type,
+                ApduType::ConfirmedServiceRequest | ApduType::ComplexAck
+            );
         let more_follows = (pdu_flags & PduFlags::MoreFollows as u8) > 0;
-        let _segmented_response_accepted =
+        let segmented_response_accepted =
             (pdu_flags & PduFlags::SegmentedResponseAccepted as u8) > 0;
 
         if segmented_message {
@@ -150,7 +198,8 @@ impl<'a> ApplicationPdu<'a> {
 
         match pdu_type {
             ApduType::ConfirmedServiceRequest => {
-                let apdu = ConfirmedRequest::decode(reader, buf)?;
+                let mut apdu = ConfirmedRequest::decode(reader, buf)?;
+                apdu.segmented_response_accepted = segmented_response_accepted;
                 Ok(Self::ConfirmedRequest(apdu))
             }
             ApduType::UnconfirmedServiceRequest => {
@@ -166,14 +215,115 @@ impl<'a> ApplicationPdu<'a> {
                 Ok(Self::SimpleAck(adpu))
             }
             ApduType::SegmentAck => {
-                let adpu = SegmentAck::decode(reader, buf)?;
+                let negative = (pdu_flags & SegmentAck::NEGATIVE_FLAG) > 0;
+                let server = (pdu_flags & PduFlags::Server as u8) > 0;
+                let adpu = SegmentAck::decode(negative, server, reader, buf)?;
                 Ok(Self::SegmentAck(adpu))
             }
             ApduType::Error => {
                 let apdu = ConfirmedBacnetError::decode(reader, buf)?;
                 Ok(Self::Error(apdu))
             }
-            apdu_type => Err(Error::ApduTypeNotSupported(apdu_type)),
+            ApduType::Reject => {
+                let apdu = Reject::decode(reader, buf)?;
+                Ok(Self::Reject(apdu))
+            }
+            ApduType::Abort => {
+                let server = (pdu_flags & PduFlags::Server as u8) > 0;
+                let apdu = Abort::decode(server, reader, buf)?;
+                Ok(Self::Abort(apdu))
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{confirmed::ComplexAckService, unconfirmed::UnconfirmedRequest},
+        common::{
+            io::Reader,
+            spec::{AbortReason, RejectReason},
+        },
+    };
+
+    use super::ApplicationPdu;
+
+    fn decode(input: &[u8]) -> ApplicationPdu<'_> {
+        let mut reader = Reader::new_with_len(input.len());
+        ApplicationPdu::decode(&mut reader, input).unwrap()
+    }
+
+    #[test]
+    fn decode_reject_and_abort() {
+        // reject: unrecognized service
+        let apdu = decode(&[0x60, 0x05, 0x09]);
+        assert_eq!(apdu.invoke_id(), Some(5));
+        match apdu {
+            ApplicationPdu::Reject(x) => assert_eq!(x.reason, RejectReason::UnrecognizedService),
+            x => panic!("expected reject: {:?}", x),
+        }
+
+        // abort from server: segmentation not supported
+        let apdu = decode(&[0x71, 0x06, 0x04]);
+        assert_eq!(apdu.invoke_id(), Some(6));
+        match apdu {
+            ApplicationPdu::Abort(x) => {
+                assert!(x.server);
+                assert_eq!(x.reason, AbortReason::SegmentationNotSupported);
+            }
+            x => panic!("expected abort: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn decode_reserved_reject_and_abort_reasons() {
+        let apdu = decode(&[0x60, 0x05, 0x28]);
+        match apdu {
+            ApplicationPdu::Reject(x) => {
+                assert_eq!(x.reason, RejectReason::Other(40));
+                assert_eq!(u8::from(x.reason), 40);
+            }
+            x => panic!("expected reject: {:?}", x),
+        }
+
+        let apdu = decode(&[0x70, 0x06, 0x0C]);
+        match apdu {
+            ApplicationPdu::Abort(x) => {
+                assert_eq!(x.reason, AbortReason::Other(12));
+                assert_eq!(u8::from(x.reason), 12);
+            }
+            x => panic!("expected abort: {:?}", x),
+        }
+
+        assert_eq!(RejectReason::from(0), RejectReason::Other(0));
+        assert_eq!(AbortReason::from(64), AbortReason::Proprietary(64));
+    }
+
+    #[test]
+    fn bare_apdu_with_default_reader() {
+        // without a bvlc header there is no length to bound the reader with (found by fuzzing)
+        let input = [0x30, 0xC5, 0x0E, 0x0C, 0x0F, 0x0B, 0x00, 0x01, 0x54, 0x1F];
+        let mut reader = Reader::default();
+        let apdu = ApplicationPdu::decode(&mut reader, &input).unwrap();
+        let ApplicationPdu::ComplexAck(ack) = apdu else {
+            panic!("expected a complex ack: {:?}", apdu);
+        };
+        let ComplexAckService::ReadPropertyMultiple(ack) = &ack.service else {
+            panic!("expected a read property multiple ack: {:?}", ack);
+        };
+        // the garbage is only found when the results are decoded
+        assert!(ack.into_iter().next().unwrap().is_err());
+
+        let input = [0x10, 0x0F, 0x01, 0x02];
+        let mut reader = Reader::default();
+        let apdu = ApplicationPdu::decode(&mut reader, &input).unwrap();
+        assert!(matches!(
+            apdu,
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::Unknown {
+                choice: 15,
+                data: [0x01, 0x02]
+            })
+        ));
+    }
+}
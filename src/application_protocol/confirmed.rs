@@ -1,9 +1,10 @@
 use crate::{
     common::{
         error::{Error, Unimplemented},
-        helper::decode_unsigned,
+        helper::{decode_constructed_value, decode_unsigned},
         io::{Reader, Writer},
-        spec::{ErrorClass, ErrorCode},
+        object_property_reference::ObjectPropertyReference,
+        spec::{AbortReason, ErrorClass, ErrorCode, RejectReason},
         tag::{ApplicationTagNumber, Tag, TagNumber},
     },
     network_protocol::{data_link::DataLink, network_pdu::NetworkMessage},
@@ -12,10 +13,13 @@ use crate::{
 use super::{
     application_pdu::{ApduType, ApplicationPdu, MaxAdpu, MaxSegments, PduFlags},
     services::{
-        change_of_value::SubscribeCov,
+        change_of_value::{CovNotification, SubscribeCov},
+        event_notification::EventNotification,
         read_property::{ReadProperty, ReadPropertyAck},
+        read_property_conditional::ReadPropertyConditional,
         read_property_multiple::{ReadPropertyMultiple, ReadPropertyMultipleAck},
         read_range::{ReadRange, ReadRangeAck},
+        virtual_terminal::{VtClose, VtData, VtDataAck, VtOpen, VtOpenAck},
         write_property::WriteProperty,
     },
 };
@@ -23,17 +27,19 @@ use super::{
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ConfirmedRequest<'a> {
-    pub max_segments: MaxSegments, // default 65
-    pub max_adpu: MaxAdpu,         // default 1476
-    pub invoke_id: u8,             // starts at 0
-    pub sequence_num: u8,          // default to 0
-    pub proposed_window_size: u8,  // default to 0
+    pub segmented_response_accepted: bool, // default true
+    pub max_segments: MaxSegments,         // default 65
+    pub max_adpu: MaxAdpu,                 // default 1476
+    pub invoke_id: u8,                     // starts at 0
+    pub sequence_num: u8,                  // default to 0
+    pub proposed_window_size: u8,          // default to 0
     pub service: ConfirmedRequestService<'a>,
 }
 
 impl<'a> ConfirmedRequest<'a> {
     pub fn new(invoke_id: u8, service: ConfirmedRequestService<'a>) -> Self {
         Self {
+            segmented_response_accepted: true,
             max_segments: MaxSegments::_65,
             max_adpu: MaxAdpu::_1476,
             invoke_id,
@@ -43,43 +49,63 @@ impl<'a> ConfirmedRequest<'a> {
         }
     }
 
+    // advertise a smaller max apdu for devices with small buffers (e.g. 480 bytes on MS/TP)
+    pub fn with_max_adpu(mut self, max_adpu: MaxAdpu) -> Self {
+        self.max_adpu = max_adpu;
+        self
+    }
+
+    // MaxSegments::_0 also means that segmented responses are not accepted
+    // use with_segmented_response_accepted afterwards to accept an unspecified number of segments
+    pub fn with_max_segments(mut self, max_segments: MaxSegments) -> Self {
+        self.segmented_response_accepted = !matches!(max_segments, MaxSegments::_0);
+        self.max_segments = max_segments;
+        self
+    }
+
+    // large reads (e.g. ReadPropertyMultiple of many objects) need this so that the device
+    // segments the response instead of aborting with segmentation-not-supported
+    pub fn with_segmented_response_accepted(mut self, accepted: bool) -> Self {
+        self.segmented_response_accepted = accepted;
+        self
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
-        let max_segments_flag = match self.max_segments {
-            MaxSegments::_0 => 0,
-            _ => PduFlags::SegmentedResponseAccepted as u8,
+        let segmented_response_accepted_flag = if self.segmented_response_accepted {
+            PduFlags::SegmentedResponseAccepted as u8
+        } else {
+            0
         };
 
-        let control = ((ApduType::ConfirmedServiceRequest as u8) << 4) | max_segments_flag;
+        let control =
+            ((ApduType::ConfirmedServiceRequest as u8) << 4) | segmented_response_accepted_flag;
         writer.push(control);
         writer.push(self.max_segments.clone() as u8 | self.max_adpu.clone() as u8);
         writer.push(self.invoke_id);
 
         // NOTE: Segment pdu not supported / implemented
+        writer.push(self.service.choice() as u8);
         match &self.service {
-            ConfirmedRequestService::ReadProperty(service) => {
-                writer.push(ConfirmedServiceChoice::ReadProperty as u8);
-                service.encode(writer)
-            }
-            ConfirmedRequestService::ReadPropertyMultiple(service) => {
-                writer.push(ConfirmedServiceChoice::ReadPropMultiple as u8);
-                service.encode(writer)
-            }
-            ConfirmedRequestService::SubscribeCov(service) => {
-                writer.push(ConfirmedServiceChoice::SubscribeCov as u8);
-                service.encode(writer)
-            }
-            ConfirmedRequestService::WriteProperty(service) => {
-                writer.push(ConfirmedServiceChoice::WriteProperty as u8);
-                service.encode(writer)
-            }
-            ConfirmedRequestService::ReadRange(service) => {
-                writer.push(ConfirmedServiceChoice::ReadRange as u8);
-                service.encode(writer)
-            }
+            ConfirmedRequestService::ReadProperty(service) => service.encode(writer),
+            ConfirmedRequestService::ReadPropertyMultiple(service) => service.encode(writer),
+            ConfirmedRequestService::SubscribeCov(service) => service.encode(writer),
+            ConfirmedRequestService::WriteProperty(service) => service.encode(writer),
+            ConfirmedRequestService::ReadRange(service) => service.encode(writer),
+            ConfirmedRequestService::CovNotification(service) => service.encode(writer),
+            ConfirmedRequestService::EventNotification(service) => service.encode(writer),
+            ConfirmedRequestService::ReadPropertyConditional(service) => service.encode(writer),
+            ConfirmedRequestService::VtOpen(service) => service.encode(writer),
+            ConfirmedRequestService::VtClose(service) => service.encode(writer),
+            ConfirmedRequestService::VtData(service) => service.encode(writer),
         };
     }
 
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
     // the control byte has already been read
+    // so segmented_response_accepted is false until ApplicationPdu::decode sets it from that byte
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         let byte0 = reader.read_byte(buf)?;
         let max_segments: MaxSegments = (byte0 & 0xF0).into();
@@ -92,6 +118,7 @@ impl<'a> ConfirmedRequest<'a> {
         let service = ConfirmedRequestService::decode(choice, reader, buf)?;
 
         Ok(Self {
+            segmented_response_accepted: false,
             max_segments,
             max_adpu,
             sequence_num: 0,
@@ -169,6 +196,34 @@ pub enum ConfirmedServiceChoice {
     MaxBacnetConfirmedService = 34,
 }
 
+impl ConfirmedServiceChoice {
+    // true if a successful response is a SimpleAck (no data) rather than a ComplexAck
+    pub fn expects_simple_ack(&self) -> bool {
+        matches!(
+            self,
+            Self::AcknowledgeAlarm
+                | Self::AuditNotification
+                | Self::CovNotification
+                | Self::CovNotificationMultiple
+                | Self::EventNotification
+                | Self::LifeSafetyOperation
+                | Self::SubscribeCov
+                | Self::SubscribeCovProperty
+                | Self::SubscribeCovPropertyMultiple
+                | Self::AddListElement
+                | Self::RemoveListElement
+                | Self::DeleteObject
+                | Self::WriteProperty
+                | Self::WritePropMultiple
+                | Self::DeviceCommunicationControl
+                | Self::TextMessage
+                | Self::ReinitializeDevice
+                | Self::VtClose
+                | Self::RequestKey
+        )
+    }
+}
+
 impl TryFrom<u8> for ConfirmedServiceChoice {
     type Error = u8;
 
@@ -228,6 +283,9 @@ impl<'a> TryFrom<DataLink<'a>> for SimpleAck {
         match value.npdu {
             Some(x) => match x.network_message {
                 NetworkMessage::Apdu(ApplicationPdu::SimpleAck(ack)) => Ok(ack),
+                NetworkMessage::Apdu(apdu) => Err(response_error(apdu).unwrap_or(
+                    Error::ConvertDataLink("npdu message is not an apdu simple ack"),
+                )),
                 _ => Err(Error::ConvertDataLink(
                     "npdu message is not an apdu simple ack",
                 )),
@@ -261,14 +319,84 @@ impl SimpleAck {
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Reject {
+    pub invoke_id: u8,
+    pub reason: RejectReason,
+}
+
+impl Reject {
+    pub fn encode(&self, writer: &mut Writer) {
+        let control = (ApduType::Reject as u8) << 4;
+        writer.push(control);
+        writer.push(self.invoke_id);
+        writer.push(self.reason.into());
+    }
+
+    // the control byte has already been read
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let invoke_id = reader.read_byte(buf)?;
+        let reason = reader.read_byte(buf)?.into();
+
+        Ok(Self { invoke_id, reason })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Abort {
+    pub server: bool, // true if sent by the server (the device responding to the request)
+    pub invoke_id: u8,
+    pub reason: AbortReason,
+}
+
+impl Abort {
+    // the server can't send a segmented response so the request has to be split up (e.g. one object per RPM)
+    pub fn is_segmentation_not_supported(&self) -> bool {
+        self.reason == AbortReason::SegmentationNotSupported
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        let server = if self.server {
+            PduFlags::Server as u8
+        } else {
+            0
+        };
+        let control = ((ApduType::Abort as u8) << 4) | server;
+        writer.push(control);
+        writer.push(self.invoke_id);
+        writer.push(self.reason.into());
+    }
+
+    // the control byte has already been read so the server flag is passed in
+    pub fn decode(server: bool, reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let invoke_id = reader.read_byte(buf)?;
+        let reason = reader.read_byte(buf)?.into();
+
+        Ok(Self {
+            server,
+            invoke_id,
+            reason,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfirmedBacnetError {
     pub invoke_id: u8,
     pub service_choice: ConfirmedServiceChoice,
     pub error_class: ErrorClass,
     pub error_code: ErrorCode,
+    // WritePropertyMultiple only: the write that failed
+    // the writes before it in the request were made (they are not rolled back) and the ones after it were not
+    pub first_failed_write: Option<ObjectPropertyReference>,
 }
 
 impl ConfirmedBacnetError {
+    const ERROR_TYPE_TAG: u8 = 0;
+    const FIRST_FAILED_WRITE_TAG: u8 = 1;
+
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
         let invoke_id = reader.read_byte(buf)?;
         let service_choice: ConfirmedServiceChoice =
@@ -279,6 +407,47 @@ impl ConfirmedBacnetError {
                 ))
             })?;
 
+        if let ConfirmedServiceChoice::WritePropMultiple = service_choice {
+            // WritePropertyMultiple-Error
+            let (error_class, error_code) = decode_constructed_value(
+                reader,
+                buf,
+                Self::ERROR_TYPE_TAG,
+                "ConfirmedBacnetError decode error type",
+                Self::decode_error_type,
+            )?;
+            let first_failed_write = decode_constructed_value(
+                reader,
+                buf,
+                Self::FIRST_FAILED_WRITE_TAG,
+                "ConfirmedBacnetError decode first failed write",
+                ObjectPropertyReference::decode,
+            )?;
+
+            return Ok(Self {
+                invoke_id,
+                service_choice,
+                error_class,
+                error_code,
+                first_failed_write: Some(first_failed_write),
+            });
+        }
+
+        let (error_class, error_code) = Self::decode_error_type(reader, buf)?;
+
+        Ok(Self {
+            invoke_id,
+            service_choice,
+            error_class,
+            error_code,
+            first_failed_write: None,
+        })
+    }
+
+    pub(crate) fn decode_error_type(
+        reader: &mut Reader,
+        buf: &[u8],
+    ) -> Result<(ErrorClass, ErrorCode), Error> {
         let tag = Tag::decode_expected(
             reader,
             buf,
@@ -299,12 +468,7 @@ impl ConfirmedBacnetError {
         let error_code =
             ErrorCode::try_from(value).map_err(|e| Error::InvalidVariant(("ErrorCode", e)))?;
 
-        Ok(Self {
-            invoke_id,
-            service_choice,
-            error_class,
-            error_code,
-        })
+        Ok((error_class, error_code))
     }
 }
 
@@ -322,6 +486,9 @@ impl<'a> TryFrom<DataLink<'a>> for ComplexAck<'a> {
         match value.npdu {
             Some(x) => match x.network_message {
                 NetworkMessage::Apdu(ApplicationPdu::ComplexAck(ack)) => Ok(ack),
+                NetworkMessage::Apdu(apdu) => Err(response_error(apdu).unwrap_or(
+                    Error::ConvertDataLink("npdu message is not an apdu complex ack"),
+                )),
                 _ => Err(Error::ConvertDataLink(
                     "npdu message is not an apdu complex ack",
                 )),
@@ -341,9 +508,21 @@ impl<'a> ComplexAck<'a> {
             ComplexAckService::ReadProperty(service) => service.encode(writer),
             ComplexAckService::ReadPropertyMultiple(service) => service.encode(writer),
             ComplexAckService::ReadRange(service) => service.encode(writer),
+            ComplexAckService::ReadPropertyConditional(service) => {
+                writer.push(ConfirmedServiceChoice::ReadPropConditional as u8);
+                for item in service.objects_with_results {
+                    item.encode(writer);
+                }
+            }
+            ComplexAckService::VtOpen(service) => service.encode(writer),
+            ComplexAckService::VtData(service) => service.encode(writer),
         }
     }
 
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         let invoke_id = reader.read_byte(buf)?;
         let choice: ConfirmedServiceChoice = reader.read_byte(buf)?.try_into().map_err(|e| {
@@ -361,6 +540,9 @@ pub enum ComplexAckService<'a> {
     ReadProperty(ReadPropertyAck<'a>),
     ReadPropertyMultiple(ReadPropertyMultipleAck<'a>),
     ReadRange(ReadRangeAck<'a>),
+    ReadPropertyConditional(ReadPropertyMultipleAck<'a>), // same format as the ReadPropertyMultiple ack
+    VtOpen(VtOpenAck),
+    VtData(VtDataAck),
     // add more here
 }
 
@@ -376,7 +558,7 @@ impl<'a> ComplexAckService<'a> {
                 Ok(ComplexAckService::ReadProperty(service))
             }
             ConfirmedServiceChoice::ReadPropMultiple => {
-                let buf = &buf[reader.index..reader.end];
+                let buf = reader.remaining(buf);
                 let service = ReadPropertyMultipleAck::new_from_buf(buf);
                 Ok(ComplexAckService::ReadPropertyMultiple(service))
             }
@@ -384,6 +566,19 @@ impl<'a> ComplexAckService<'a> {
                 let service = ReadRangeAck::decode(reader, buf)?;
                 Ok(ComplexAckService::ReadRange(service))
             }
+            ConfirmedServiceChoice::ReadPropConditional => {
+                let buf = reader.remaining(buf);
+                let service = ReadPropertyMultipleAck::new_from_buf(buf);
+                Ok(ComplexAckService::ReadPropertyConditional(service))
+            }
+            ConfirmedServiceChoice::VtOpen => {
+                let service = VtOpenAck::decode(reader, buf)?;
+                Ok(ComplexAckService::VtOpen(service))
+            }
+            ConfirmedServiceChoice::VtData => {
+                let service = VtDataAck::decode(reader, buf)?;
+                Ok(ComplexAckService::VtData(service))
+            }
             s => Err(Error::Unimplemented(Unimplemented::ConfirmedServiceChoice(
                 s,
             ))),
@@ -397,12 +592,38 @@ pub enum ConfirmedRequestService<'a> {
     ReadProperty(ReadProperty),
     ReadPropertyMultiple(ReadPropertyMultiple<'a>),
     SubscribeCov(SubscribeCov),
+    CovNotification(CovNotification<'a>), // answer with a SimpleAck (see CovNotification::ack)
+    EventNotification(EventNotification<'a>), // answer with a SimpleAck (see EventNotification::ack)
     WriteProperty(WriteProperty<'a>),
     ReadRange(ReadRange),
+    ReadPropertyConditional(ReadPropertyConditional<'a>), // encode only (deprecated service)
+    VtOpen(VtOpen),
+    VtClose(VtClose<'a>),
+    VtData(VtData<'a>),
     // add more here (see ConfirmedServiceChoice enum)
 }
 
 impl<'a> ConfirmedRequestService<'a> {
+    pub fn choice(&self) -> ConfirmedServiceChoice {
+        match self {
+            Self::ReadProperty(_) => ConfirmedServiceChoice::ReadProperty,
+            Self::ReadPropertyMultiple(_) => ConfirmedServiceChoice::ReadPropMultiple,
+            Self::SubscribeCov(_) => ConfirmedServiceChoice::SubscribeCov,
+            Self::CovNotification(_) => ConfirmedServiceChoice::CovNotification,
+            Self::EventNotification(_) => ConfirmedServiceChoice::EventNotification,
+            Self::WriteProperty(_) => ConfirmedServiceChoice::WriteProperty,
+            Self::ReadRange(_) => ConfirmedServiceChoice::ReadRange,
+            Self::ReadPropertyConditional(_) => ConfirmedServiceChoice::ReadPropConditional,
+            Self::VtOpen(_) => ConfirmedServiceChoice::VtOpen,
+            Self::VtClose(_) => ConfirmedServiceChoice::VtClose,
+            Self::VtData(_) => ConfirmedServiceChoice::VtData,
+        }
+    }
+
+    pub fn expects_simple_ack(&self) -> bool {
+        self.choice().expects_simple_ack()
+    }
+
     pub fn decode(
         choice: ConfirmedServiceChoice,
         reader: &mut Reader,
@@ -425,6 +646,26 @@ impl<'a> ConfirmedRequestService<'a> {
                 let service = WriteProperty::decode(reader, buf)?;
                 Ok(ConfirmedRequestService::WriteProperty(service))
             }
+            ConfirmedServiceChoice::CovNotification => {
+                let service = CovNotification::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::CovNotification(service))
+            }
+            ConfirmedServiceChoice::EventNotification => {
+                let service = EventNotification::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::EventNotification(service))
+            }
+            ConfirmedServiceChoice::VtOpen => {
+                let service = VtOpen::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::VtOpen(service))
+            }
+            ConfirmedServiceChoice::VtClose => {
+                let service = VtClose::decode(reader, buf);
+                Ok(ConfirmedRequestService::VtClose(service))
+            }
+            ConfirmedServiceChoice::VtData => {
+                let service = VtData::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::VtData(service))
+            }
             s => Err(Error::Unimplemented(Unimplemented::ConfirmedServiceChoice(
                 s,
             ))),
@@ -432,12 +673,25 @@ impl<'a> ConfirmedRequestService<'a> {
     }
 }
 
+// the reason a request failed if the response is an Error, Reject or Abort pdu
+// the caller must have checked that the response has the invoke id of its request (see `ApplicationPdu::invoke_id`)
+pub(crate) fn response_error(apdu: ApplicationPdu) -> Option<Error> {
+    match apdu {
+        ApplicationPdu::Error(x) => Some(Error::RequestFailed(x)),
+        ApplicationPdu::Reject(x) => Some(Error::Rejected(x.reason)),
+        ApplicationPdu::Abort(x) => Some(Error::Aborted(x.reason)),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct SegmentAck {
+    pub negative: bool, // true if a segment was missing or out of order (asks for a retransmit)
+    pub server: bool,   // true if sent by the server (the device sending the segmented response)
     pub invoke_id: u8,
-    pub sequence_num: u8,
-    pub proposed_window_size: u8,
+    pub sequence_number: u8, // the last segment received in order
+    pub actual_window_size: u8,
 }
 
 impl<'a> TryFrom<DataLink<'a>> for SegmentAck {
@@ -457,23 +711,301 @@ impl<'a> TryFrom<DataLink<'a>> for SegmentAck {
 }
 
 impl SegmentAck {
+    // the NAK bit shares its position with the segmented response accepted flag of a request
+    pub(crate) const NEGATIVE_FLAG: u8 = 0b0010;
+
+    // a positive ack sent by the client for the segments of a complex ack
+    pub fn new(invoke_id: u8, sequence_number: u8, actual_window_size: u8) -> Self {
+        Self {
+            negative: false,
+            server: false,
+            invoke_id,
+            sequence_number,
+            actual_window_size,
+        }
+    }
+
+    // asks the server to retransmit everything after `sequence_number` (the last segment received in order)
+    pub fn new_negative(invoke_id: u8, sequence_number: u8, actual_window_size: u8) -> Self {
+        Self {
+            negative: true,
+            ..Self::new(invoke_id, sequence_number, actual_window_size)
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
-        let control = (ApduType::SegmentAck as u8) << 4;
+        let mut control = (ApduType::SegmentAck as u8) << 4;
+        if self.negative {
+            control |= Self::NEGATIVE_FLAG;
+        }
+        if self.server {
+            control |= PduFlags::Server as u8;
+        }
         writer.push(control);
         writer.push(self.invoke_id);
-        writer.push(self.sequence_num);
-        writer.push(self.proposed_window_size);
+        writer.push(self.sequence_number);
+        writer.push(self.actual_window_size);
     }
 
-    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+    // the control byte has already been read so the flags are passed in
+    pub fn decode(
+        negative: bool,
+        server: bool,
+        reader: &mut Reader,
+        buf: &[u8],
+    ) -> Result<Self, Error> {
         let invoke_id = reader.read_byte(buf)?;
-        let sequence_num = reader.read_byte(buf)?;
-        let proposed_window_size = reader.read_byte(buf)?;
+        let sequence_number = reader.read_byte(buf)?;
+        let actual_window_size = reader.read_byte(buf)?;
 
         Ok(Self {
+            negative,
+            server,
             invoke_id,
-            sequence_num,
-            proposed_window_size,
+            sequence_number,
+            actual_window_size,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::{ApplicationPdu, MaxAdpu, MaxSegments},
+            services::{
+                read_property::ReadProperty,
+                read_property_multiple::{ReadAccessSpecification, ReadPropertyMultiple},
+            },
+        },
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{AbortReason, ErrorClass, ErrorCode, RejectReason},
+        },
+        network_protocol::{
+            data_link::{DataLink, DataLinkFunction},
+            network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+        },
+    };
+
+    use super::{
+        Abort, ComplexAck, ConfirmedBacnetError, ConfirmedRequest, ConfirmedRequestService,
+        ConfirmedServiceChoice, Reject, SegmentAck,
+    };
+
+    #[test]
+    fn small_max_adpu() {
+        let object_id = ObjectId::new(ObjectType::ObjectDevice, 1);
+        let service = ConfirmedRequestService::ReadProperty(ReadProperty::new(
+            object_id,
+            PropertyId::PropObjectName,
+        ));
+        let request = ConfirmedRequest::new(7, service)
+            .with_max_adpu(MaxAdpu::_480)
+            .with_max_segments(MaxSegments::_0);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+
+        // control (no segmentation accepted), max segments | max apdu, invoke id
+        assert_eq!(&writer.to_bytes()[..3], &[0x00, 0x03, 7]);
+    }
+
+    #[test]
+    fn reject_instead_of_ack() {
+        let apdu = ApplicationPdu::Reject(Reject {
+            invoke_id: 3,
+            reason: RejectReason::UnrecognizedService,
+        });
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+        let result: Result<ComplexAck, Error> = data_link.try_into();
+        assert!(matches!(
+            result,
+            Err(Error::Rejected(RejectReason::UnrecognizedService))
+        ));
+    }
+
+    #[test]
+    fn read_property_multiple_segmented_response_accepted() {
+        let property_ids = [PropertyId::PropObjectName, PropertyId::PropPresentValue];
+        let objects = [ReadAccessSpecification::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            &property_ids,
+        )];
+        let service =
+            ConfirmedRequestService::ReadPropertyMultiple(ReadPropertyMultiple::new(&objects));
+        let request = ConfirmedRequest::new(9, service)
+            .with_max_adpu(MaxAdpu::_480)
+            .with_max_segments(MaxSegments::_16);
+        let apdu = ApplicationPdu::ConfirmedRequest(request);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        apdu.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        // control (segmented response accepted), max segments | max apdu, invoke id, service choice
+        assert_eq!(&encoded[..4], &[0x02, 0x43, 9, 0x0E]);
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        match ApplicationPdu::decode(&mut reader, encoded).unwrap() {
+            ApplicationPdu::ConfirmedRequest(x) => {
+                assert!(x.segmented_response_accepted);
+                assert!(matches!(x.max_segments, MaxSegments::_16));
+            }
+            x => panic!("expected confirmed request: {:?}", x),
+        }
+
+        // accept an unspecified number of segments
+        let service =
+            ConfirmedRequestService::ReadPropertyMultiple(ReadPropertyMultiple::new(&objects));
+        let request = ConfirmedRequest::new(10, service)
+            .with_max_segments(MaxSegments::_0)
+            .with_segmented_response_accepted(true);
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[..3], &[0x02, 0x05, 10]);
+
+        // the flag is only in the control byte which ApplicationPdu::decode reads
+        let service =
+            ConfirmedRequestService::ReadPropertyMultiple(ReadPropertyMultiple::new(&objects));
+        let request = ConfirmedRequest::new(11, service).with_max_segments(MaxSegments::_0);
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(encoded[0], 0x00);
+        let mut reader = Reader::new_with_len(encoded.len());
+        match ApplicationPdu::decode(&mut reader, encoded).unwrap() {
+            ApplicationPdu::ConfirmedRequest(x) => assert!(!x.segmented_response_accepted),
+            x => panic!("expected confirmed request: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn write_property_multiple_error() {
+        // a WritePropertyMultiple to analog value 1 then analog value 2 where the write to
+        // slot 3 of the priority array of analog value 2 was denied (so only analog value 1 was written)
+        let input = [
+            0x07, // invoke id
+            0x10, // service choice
+            0x0E, 0x91, 0x02, 0x91, 0x28, 0x0F, // error type
+            0x1E, 0x0C, 0x00, 0x80, 0x00, 0x02, 0x19, 0x57, 0x29, 0x03,
+            0x1F, // first failed write
+        ];
+        let mut reader = Reader::new_with_len(input.len());
+        let error = ConfirmedBacnetError::decode(&mut reader, &input).unwrap();
+
+        assert_eq!(error.invoke_id, 7);
+        assert!(matches!(
+            error.service_choice,
+            ConfirmedServiceChoice::WritePropMultiple
+        ));
+        assert!(matches!(error.error_class, ErrorClass::Property));
+        assert!(matches!(error.error_code, ErrorCode::WriteAccessDenied));
+
+        let failed = error.first_failed_write.unwrap();
+        assert_eq!(
+            failed.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogValue, 2)
+        );
+        assert_eq!(failed.property_id, PropertyId::PropPriorityArray);
+        assert_eq!(failed.array_index, Some(3));
+        assert!(reader.eof());
+    }
+
+    #[test]
+    fn expected_ack_type() {
+        let object_id = ObjectId::new(ObjectType::ObjectDevice, 1);
+        let service = ConfirmedRequestService::ReadProperty(ReadProperty::new(
+            object_id,
+            PropertyId::PropObjectName,
+        ));
+        assert!(matches!(
+            service.choice(),
+            ConfirmedServiceChoice::ReadProperty
+        ));
+        assert!(!service.expects_simple_ack());
+
+        assert!(ConfirmedServiceChoice::WriteProperty.expects_simple_ack());
+        assert!(ConfirmedServiceChoice::SubscribeCov.expects_simple_ack());
+        assert!(ConfirmedServiceChoice::ReinitializeDevice.expects_simple_ack());
+        assert!(!ConfirmedServiceChoice::ReadPropMultiple.expects_simple_ack());
+        assert!(!ConfirmedServiceChoice::AtomicReadFile.expects_simple_ack());
+        assert!(!ConfirmedServiceChoice::CreateObject.expects_simple_ack());
+    }
+
+    #[test]
+    fn segment_ack() {
+        let ack = SegmentAck::new(5, 7, 8);
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        ApplicationPdu::SegmentAck(ack).encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(encoded, [0x40, 0x05, 0x07, 0x08]);
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let ApplicationPdu::SegmentAck(decoded) =
+            ApplicationPdu::decode(&mut reader, encoded).unwrap()
+        else {
+            panic!("expected segment ack");
+        };
+        assert!(!decoded.negative);
+        assert!(!decoded.server);
+        assert_eq!(decoded.invoke_id, 5);
+        assert_eq!(decoded.sequence_number, 7);
+        assert_eq!(decoded.actual_window_size, 8);
+
+        // a negative ack from the server
+        let input = [0x43, 0x05, 0x06, 0x08];
+        let mut reader = Reader::new_with_len(input.len());
+        let ApplicationPdu::SegmentAck(decoded) =
+            ApplicationPdu::decode(&mut reader, &input).unwrap()
+        else {
+            panic!("expected segment ack");
+        };
+        assert!(decoded.negative);
+        assert!(decoded.server);
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), input);
+    }
+
+    #[test]
+    fn segmentation_not_supported_abort() {
+        // abort from the server for invoke id 3
+        let input = [0x81, 0x0A, 0x00, 0x09, 0x01, 0x00, 0x71, 0x03, 0x04];
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &input).unwrap();
+        match message.apdu() {
+            Some(ApplicationPdu::Abort(abort)) => {
+                assert_eq!(abort.invoke_id, 3);
+                assert!(abort.is_segmentation_not_supported());
+            }
+            x => panic!("expected abort: {:?}", x),
+        }
+
+        // a client waiting for an ack gets an error it can fall back on
+        let error = ComplexAck::try_from(message).unwrap_err();
+        assert!(error.is_segmentation_not_supported());
+        assert!(matches!(
+            error,
+            Error::Aborted(AbortReason::SegmentationNotSupported)
+        ));
+
+        let other = Abort {
+            server: true,
+            invoke_id: 3,
+            reason: AbortReason::BufferOverflow,
+        };
+        assert!(!other.is_segmentation_not_supported());
+        assert!(!Error::Aborted(AbortReason::BufferOverflow).is_segmentation_not_supported());
+    }
+}
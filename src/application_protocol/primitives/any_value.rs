@@ -0,0 +1,323 @@
+use alloc::{string::String, vec::Vec};
+use core::str::from_utf8;
+
+use crate::common::{
+    error::Error,
+    helper::{check_len, decode_signed_i64, decode_unsigned, MAX_NESTING_DEPTH},
+    io::Reader,
+    object_id::ObjectId,
+    tag::{ApplicationTagNumber, Tag, TagNumber},
+};
+
+use super::data_value::{Date, Time};
+
+// A property value decoded without knowing its type in advance (e.g. for a generic object browser).
+// Primitive values become leaves and constructed values (opening and closing context tags) become
+// nested lists. Context specific primitives can't be interpreted without the type so their bytes are kept.
+#[derive(Debug, Clone)]
+pub enum OwnedValue {
+    Null,
+    Boolean(bool),
+    UnsignedInt(u64),
+    SignedInt(i64),
+    Real(f32),
+    Double(f64),
+    OctetString(Vec<u8>),
+    CharacterString(String),
+    BitString {
+        unused_bits: u8,
+        bits: Vec<u8>,
+    },
+    Enumerated(u32),
+    Date(Date),
+    Time(Time),
+    ObjectId(ObjectId),
+    Context {
+        tag_number: u8,
+        bytes: Vec<u8>,
+    },
+    Constructed {
+        tag_number: u8,
+        values: Vec<OwnedValue>,
+    },
+    // reserved application tags and character sets other than utf8
+    Unrecognised {
+        tag_number: u8,
+        bytes: Vec<u8>,
+    },
+}
+
+// decodes a single value (and everything nested inside it if it is constructed)
+pub fn decode_any_value(reader: &mut Reader, buf: &[u8]) -> Result<OwnedValue, Error> {
+    decode_any_value_with_max_depth(reader, buf, MAX_NESTING_DEPTH)
+}
+
+// returns Error::NestingTooDeep if constructed values are nested more than max_depth times
+// (lower the limit on devices with a small stack)
+pub fn decode_any_value_with_max_depth(
+    reader: &mut Reader,
+    buf: &[u8],
+    max_depth: usize,
+) -> Result<OwnedValue, Error> {
+    decode_any_value_inner(reader, buf, 0, max_depth)
+}
+
+// decodes values until the end of the reader (e.g. the body of a ReadPropertyAck property value)
+pub fn decode_any_values(reader: &mut Reader, buf: &[u8]) -> Result<Vec<OwnedValue>, Error> {
+    decode_any_values_with_max_depth(reader, buf, MAX_NESTING_DEPTH)
+}
+
+// the limit applies to each value in turn
+pub fn decode_any_values_with_max_depth(
+    reader: &mut Reader,
+    buf: &[u8],
+    max_depth: usize,
+) -> Result<Vec<OwnedValue>, Error> {
+    let mut values = Vec::new();
+    while !reader.eof() {
+        values.push(decode_any_value_with_max_depth(reader, buf, max_depth)?);
+    }
+    Ok(values)
+}
+
+fn decode_any_value_inner(
+    reader: &mut Reader,
+    buf: &[u8],
+    depth: usize,
+    max_depth: usize,
+) -> Result<OwnedValue, Error> {
+    let tag = Tag::decode(reader, buf)?;
+    match tag.number {
+        TagNumber::Application(tag_number) => {
+            decode_application(tag_number, tag.value, reader, buf)
+        }
+        TagNumber::ContextSpecific(tag_number) => {
+            let bytes = reader.read_slice(tag.value as usize, buf)?.to_vec();
+            Ok(OwnedValue::Context { tag_number, bytes })
+        }
+        TagNumber::ContextSpecificOpening(tag_number) => {
+            if depth >= max_depth {
+                return Err(Error::NestingTooDeep);
+            }
+
+            let mut values = Vec::new();
+            loop {
+                let index = reader.index;
+                let tag = Tag::decode(reader, buf)?;
+                match tag.number {
+                    TagNumber::ContextSpecificClosing(x) if x == tag_number => break,
+                    TagNumber::ContextSpecificClosing(x) => {
+                        return Err(Error::TagNotSupported((
+                            "decode_any_value mismatched closing tag",
+                            TagNumber::ContextSpecificClosing(x),
+                        )))
+                    }
+                    _ => {
+                        reader.index = index;
+                        values.push(decode_any_value_inner(reader, buf, depth + 1, max_depth)?);
+                    }
+                }
+            }
+            Ok(OwnedValue::Constructed { tag_number, values })
+        }
+        number => Err(Error::TagNotSupported((
+            "decode_any_value unexpected closing tag",
+            number,
+        ))),
+    }
+}
+
+fn decode_application(
+    tag_number: ApplicationTagNumber,
+    len: u32,
+    reader: &mut Reader,
+    buf: &[u8],
+) -> Result<OwnedValue, Error> {
+    let value = match tag_number {
+        ApplicationTagNumber::Null => OwnedValue::Null,
+        ApplicationTagNumber::Boolean => OwnedValue::Boolean(len > 0),
+        ApplicationTagNumber::UnsignedInt => {
+            OwnedValue::UnsignedInt(decode_unsigned(len, reader, buf)?)
+        }
+        ApplicationTagNumber::SignedInt => {
+            OwnedValue::SignedInt(decode_signed_i64(len, reader, buf)?)
+        }
+        ApplicationTagNumber::Real => {
+            check_len(len, 4, "decode_any_value real")?;
+            OwnedValue::Real(f32::from_be_bytes(reader.read_bytes(buf)?))
+        }
+        ApplicationTagNumber::Double => {
+            check_len(len, 8, "decode_any_value double")?;
+            OwnedValue::Double(f64::from_be_bytes(reader.read_bytes(buf)?))
+        }
+        ApplicationTagNumber::OctetString => {
+            OwnedValue::OctetString(reader.read_slice(len as usize, buf)?.to_vec())
+        }
+        ApplicationTagNumber::CharacterString => {
+            let bytes = reader.read_slice(len as usize, buf)?;
+            match bytes.split_first() {
+                None => OwnedValue::CharacterString(String::new()),
+                Some((0, text)) => match from_utf8(text) {
+                    Ok(text) => OwnedValue::CharacterString(text.into()),
+                    Err(_) => unrecognised(tag_number, bytes),
+                },
+                Some(_) => unrecognised(tag_number, bytes),
+            }
+        }
+        ApplicationTagNumber::BitString => {
+            let bytes = reader.read_slice(len as usize, buf)?;
+            match bytes.split_first() {
+                Some((unused_bits, bits)) => OwnedValue::BitString {
+                    unused_bits: *unused_bits,
+                    bits: bits.to_vec(),
+                },
+                None => return Err(Error::Length(("decode_any_value bit string", len))),
+            }
+        }
+        ApplicationTagNumber::Enumerated => {
+            OwnedValue::Enumerated(decode_unsigned(len, reader, buf)? as u32)
+        }
+        ApplicationTagNumber::Date => {
+            check_len(len, Date::LEN, "decode_any_value date")?;
+            OwnedValue::Date(Date::decode(reader, buf)?)
+        }
+        ApplicationTagNumber::Time => {
+            check_len(len, Time::LEN, "decode_any_value time")?;
+            OwnedValue::Time(Time::decode(reader, buf)?)
+        }
+        ApplicationTagNumber::ObjectId => OwnedValue::ObjectId(ObjectId::decode(len, reader, buf)?),
+        ApplicationTagNumber::Reserve1
+        | ApplicationTagNumber::Reserve2
+        | ApplicationTagNumber::Reserve3 => {
+            unrecognised(tag_number, reader.read_slice(len as usize, buf)?)
+        }
+    };
+
+    Ok(value)
+}
+
+fn unrecognised(tag_number: ApplicationTagNumber, bytes: &[u8]) -> OwnedValue {
+    OwnedValue::Unrecognised {
+        tag_number: tag_number as u8,
+        bytes: bytes.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        error::Error,
+        helper::{encode_closing_tag, encode_context_unsigned, encode_opening_tag},
+        io::{Reader, Writer},
+    };
+
+    use super::{
+        decode_any_value, decode_any_value_with_max_depth, decode_any_values,
+        decode_any_values_with_max_depth, OwnedValue,
+    };
+
+    #[test]
+    fn nested_values() {
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        writer.extend_from_slice(&[0x44, 0x41, 0xAC, 0x00, 0x00]); // real 21.5
+        encode_opening_tag(&mut writer, 0);
+        encode_context_unsigned(&mut writer, 1, 300);
+        encode_opening_tag(&mut writer, 2);
+        writer.extend_from_slice(&[0x73, 0x00, b'h', b'i']); // character string "hi"
+        encode_closing_tag(&mut writer, 2);
+        encode_closing_tag(&mut writer, 0);
+        writer.extend_from_slice(&[0xD2, 0xAA, 0xBB]); // reserved application tag 13
+        writer.extend_from_slice(&[0x73, 0x04, b'h', b'i']); // ucs-2 character string
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let values = decode_any_values(&mut reader, encoded).unwrap();
+        assert_eq!(values.len(), 4);
+        assert!(matches!(values[0], OwnedValue::Real(x) if x == 21.5));
+
+        let OwnedValue::Constructed {
+            tag_number: 0,
+            values: inner,
+        } = &values[1]
+        else {
+            panic!("expected a constructed value: {:?}", values[1]);
+        };
+        assert!(matches!(
+            &inner[0],
+            OwnedValue::Context { tag_number: 1, bytes } if bytes.as_slice() == [0x01, 0x2C]
+        ));
+        assert!(matches!(
+            &inner[1],
+            OwnedValue::Constructed { tag_number: 2, values }
+                if matches!(&values[..], [OwnedValue::CharacterString(x)] if x == "hi")
+        ));
+
+        assert!(matches!(
+            &values[2],
+            OwnedValue::Unrecognised { tag_number: 13, bytes } if bytes.as_slice() == [0xAA, 0xBB]
+        ));
+        assert!(matches!(
+            &values[3],
+            OwnedValue::Unrecognised { tag_number: 7, bytes } if bytes.len() == 3
+        ));
+    }
+
+    #[test]
+    fn malformed_values_are_errors() {
+        // missing closing tag
+        let input = [0x0E, 0x21, 0x05];
+        let mut reader = Reader::new_with_len(input.len());
+        assert!(decode_any_value(&mut reader, &input).is_err());
+
+        // mismatched closing tag
+        let input = [0x0E, 0x21, 0x05, 0x1F];
+        let mut reader = Reader::new_with_len(input.len());
+        assert!(matches!(
+            decode_any_value(&mut reader, &input),
+            Err(Error::TagNotSupported(_))
+        ));
+
+        // nested too deeply
+        let input = [0x0E; 64];
+        let mut reader = Reader::new_with_len(input.len());
+        assert!(matches!(
+            decode_any_value(&mut reader, &input),
+            Err(Error::NestingTooDeep)
+        ));
+
+        // deep enough to overflow the stack without the limit
+        let input = alloc::vec![0x0E; 1_000_000];
+        let mut reader = Reader::new_with_len(input.len());
+        assert!(matches!(
+            decode_any_values(&mut reader, &input),
+            Err(Error::NestingTooDeep)
+        ));
+    }
+
+    #[test]
+    fn configurable_max_depth() {
+        // three levels of nesting around an unsigned int
+        let input = [0x0E, 0x1E, 0x2E, 0x21, 0x05, 0x2F, 0x1F, 0x0F];
+        let mut reader = Reader::new_with_len(input.len());
+        assert!(decode_any_value_with_max_depth(&mut reader, &input, 3).is_ok());
+
+        let mut reader = Reader::new_with_len(input.len());
+        assert!(matches!(
+            decode_any_value_with_max_depth(&mut reader, &input, 2),
+            Err(Error::NestingTooDeep)
+        ));
+
+        // the limit applies to each value rather than the list
+        let input = [0x0E, 0x1E, 0x1F, 0x0F, 0x0E, 0x1E, 0x1F, 0x0F];
+        let mut reader = Reader::new_with_len(input.len());
+        let values = decode_any_values_with_max_depth(&mut reader, &input, 2).unwrap();
+        assert_eq!(values.len(), 2);
+
+        let mut reader = Reader::new_with_len(input.len());
+        assert!(matches!(
+            decode_any_values_with_max_depth(&mut reader, &input, 1),
+            Err(Error::NestingTooDeep)
+        ));
+    }
+}
@@ -1,14 +1,22 @@
 use core::{fmt::Display, str::from_utf8};
 
+use crate::application_protocol::{
+    confirmed::ConfirmedServiceChoice, unconfirmed::UnconfirmedServiceChoice,
+};
 use crate::common::{
     daily_schedule::WeeklySchedule,
     error::Error,
-    helper::{decode_unsigned, encode_application_enumerated},
+    helper::{
+        check_len, decode_signed, decode_signed_i64, decode_unsigned,
+        encode_application_enumerated, encode_application_octet_string, encode_application_signed,
+        encode_application_signed_i64, encode_application_unsigned,
+    },
     io::{Reader, Writer},
     object_id::{ObjectId, ObjectType},
     property_id::PropertyId,
     spec::{
-        Binary, EngineeringUnits, EventState, LogBufferResult, LoggingType, NotifyType, Status,
+        Binary, DeviceStatus, EngineeringUnits, EventState, EventTransitionBits, LimitEnable,
+        LogBufferResult, LoggingType, NotifyType, Polarity, Reliability, Status,
     },
     tag::{ApplicationTagNumber, Tag, TagNumber},
 };
@@ -16,6 +24,7 @@ use crate::common::{
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ApplicationDataValue<'a> {
+    Null,
     Boolean(bool),
     Real(f32),
     Double(f64),
@@ -23,9 +32,13 @@ pub enum ApplicationDataValue<'a> {
     Time(Time),
     ObjectId(ObjectId),
     CharacterString(CharacterString<'a>),
+    OctetString(&'a [u8]),
     Enumerated(Enumerated),
     BitString(BitString<'a>),
     UnsignedInt(u32),
+    BigUnsignedInt(u64), // only used for values that don't fit in a u32
+    SignedInt(i32),
+    BigSignedInt(i64), // only used for values that don't fit in an i32
     WeeklySchedule(WeeklySchedule<'a>),
 }
 
@@ -35,6 +48,9 @@ pub enum ApplicationDataValueWrite<'a> {
     Boolean(bool),
     Enumerated(Enumerated),
     Real(f32),
+    UnsignedInt(u32),
+    SignedInt(i32),
+    CharacterString(CharacterString<'a>),
     WeeklySchedule(WeeklySchedule<'a>),
 }
 
@@ -48,21 +64,31 @@ pub enum Enumerated {
     EventState(EventState),
     NotifyType(NotifyType),
     LoggingType(LoggingType),
+    Reliability(Reliability),
+    Polarity(Polarity),
+    DeviceStatus(DeviceStatus),
     Unknown(u32),
 }
 
 impl Enumerated {
-    pub fn encode(&self, writer: &mut Writer) {
-        let value = match self {
+    // the raw value as sent on the wire (including values that didn't map to a known variant)
+    pub fn value(&self) -> u32 {
+        match self {
             Self::Units(x) => x.clone() as u32,
             Self::Binary(x) => x.clone() as u32,
-            Self::ObjectType(x) => *x as u32,
+            Self::ObjectType(x) => (*x).into(),
             Self::EventState(x) => x.clone() as u32,
             Self::NotifyType(x) => x.clone() as u32,
             Self::LoggingType(x) => x.clone() as u32,
+            Self::Reliability(x) => (*x).into(),
+            Self::Polarity(x) => (*x).into(),
+            Self::DeviceStatus(x) => (*x).into(),
             Self::Unknown(x) => *x,
-        };
-        encode_application_enumerated(writer, value);
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_enumerated(writer, self.value());
     }
 }
 
@@ -78,6 +104,57 @@ pub struct Date {
 
 impl Date {
     pub const LEN: u32 = 4; // 4 bytes
+    pub const UNSPECIFIED: u8 = 0xFF; // wildcard value for month, day and wday
+    pub const UNSPECIFIED_YEAR: u16 = 1900 + 255;
+
+    pub fn new(year: u16, month: u8, day: u8, wday: u8) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            wday,
+        }
+    }
+
+    // calculates the day of the week from a calendar date using Zeller's congruence
+    // the day of the week is unspecified if any field is a wildcard or a special value
+    // (e.g. month 13 for odd months or day 32 for the last day of the month)
+    pub fn from_ymd(year: u16, month: u8, day: u8) -> Self {
+        if year == Self::UNSPECIFIED_YEAR || !(1..=12).contains(&month) || !(1..=31).contains(&day)
+        {
+            return Self::new(year, month, day, Self::UNSPECIFIED);
+        }
+
+        // january and february are counted as months 13 and 14 of the previous year
+        let (y, m) = if month < 3 {
+            ((year as u32).saturating_sub(1), month as u32 + 12)
+        } else {
+            (year as u32, month as u32)
+        };
+        let k = y % 100;
+        let j = y / 100;
+        let h = (day as u32 + 13 * (m + 1) / 5 + k + k / 4 + j / 4 + 5 * j) % 7; // 0 = Saturday
+
+        let wday = ((h + 5) % 7 + 1) as u8;
+        Self::new(year, month, day, wday)
+    }
+
+    // every field is a wildcard (e.g. an open ended date range)
+    pub fn unspecified() -> Self {
+        Self::new(
+            Self::UNSPECIFIED_YEAR,
+            Self::UNSPECIFIED,
+            Self::UNSPECIFIED,
+            Self::UNSPECIFIED,
+        )
+    }
+
+    pub fn is_unspecified(&self) -> bool {
+        self.year == Self::UNSPECIFIED_YEAR
+            && self.month == Self::UNSPECIFIED
+            && self.day == Self::UNSPECIFIED
+            && self.wday == Self::UNSPECIFIED
+    }
 
     //  year = years since 1900, wildcard=1900+255
     //  month 1=Jan
@@ -128,6 +205,58 @@ pub struct Time {
 
 impl Time {
     pub const LEN: u32 = 4; // 4 bytes
+    pub const UNSPECIFIED: u8 = 0xFF; // wildcard value for any field
+
+    pub fn new(hour: u8, minute: u8, second: u8, hundredths: u8) -> Self {
+        Self {
+            hour,
+            minute,
+            second,
+            hundredths,
+        }
+    }
+
+    // every field is a wildcard (e.g. "any time" in a schedule)
+    pub fn unspecified() -> Self {
+        Self::new(
+            Self::UNSPECIFIED,
+            Self::UNSPECIFIED,
+            Self::UNSPECIFIED,
+            Self::UNSPECIFIED,
+        )
+    }
+
+    pub fn is_unspecified(&self) -> bool {
+        self.hour == Self::UNSPECIFIED
+            && self.minute == Self::UNSPECIFIED
+            && self.second == Self::UNSPECIFIED
+            && self.hundredths == Self::UNSPECIFIED
+    }
+
+    // the following return None if the field is a wildcard
+    pub fn hour_opt(&self) -> Option<u8> {
+        Self::specified(self.hour)
+    }
+
+    pub fn minute_opt(&self) -> Option<u8> {
+        Self::specified(self.minute)
+    }
+
+    pub fn second_opt(&self) -> Option<u8> {
+        Self::specified(self.second)
+    }
+
+    pub fn hundredths_opt(&self) -> Option<u8> {
+        Self::specified(self.hundredths)
+    }
+
+    fn specified(value: u8) -> Option<u8> {
+        if value == Self::UNSPECIFIED {
+            None
+        } else {
+            Some(value)
+        }
+    }
 
     // assuming that this comes from a Time tag
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
@@ -158,6 +287,16 @@ pub struct CharacterString<'a> {
     pub inner: &'a str,
 }
 
+// A character string exactly as it was sent: no utf8 validation and any character set.
+// Useful for passing values through (e.g. a sniffer) or for doing the validation or transcoding yourself.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterStringRaw<'a> {
+    pub encoding: u8,
+    pub bytes: &'a [u8],
+}
+
 impl<'a> Display for ApplicationDataValue<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -174,6 +313,12 @@ impl<'a> Display for ApplicationDataValue<'a> {
 pub enum BitString<'a> {
     Status(Status),
     LogBufferResult(LogBufferResult),
+    LimitEnable(LimitEnable),
+    EventEnable(EventTransitionBits),
+    AckedTransitions(EventTransitionBits),
+    AckRequired(EventTransitionBits),
+    ServicesSupported(ServicesSupported<'a>),
+    ObjectTypesSupported(ObjectTypesSupported<'a>),
     Custom(CustomBitStream<'a>),
 }
 
@@ -191,6 +336,137 @@ pub struct CustomBitStream<'a> {
     pub bits: &'a [u8],
 }
 
+impl<'a> CustomBitStream<'a> {
+    pub fn new(unused_bits: u8, bits: &'a [u8]) -> Self {
+        Self { unused_bits, bits }
+    }
+
+    // number of meaningful bits (the unused trailing bits of the last byte are excluded)
+    pub fn len(&self) -> usize {
+        (self.bits.len() * 8).saturating_sub(self.unused_bits as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // bit 0 is the most significant bit of the first byte
+    // returns false for any bit beyond the meaningful length (including the unused trailing bits)
+    pub fn bit(&self, index: usize) -> bool {
+        if index >= self.len() {
+            return false;
+        }
+
+        let mask = 0x80 >> (index % 8);
+        self.bits[index / 8] & mask == mask
+    }
+}
+
+// BACnetServicesSupported - the value of PropProtocolServicesSupported
+// the bit positions are not the service choice numbers so each service is mapped to its bit below
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ServicesSupported<'a> {
+    pub bits: CustomBitStream<'a>,
+}
+
+impl<'a> ServicesSupported<'a> {
+    pub fn new(bits: CustomBitStream<'a>) -> Self {
+        Self { bits }
+    }
+
+    // a device with a shorter bit string than we know about does not support the missing services
+    pub fn supports(&self, service: ConfirmedServiceChoice) -> bool {
+        Self::confirmed_bit(service).is_some_and(|bit| self.bits.bit(bit))
+    }
+
+    pub fn supports_unconfirmed(&self, service: UnconfirmedServiceChoice) -> bool {
+        Self::unconfirmed_bit(service).is_some_and(|bit| self.bits.bit(bit))
+    }
+
+    fn confirmed_bit(service: ConfirmedServiceChoice) -> Option<usize> {
+        let bit = match service {
+            ConfirmedServiceChoice::AcknowledgeAlarm => 0,
+            ConfirmedServiceChoice::CovNotification => 1,
+            ConfirmedServiceChoice::EventNotification => 2,
+            ConfirmedServiceChoice::GetAlarmSummary => 3,
+            ConfirmedServiceChoice::GetEnrollmentSummary => 4,
+            ConfirmedServiceChoice::SubscribeCov => 5,
+            ConfirmedServiceChoice::AtomicReadFile => 6,
+            ConfirmedServiceChoice::AtomicWriteFile => 7,
+            ConfirmedServiceChoice::AddListElement => 8,
+            ConfirmedServiceChoice::RemoveListElement => 9,
+            ConfirmedServiceChoice::CreateObject => 10,
+            ConfirmedServiceChoice::DeleteObject => 11,
+            ConfirmedServiceChoice::ReadProperty => 12,
+            ConfirmedServiceChoice::ReadPropConditional => 13,
+            ConfirmedServiceChoice::ReadPropMultiple => 14,
+            ConfirmedServiceChoice::WriteProperty => 15,
+            ConfirmedServiceChoice::WritePropMultiple => 16,
+            ConfirmedServiceChoice::DeviceCommunicationControl => 17,
+            ConfirmedServiceChoice::PrivateTransfer => 18,
+            ConfirmedServiceChoice::TextMessage => 19,
+            ConfirmedServiceChoice::ReinitializeDevice => 20,
+            ConfirmedServiceChoice::VtOpen => 21,
+            ConfirmedServiceChoice::VtClose => 22,
+            ConfirmedServiceChoice::VtData => 23,
+            ConfirmedServiceChoice::Authenticate => 24,
+            ConfirmedServiceChoice::RequestKey => 25,
+            ConfirmedServiceChoice::ReadRange => 35,
+            ConfirmedServiceChoice::LifeSafetyOperation => 37,
+            ConfirmedServiceChoice::SubscribeCovProperty => 38,
+            ConfirmedServiceChoice::GetEventInformation => 39,
+            ConfirmedServiceChoice::SubscribeCovPropertyMultiple => 41,
+            ConfirmedServiceChoice::CovNotificationMultiple => 42,
+            ConfirmedServiceChoice::AuditNotification => 44,
+            ConfirmedServiceChoice::AuditLogQuery => 45,
+            ConfirmedServiceChoice::MaxBacnetConfirmedService => return None,
+        };
+        Some(bit)
+    }
+
+    fn unconfirmed_bit(service: UnconfirmedServiceChoice) -> Option<usize> {
+        let bit = match service {
+            UnconfirmedServiceChoice::IAm => 26,
+            UnconfirmedServiceChoice::IHave => 27,
+            UnconfirmedServiceChoice::CovNotification => 28,
+            UnconfirmedServiceChoice::EventNotification => 29,
+            UnconfirmedServiceChoice::PrivateTransfer => 30,
+            UnconfirmedServiceChoice::TextMessage => 31,
+            UnconfirmedServiceChoice::TimeSynchronization => 32,
+            UnconfirmedServiceChoice::WhoHas => 33,
+            UnconfirmedServiceChoice::WhoIs => 34,
+            UnconfirmedServiceChoice::UtcTimeSynchronization => 36,
+            UnconfirmedServiceChoice::WriteGroup => 40,
+            UnconfirmedServiceChoice::CovNotificationMultiple => 43,
+            UnconfirmedServiceChoice::AuditNotification => 46,
+            UnconfirmedServiceChoice::WhoAmI => 47,
+            UnconfirmedServiceChoice::YouAre => 48,
+            UnconfirmedServiceChoice::MaxBacnetUnconfirmedService => return None,
+        };
+        Some(bit)
+    }
+}
+
+// BACnetObjectTypesSupported - the value of PropProtocolObjectTypesSupported
+// the bit position is the object type number
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ObjectTypesSupported<'a> {
+    pub bits: CustomBitStream<'a>,
+}
+
+impl<'a> ObjectTypesSupported<'a> {
+    pub fn new(bits: CustomBitStream<'a>) -> Self {
+        Self { bits }
+    }
+
+    // object types newer than this library are looked up by their raw number (ObjectType::Unknown)
+    pub fn supports(&self, object_type: ObjectType) -> bool {
+        self.bits.bit(u32::from(object_type) as usize)
+    }
+}
+
 impl<'a> BitString<'a> {
     pub fn encode_application(&self, writer: &mut Writer) {
         match self {
@@ -204,13 +480,25 @@ impl<'a> BitString<'a> {
                 writer.push(0); // no unused bits
                 writer.push(x.inner);
             }
-            Self::Custom(x) => {
+            Self::LimitEnable(x) => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::BitString), 2).encode(writer);
+                writer.push(LimitEnable::UNUSED_BITS);
+                writer.push(x.inner);
+            }
+            Self::EventEnable(x) | Self::AckedTransitions(x) | Self::AckRequired(x) => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::BitString), 2).encode(writer);
+                writer.push(EventTransitionBits::UNUSED_BITS);
+                writer.push(x.inner);
+            }
+            Self::Custom(x)
+            | Self::ServicesSupported(ServicesSupported { bits: x })
+            | Self::ObjectTypesSupported(ObjectTypesSupported { bits: x }) => {
                 Tag::new(
                     TagNumber::Application(ApplicationTagNumber::BitString),
                     x.bits.len() as u32 + 1,
                 )
                 .encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(x.unused_bits);
                 writer.extend_from_slice(x.bits);
             }
         }
@@ -228,10 +516,22 @@ impl<'a> BitString<'a> {
                 writer.push(0); // no unused bits
                 writer.push(x.inner);
             }
-            Self::Custom(x) => {
+            Self::LimitEnable(x) => {
+                Tag::new(TagNumber::ContextSpecific(tag_num), 2).encode(writer);
+                writer.push(LimitEnable::UNUSED_BITS);
+                writer.push(x.inner);
+            }
+            Self::EventEnable(x) | Self::AckedTransitions(x) | Self::AckRequired(x) => {
+                Tag::new(TagNumber::ContextSpecific(tag_num), 2).encode(writer);
+                writer.push(EventTransitionBits::UNUSED_BITS);
+                writer.push(x.inner);
+            }
+            Self::Custom(x)
+            | Self::ServicesSupported(ServicesSupported { bits: x })
+            | Self::ObjectTypesSupported(ObjectTypesSupported { bits: x }) => {
                 Tag::new(TagNumber::ContextSpecific(tag_num), x.bits.len() as u32 + 1)
                     .encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(x.unused_bits);
                 writer.extend_from_slice(x.bits);
             }
         }
@@ -243,7 +543,18 @@ impl<'a> BitString<'a> {
         reader: &mut Reader,
         buf: &'a [u8],
     ) -> Result<Self, Error> {
+        if len == 0 {
+            return Err(Error::Length((
+                "bit string should have a length of at least 1",
+                len,
+            )));
+        }
         let unused_bits = reader.read_byte(buf)?;
+        if unused_bits > 7 {
+            return Err(Error::InvalidValue(
+                "bit string unused bits should be between 0 and 7",
+            ));
+        }
         match property_id {
             PropertyId::PropStatusFlags => {
                 let status_flags = Status::new(reader.read_byte(buf)?);
@@ -253,26 +564,107 @@ impl<'a> BitString<'a> {
                 let flags = LogBufferResult::new(reader.read_byte(buf)?);
                 Ok(Self::LogBufferResult(flags))
             }
+            PropertyId::PropLimitEnable => {
+                let flags = LimitEnable::new(reader.read_byte(buf)?);
+                Ok(Self::LimitEnable(flags))
+            }
+            PropertyId::PropEventEnable => {
+                let flags = EventTransitionBits::new(reader.read_byte(buf)?);
+                Ok(Self::EventEnable(flags))
+            }
+            PropertyId::PropAckedTransitions => {
+                let flags = EventTransitionBits::new(reader.read_byte(buf)?);
+                Ok(Self::AckedTransitions(flags))
+            }
+            PropertyId::PropAckRequired => {
+                let flags = EventTransitionBits::new(reader.read_byte(buf)?);
+                Ok(Self::AckRequired(flags))
+            }
+            PropertyId::PropProtocolServicesSupported => {
+                let bits = Self::decode_custom(len, unused_bits, reader, buf)?;
+                Ok(Self::ServicesSupported(ServicesSupported::new(bits)))
+            }
+            PropertyId::PropProtocolObjectTypesSupported => {
+                let bits = Self::decode_custom(len, unused_bits, reader, buf)?;
+                Ok(Self::ObjectTypesSupported(ObjectTypesSupported::new(bits)))
+            }
             _ => {
-                let len = (len - 1) as usize; // we have already read a byte
-                let bits = reader.read_slice(len, buf)?;
-                Ok(Self::Custom(CustomBitStream { unused_bits, bits }))
+                let bits = Self::decode_custom(len, unused_bits, reader, buf)?;
+                Ok(Self::Custom(bits))
             }
         }
     }
+
+    fn decode_custom(
+        len: u32,
+        unused_bits: u8,
+        reader: &mut Reader,
+        buf: &'a [u8],
+    ) -> Result<CustomBitStream<'a>, Error> {
+        let len = (len - 1) as usize; // we have already read a byte
+        if len == 0 && unused_bits > 0 {
+            return Err(Error::InvalidValue(
+                "empty bit string cannot have unused bits",
+            ));
+        }
+        let bits = reader.read_slice(len, buf)?;
+        Ok(CustomBitStream { unused_bits, bits })
+    }
 }
 
 impl<'a> CharacterString<'a> {
     pub fn decode(len: u32, reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
-        let character_set = reader.read_byte(buf)?;
-        if character_set != 0 {
-            unimplemented!("non-utf8 characterset not supported")
+        CharacterStringRaw::decode(len, reader, buf)?.to_character_string()
+    }
+}
+
+impl<'a> CharacterStringRaw<'a> {
+    pub const UTF8: u8 = 0;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::CharacterString),
+            self.bytes.len() as u32 + 1, // keep space for encoding byte
+        )
+        .encode(writer);
+        writer.push(self.encoding);
+        writer.extend_from_slice(self.bytes);
+    }
+
+    // len is the length of the tag (the encoding byte plus the string)
+    pub fn decode(len: u32, reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        if len == 0 {
+            // some devices leave out the character set byte for an empty string
+            return Ok(Self {
+                encoding: Self::UTF8,
+                bytes: &[],
+            });
+        }
+        let encoding = reader.read_byte(buf)?;
+        let bytes = reader.read_slice(len as usize - 1, buf)?;
+        Ok(Self { encoding, bytes })
+    }
+
+    // decodes the application tag as well as the string
+    pub fn decode_application(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::CharacterString),
+            "CharacterStringRaw decode",
+        )?;
+        Self::decode(tag.value, reader, buf)
+    }
+
+    pub fn to_character_string(&self) -> Result<CharacterString<'a>, Error> {
+        if self.encoding != Self::UTF8 {
+            return Err(Error::InvalidValue(
+                "CharacterString character set is not utf8",
+            ));
         }
-        let slice = reader.read_slice(len as usize - 1, buf)?;
-        let inner = from_utf8(slice).map_err(|_| {
+        let inner = from_utf8(self.bytes).map_err(|_| {
             Error::InvalidValue("CharacterString bytes are not a valid utf8 string")
         })?;
-
         Ok(CharacterString { inner })
     }
 }
@@ -309,6 +701,21 @@ impl<'a> ApplicationDataValueWrite<'a> {
                         let value = decode_enumerated(object_id, property_id, &tag, reader, buf)?;
                         Ok(Self::Enumerated(value))
                     }
+                    TagNumber::Application(ApplicationTagNumber::UnsignedInt) => {
+                        let value = decode_unsigned(tag.value, reader, buf)?;
+                        let value = u32::try_from(value).map_err(|_| {
+                            Error::Length(("ApplicationDataValueWrite unsigned int", tag.value))
+                        })?;
+                        Ok(Self::UnsignedInt(value))
+                    }
+                    TagNumber::Application(ApplicationTagNumber::SignedInt) => {
+                        let value = decode_signed(tag.value, reader, buf)?;
+                        Ok(Self::SignedInt(value))
+                    }
+                    TagNumber::Application(ApplicationTagNumber::CharacterString) => {
+                        let text = CharacterString::decode(tag.value, reader, buf)?;
+                        Ok(Self::CharacterString(text))
+                    }
                     tag_number => Err(Error::TagNotSupported((
                         "ApplicationDataValueWrite decode",
                         tag_number,
@@ -336,14 +743,36 @@ impl<'a> ApplicationDataValueWrite<'a> {
             Self::Enumerated(x) => {
                 x.encode(writer);
             }
+            Self::UnsignedInt(x) => {
+                encode_application_unsigned(writer, *x as u64);
+            }
+            Self::SignedInt(x) => {
+                encode_application_signed(writer, *x);
+            }
+            Self::CharacterString(x) => {
+                ApplicationDataValue::CharacterString(x.clone()).encode(writer);
+            }
             Self::WeeklySchedule(x) => x.encode(writer),
         }
     }
 }
 
 impl<'a> ApplicationDataValue<'a> {
+    // some devices report NaN when there is no valid value (e.g. an analog input with a disconnected sensor)
+    // the bits are decoded as is so infinities and NaN payloads are preserved
+    pub fn is_unknown_real(&self) -> bool {
+        match self {
+            Self::Real(x) => x.is_nan(),
+            Self::Double(x) => x.is_nan(),
+            _ => false,
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         match self {
+            ApplicationDataValue::Null => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::Null), 0).encode(writer)
+            }
             ApplicationDataValue::Boolean(x) => Tag::new(
                 TagNumber::Application(ApplicationTagNumber::Boolean),
                 if *x { 1 } else { 0 },
@@ -353,6 +782,10 @@ impl<'a> ApplicationDataValue<'a> {
                 Tag::new(TagNumber::Application(ApplicationTagNumber::Real), 4).encode(writer);
                 writer.extend_from_slice(&x.to_be_bytes());
             }
+            ApplicationDataValue::Double(x) => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::Double), 8).encode(writer);
+                writer.extend_from_slice(&x.to_be_bytes());
+            }
             ApplicationDataValue::Date(x) => {
                 Tag::new(
                     TagNumber::Application(ApplicationTagNumber::Date),
@@ -387,6 +820,9 @@ impl<'a> ApplicationDataValue<'a> {
                 writer.push(0); // utf8 encoding
                 writer.extend_from_slice(utf8_encoded);
             }
+            ApplicationDataValue::OctetString(x) => {
+                encode_application_octet_string(writer, x);
+            }
             ApplicationDataValue::Enumerated(x) => {
                 x.encode(writer);
             }
@@ -398,12 +834,19 @@ impl<'a> ApplicationDataValue<'a> {
                     .encode(writer);
                 writer.extend_from_slice(&x.to_be_bytes());
             }
+            ApplicationDataValue::BigUnsignedInt(x) => {
+                encode_application_unsigned(writer, *x);
+            }
+            ApplicationDataValue::SignedInt(x) => {
+                encode_application_signed(writer, *x);
+            }
+            ApplicationDataValue::BigSignedInt(x) => {
+                encode_application_signed_i64(writer, *x);
+            }
             ApplicationDataValue::WeeklySchedule(x) => {
                 // no application tag required for weekly schedule
                 x.encode(writer);
             }
-
-            x => todo!("{:?}", x),
         };
     }
 
@@ -425,17 +868,20 @@ impl<'a> ApplicationDataValue<'a> {
         };
 
         match tag_num {
-            ApplicationTagNumber::Real => {
-                if tag.value != 4 {
-                    return Err(Error::Length((
-                        "real tag should have length of 4",
-                        tag.value,
-                    )));
-                }
-                Ok(ApplicationDataValue::Real(f32::from_be_bytes(
+            ApplicationTagNumber::Null => Ok(ApplicationDataValue::Null),
+            // some devices tag the double present value of a large analog value as a real
+            ApplicationTagNumber::Real | ApplicationTagNumber::Double => match tag.value {
+                4 => Ok(ApplicationDataValue::Real(f32::from_be_bytes(
                     reader.read_bytes(buf)?,
-                )))
-            }
+                ))),
+                8 => Ok(ApplicationDataValue::Double(f64::from_be_bytes(
+                    reader.read_bytes(buf)?,
+                ))),
+                len => Err(Error::Length((
+                    "real tag should have length of 4 and double a length of 8",
+                    len,
+                ))),
+            },
             ApplicationTagNumber::ObjectId => {
                 let object_id = ObjectId::decode(tag.value, reader, buf)?;
                 Ok(ApplicationDataValue::ObjectId(object_id))
@@ -444,6 +890,10 @@ impl<'a> ApplicationDataValue<'a> {
                 let text = CharacterString::decode(tag.value, reader, buf)?;
                 Ok(ApplicationDataValue::CharacterString(text))
             }
+            ApplicationTagNumber::OctetString => {
+                let octets = reader.read_slice(tag.value as usize, buf)?;
+                Ok(ApplicationDataValue::OctetString(octets))
+            }
             ApplicationTagNumber::Enumerated => {
                 let value = decode_enumerated(object_id, property_id, tag, reader, buf)?;
                 Ok(ApplicationDataValue::Enumerated(value))
@@ -457,8 +907,18 @@ impl<'a> ApplicationDataValue<'a> {
                 Ok(ApplicationDataValue::Boolean(value))
             }
             ApplicationTagNumber::UnsignedInt => {
-                let value = decode_unsigned(tag.value, reader, buf)? as u32;
-                Ok(ApplicationDataValue::UnsignedInt(value))
+                let value = decode_unsigned(tag.value, reader, buf)?;
+                match u32::try_from(value) {
+                    Ok(value) => Ok(ApplicationDataValue::UnsignedInt(value)),
+                    Err(_) => Ok(ApplicationDataValue::BigUnsignedInt(value)),
+                }
+            }
+            ApplicationTagNumber::SignedInt => {
+                let value = decode_signed_i64(tag.value, reader, buf)?;
+                match i32::try_from(value) {
+                    Ok(value) => Ok(ApplicationDataValue::SignedInt(value)),
+                    Err(_) => Ok(ApplicationDataValue::BigSignedInt(value)),
+                }
             }
             ApplicationTagNumber::Time => {
                 if tag.value != 4 {
@@ -471,7 +931,7 @@ impl<'a> ApplicationDataValue<'a> {
                 Ok(ApplicationDataValue::Time(time))
             }
             ApplicationTagNumber::Date => {
-                // let date = Date::decode_from_tag(&tag);
+                check_len(tag.value, Date::LEN, "date tag should have length of 4")?;
                 let date = Date::decode(reader, buf)?;
                 Ok(ApplicationDataValue::Date(date))
             }
@@ -493,12 +953,11 @@ fn decode_enumerated(
 ) -> Result<Enumerated, Error> {
     let value = decode_unsigned(tag.value, reader, buf)? as u32;
     match property_id {
-        PropertyId::PropUnits => {
-            let units = value
-                .try_into()
-                .map_err(|x| Error::InvalidVariant(("EngineeringUnits", x)))?;
-            Ok(Enumerated::Units(units))
-        }
+        PropertyId::PropUnits => match EngineeringUnits::try_from(value) {
+            Ok(units) => Ok(Enumerated::Units(units)),
+            // gaps in the enumeration and vendor specific units are passed through as is
+            Err(value) => Ok(Enumerated::Unknown(value)),
+        },
         PropertyId::PropPresentValue => match object_id.object_type {
             ObjectType::ObjectBinaryInput
             | ObjectType::ObjectBinaryOutput
@@ -530,7 +989,576 @@ fn decode_enumerated(
                 .map_err(|x| Error::InvalidVariant(("LoggingType", x)))?;
             Ok(Enumerated::LoggingType(logging_type))
         }
+        PropertyId::PropReliability => {
+            let reliability = Reliability::try_from(value)
+                .map_err(|x| Error::InvalidVariant(("Reliability", x)))?;
+            Ok(Enumerated::Reliability(reliability))
+        }
+        PropertyId::PropPolarity => {
+            let polarity =
+                Polarity::try_from(value).map_err(|x| Error::InvalidVariant(("Polarity", x)))?;
+            Ok(Enumerated::Polarity(polarity))
+        }
+        PropertyId::PropSystemStatus => {
+            let status = DeviceStatus::try_from(value)
+                .map_err(|x| Error::InvalidVariant(("DeviceStatus", x)))?;
+            Ok(Enumerated::DeviceStatus(status))
+        }
 
         _ => Ok(Enumerated::Unknown(value)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::ConfirmedServiceChoice, unconfirmed::UnconfirmedServiceChoice,
+        },
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{DeviceStatus, EngineeringUnits, Polarity, Reliability},
+            tag::Tag,
+        },
+    };
+
+    use super::{
+        ApplicationDataValue, ApplicationDataValueWrite, BitString, CharacterStringRaw, Date,
+        Enumerated, Time,
+    };
+
+    // decodes one application tagged value of this property and checks that nothing is left over
+    fn decode_value(
+        object_type: ObjectType,
+        property_id: PropertyId,
+        input: &[u8],
+    ) -> Result<ApplicationDataValue<'_>, Error> {
+        let object_id = ObjectId::new(object_type, 1);
+        let mut reader = Reader::new_with_len(input.len());
+        let tag = Tag::decode(&mut reader, input)?;
+        let value =
+            ApplicationDataValue::decode(&tag, &object_id, &property_id, &mut reader, input)?;
+        assert!(reader.eof());
+        Ok(value)
+    }
+
+    #[test]
+    fn bit_string_with_unused_bits() {
+        // 12 bits: 1010_0000 1001_xxxx where the 4 trailing bits are unused (and set to junk)
+        let input: [u8; 3] = [4, 0b1010_0000, 0b1001_1111];
+        let mut reader = Reader::new_with_len(input.len());
+        let decoded = BitString::decode(
+            &PropertyId::PropBitMask,
+            input.len() as u32,
+            &mut reader,
+            &input,
+        )
+        .unwrap();
+
+        let bits = match &decoded {
+            BitString::Custom(x) => x,
+            x => panic!("unexpected bit string {:?}", x),
+        };
+        assert_eq!(bits.len(), 12);
+        assert!(bits.bit(0));
+        assert!(!bits.bit(1));
+        assert!(bits.bit(2));
+        assert!(bits.bit(8));
+        assert!(!bits.bit(9));
+        assert!(bits.bit(11));
+        assert!(!bits.bit(12)); // unused bit
+        assert!(!bits.bit(15)); // unused bit
+
+        // encoding should preserve the unused bits count
+        let mut output: [u8; 4] = [0; 4];
+        let mut writer = Writer::new(&mut output);
+        decoded.encode_application(&mut writer);
+        assert_eq!(writer.to_bytes(), &[0x83, 4, 0b1010_0000, 0b1001_1111]);
+    }
+
+    #[test]
+    fn protocol_services_supported() {
+        // 44 bits from a controller: subscribe-cov, read-property, read-property-multiple,
+        // write-property, device-communication-control, reinitialize-device, i-am, i-have,
+        // time-synchronization, who-has, who-is, read-range and utc-time-synchronization
+        // the 4 trailing bits are unused (and set to junk)
+        let input = [0x85, 0x07, 0x04, 0x04, 0x0B, 0x48, 0x30, 0xF8, 0x0F];
+        let decoded = decode_value(
+            ObjectType::ObjectDevice,
+            PropertyId::PropProtocolServicesSupported,
+            &input,
+        )
+        .unwrap();
+        let ApplicationDataValue::BitString(BitString::ServicesSupported(services)) = &decoded
+        else {
+            panic!("expected services supported: {:?}", decoded);
+        };
+        assert_eq!(services.bits.len(), 44);
+
+        assert!(services.supports(ConfirmedServiceChoice::SubscribeCov));
+        assert!(services.supports(ConfirmedServiceChoice::ReadProperty));
+        assert!(services.supports(ConfirmedServiceChoice::ReadPropMultiple));
+        assert!(services.supports(ConfirmedServiceChoice::WriteProperty));
+        assert!(services.supports(ConfirmedServiceChoice::ReadRange));
+        assert!(!services.supports(ConfirmedServiceChoice::WritePropMultiple));
+        assert!(!services.supports(ConfirmedServiceChoice::CreateObject));
+        assert!(!services.supports(ConfirmedServiceChoice::SubscribeCovProperty));
+        // beyond the end of the bit string (the junk in the unused bits is ignored)
+        assert!(!services.supports(ConfirmedServiceChoice::AuditNotification));
+
+        assert!(services.supports_unconfirmed(UnconfirmedServiceChoice::WhoIs));
+        assert!(services.supports_unconfirmed(UnconfirmedServiceChoice::IAm));
+        assert!(services.supports_unconfirmed(UnconfirmedServiceChoice::UtcTimeSynchronization));
+        assert!(!services.supports_unconfirmed(UnconfirmedServiceChoice::CovNotification));
+        assert!(!services.supports_unconfirmed(UnconfirmedServiceChoice::WriteGroup));
+        assert!(!services.supports_unconfirmed(UnconfirmedServiceChoice::WhoAmI));
+
+        let mut output = [0; 16];
+        let mut writer = Writer::new(&mut output);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &input);
+    }
+
+    #[test]
+    fn protocol_object_types_supported() {
+        // 60 bits: analog-input, analog-value, binary-value, device, multi-state-value, schedule,
+        // trend-log and a type newer than this library (bit 58)
+        // the 4 trailing bits are unused (and set to junk)
+        let input = [
+            0x85, 0x09, 0x04, 0xA4, 0x80, 0x58, 0x00, 0x00, 0x00, 0x00, 0x2F,
+        ];
+        let decoded = decode_value(
+            ObjectType::ObjectDevice,
+            PropertyId::PropProtocolObjectTypesSupported,
+            &input,
+        )
+        .unwrap();
+        let ApplicationDataValue::BitString(BitString::ObjectTypesSupported(object_types)) =
+            &decoded
+        else {
+            panic!("expected object types supported: {:?}", decoded);
+        };
+        assert_eq!(object_types.bits.len(), 60);
+
+        assert!(object_types.supports(ObjectType::ObjectAnalogInput));
+        assert!(object_types.supports(ObjectType::ObjectAnalogValue));
+        assert!(object_types.supports(ObjectType::ObjectBinaryValue));
+        assert!(object_types.supports(ObjectType::ObjectDevice));
+        assert!(object_types.supports(ObjectType::ObjectMultiStateValue));
+        assert!(object_types.supports(ObjectType::ObjectSchedule));
+        assert!(object_types.supports(ObjectType::ObjectTrendlog));
+        assert!(object_types.supports(ObjectType::Unknown(58)));
+        assert!(!object_types.supports(ObjectType::ObjectAnalogOutput));
+        assert!(!object_types.supports(ObjectType::ObjectNetworkPort));
+        // beyond the end of the bit string (the junk in the unused bits is ignored)
+        assert!(!object_types.supports(ObjectType::Unknown(60)));
+        assert!(!object_types.supports(ObjectType::Unknown(62)));
+        assert!(!object_types.supports(ObjectType::Invalid));
+
+        let mut output = [0; 16];
+        let mut writer = Writer::new(&mut output);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &input);
+    }
+
+    #[test]
+    fn time_wildcard_round_trip() {
+        let input: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+        let mut reader = Reader::new_with_len(input.len());
+        let decoded = Time::decode(&mut reader, &input).unwrap();
+        assert!(decoded.is_unspecified());
+        assert_eq!(decoded.hour_opt(), None);
+        assert_eq!(decoded.hundredths_opt(), None);
+
+        let mut output: [u8; 4] = [0; 4];
+        let mut writer = Writer::new(&mut output);
+        Time::unspecified().encode(&mut writer);
+        assert_eq!(input, output);
+    }
+
+    #[test]
+    fn unknown_units() {
+        let decode = |input| {
+            decode_value(ObjectType::ObjectAnalogInput, PropertyId::PropUnits, input).unwrap()
+        };
+
+        // 62 is degrees celsius
+        let value = decode(&[0x91, 62]);
+        assert!(matches!(
+            value,
+            ApplicationDataValue::Enumerated(Enumerated::Units(EngineeringUnits::DegreesCelsius))
+        ));
+
+        // 1000 is in the 256-47807 range that vendors may use for proprietary units
+        let value = decode(&[0x92, 0x03, 0xE8]);
+        assert!(matches!(
+            value,
+            ApplicationDataValue::Enumerated(Enumerated::Unknown(1000))
+        ));
+
+        // 47813 is in the 47808-49999 range reserved for ASHRAE but not yet assigned
+        let value = decode(&[0x92, 0xBA, 0xC5]);
+        assert!(matches!(
+            value,
+            ApplicationDataValue::Enumerated(Enumerated::Unknown(47813))
+        ));
+    }
+
+    #[test]
+    fn reliability() {
+        let decode = |input| {
+            decode_value(
+                ObjectType::ObjectAnalogInput,
+                PropertyId::PropReliability,
+                input,
+            )
+            .unwrap()
+        };
+
+        let value = decode(&[0x91, 0x01]);
+        assert!(matches!(
+            value,
+            ApplicationDataValue::Enumerated(Enumerated::Reliability(Reliability::NoSensor))
+        ));
+
+        // vendor proprietary reliability survives a round trip
+        let value = decode(&[0x92, 0x01, 0x00]);
+        assert!(matches!(
+            value,
+            ApplicationDataValue::Enumerated(Enumerated::Reliability(Reliability::Unknown(256)))
+        ));
+        let mut buf = [0; 4];
+        let mut writer = Writer::new(&mut buf);
+        value.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &[0x92, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn polarity_and_device_status() {
+        fn decode(object_type: ObjectType, property_id: PropertyId, input: &[u8]) -> Enumerated {
+            match decode_value(object_type, property_id, input) {
+                Ok(ApplicationDataValue::Enumerated(x)) => x,
+                x => panic!("expected an enumerated value: {:?}", x),
+            }
+        }
+
+        let polarity = |input| {
+            decode(
+                ObjectType::ObjectBinaryOutput,
+                PropertyId::PropPolarity,
+                input,
+            )
+        };
+        assert!(matches!(
+            polarity(&[0x91, 0x00]),
+            Enumerated::Polarity(Polarity::Normal)
+        ));
+        assert!(matches!(
+            polarity(&[0x91, 0x01]),
+            Enumerated::Polarity(Polarity::Reverse)
+        ));
+        assert!(matches!(
+            polarity(&[0x91, 0x02]),
+            Enumerated::Polarity(Polarity::Unknown(2))
+        ));
+
+        let status = |input| {
+            decode(
+                ObjectType::ObjectDevice,
+                PropertyId::PropSystemStatus,
+                input,
+            )
+        };
+        assert!(matches!(
+            status(&[0x91, 0x00]),
+            Enumerated::DeviceStatus(DeviceStatus::Operational)
+        ));
+        assert!(matches!(
+            status(&[0x91, 0x05]),
+            Enumerated::DeviceStatus(DeviceStatus::BackupInProgress)
+        ));
+        let proprietary = status(&[0x91, 0x40]);
+        assert!(matches!(
+            proprietary,
+            Enumerated::DeviceStatus(DeviceStatus::Unknown(64))
+        ));
+        assert_eq!(proprietary.value(), 64);
+    }
+
+    #[test]
+    fn zero_length_values() {
+        let decode = |input| {
+            decode_value(
+                ObjectType::ObjectAnalogValue,
+                PropertyId::PropDescription,
+                input,
+            )
+        };
+
+        // empty strings are valid (with or without the character set byte)
+        assert!(matches!(
+            decode(&[0x71, 0x00]),
+            Ok(ApplicationDataValue::CharacterString(x)) if x.inner.is_empty()
+        ));
+        assert!(matches!(
+            decode(&[0x70]),
+            Ok(ApplicationDataValue::CharacterString(x)) if x.inner.is_empty()
+        ));
+        assert!(matches!(
+            decode(&[0x60]),
+            Ok(ApplicationDataValue::OctetString([]))
+        ));
+
+        // fixed size values are an error rather than a panic or a read of the next value
+        assert!(matches!(decode(&[0x40]), Err(Error::Length(_))));
+        assert!(matches!(decode(&[0xA0, 0x21, 0x01]), Err(Error::Length(_))));
+        assert!(matches!(decode(&[0xB0]), Err(Error::Length(_))));
+        assert!(matches!(decode(&[0x20]), Err(Error::Length(_))));
+        assert!(matches!(decode(&[0x90]), Err(Error::Length(_))));
+        assert!(matches!(decode(&[0x80]), Err(Error::Length(_))));
+    }
+
+    #[test]
+    fn malformed_lengths() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let property_id = PropertyId::PropPresentValue;
+
+        // a real tag claiming length 3 followed by a time tag claiming length 3
+        for input in [&[0x43, 0x41, 0xAC, 0x00][..], &[0xB3, 12, 0, 0][..]] {
+            let result = decode_value(ObjectType::ObjectAnalogValue, property_id, input);
+            assert!(matches!(result, Err(Error::Length(_))));
+        }
+
+        let input = [0x43, 0x41, 0xAC, 0x00];
+        let mut reader = Reader::new_with_len(input.len());
+        let result =
+            ApplicationDataValueWrite::decode(&object_id, &property_id, &mut reader, &input);
+        assert!(matches!(result, Err(Error::Length(_))));
+    }
+
+    #[test]
+    fn big_integers() {
+        fn round_trip(input: &[u8]) -> ApplicationDataValue<'_> {
+            let value = decode_value(
+                ObjectType::ObjectAccumulator,
+                PropertyId::PropPresentValue,
+                input,
+            )
+            .unwrap();
+
+            let mut buf = [0; 16];
+            let mut writer = Writer::new(&mut buf);
+            value.encode(&mut writer);
+            assert_eq!(writer.to_bytes(), input);
+            value
+        }
+
+        // a 6 byte unsigned counter
+        let value = round_trip(&[0x25, 0x06, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        assert!(matches!(
+            value,
+            ApplicationDataValue::BigUnsignedInt(0x0102_0304_0506)
+        ));
+
+        // a 5 byte negative signed value
+        let value = round_trip(&[0x35, 0x05, 0xFF, 0x00, 0x00, 0x00, 0x00]);
+        assert!(matches!(
+            value,
+            ApplicationDataValue::BigSignedInt(-0x01_0000_0000)
+        ));
+
+        let value = round_trip(&[0x31, 0xFE]);
+        assert!(matches!(value, ApplicationDataValue::SignedInt(-2)));
+
+        // a value that fits in a u32 stays an UnsignedInt
+        let value = decode_value(
+            ObjectType::ObjectAccumulator,
+            PropertyId::PropPresentValue,
+            &[0x21, 0x2A],
+        );
+        assert!(matches!(value, Ok(ApplicationDataValue::UnsignedInt(42))));
+    }
+
+    #[test]
+    fn date_from_ymd() {
+        let wday = |year, month, day| Date::from_ymd(year, month, day).wday;
+        assert_eq!(wday(1970, 1, 1), 4); // thursday
+        assert_eq!(wday(2000, 1, 1), 6); // saturday
+        assert_eq!(wday(2000, 2, 29), 2); // tuesday
+        assert_eq!(wday(2023, 12, 31), 7); // sunday
+        assert_eq!(wday(2024, 3, 14), 4); // thursday
+        assert_eq!(wday(2024, 3, 18), 1); // monday
+
+        let date = Date::from_ymd(2024, 2, 29);
+        assert_eq!(
+            (date.year, date.month, date.day, date.wday),
+            (2024, 2, 29, 4)
+        );
+
+        // year 0 doesn't underflow
+        assert!((1..=7).contains(&wday(0, 1, 1)));
+
+        // wildcards and special values have no day of the week
+        assert_eq!(wday(Date::UNSPECIFIED_YEAR, 1, 1), Date::UNSPECIFIED);
+        assert_eq!(wday(2024, Date::UNSPECIFIED, 1), Date::UNSPECIFIED);
+        assert_eq!(wday(2024, 13, 1), Date::UNSPECIFIED); // odd months
+        assert_eq!(wday(2024, 14, 1), Date::UNSPECIFIED); // even months
+        assert_eq!(wday(2024, 1, Date::UNSPECIFIED), Date::UNSPECIFIED);
+        assert_eq!(wday(2024, 1, 32), Date::UNSPECIFIED); // last day of the month
+        assert_eq!(wday(2024, 1, 33), Date::UNSPECIFIED); // odd days
+        let date = Date::from_ymd(2024, 13, 32);
+        assert_eq!((date.year, date.month, date.day), (2024, 13, 32));
+    }
+
+    #[test]
+    fn large_analog_value_present_value() {
+        let decode = |input| {
+            decode_value(
+                ObjectType::ObjectLargeAnalogValue,
+                PropertyId::PropPresentValue,
+                input,
+            )
+        };
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        ApplicationDataValue::Double(1234567.891).encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(encoded[0], 0x55);
+        assert_eq!(encoded[1], 0x08);
+        assert!(matches!(
+            decode(encoded),
+            Ok(ApplicationDataValue::Double(x)) if x == 1234567.891
+        ));
+
+        // a double tagged as a real
+        let mut input = [0x45, 0x08, 0, 0, 0, 0, 0, 0, 0, 0];
+        input[2..].copy_from_slice(&2.5f64.to_be_bytes());
+        assert!(matches!(
+            decode(&input),
+            Ok(ApplicationDataValue::Double(x)) if x == 2.5
+        ));
+        assert!(matches!(
+            decode(&[0x44, 0x41, 0xAC, 0x00, 0x00]),
+            Ok(ApplicationDataValue::Real(x)) if x == 21.5
+        ));
+        assert!(matches!(
+            decode(&[0x45, 0x06, 0, 0, 0, 0, 0, 0]),
+            Err(Error::Length(_))
+        ));
+    }
+
+    #[test]
+    fn nan_and_infinite_reals() {
+        let decode = |input| {
+            decode_value(
+                ObjectType::ObjectAnalogInput,
+                PropertyId::PropPresentValue,
+                input,
+            )
+            .unwrap()
+        };
+
+        // quiet NaN with a payload
+        let input = [0x44, 0x7F, 0xC0, 0x00, 0x01];
+        let value = decode(&input);
+        assert!(value.is_unknown_real());
+        assert!(matches!(value, ApplicationDataValue::Real(x) if x.to_bits() == 0x7FC0_0001));
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        value.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &input);
+
+        let value = decode(&[0x44, 0xFF, 0x80, 0x00, 0x00]);
+        assert!(matches!(value, ApplicationDataValue::Real(x) if x == f32::NEG_INFINITY));
+        assert!(!value.is_unknown_real());
+
+        let value = decode(&[0x44, 0x41, 0xAC, 0x00, 0x00]);
+        assert!(!value.is_unknown_real());
+        assert!(!ApplicationDataValue::Null.is_unknown_real());
+        assert!(ApplicationDataValue::Double(f64::NAN).is_unknown_real());
+    }
+
+    #[test]
+    fn alarm_config_bit_strings() {
+        fn decode(property_id: PropertyId, input: &[u8]) -> BitString<'_> {
+            match decode_value(ObjectType::ObjectAnalogInput, property_id, input) {
+                Ok(ApplicationDataValue::BitString(x)) => x,
+                x => panic!("expected a bit string: {:?}", x),
+            }
+        }
+
+        // high limit only
+        let decoded = decode(PropertyId::PropLimitEnable, &[0x82, 6, 0b0100_0000]);
+        match &decoded {
+            BitString::LimitEnable(x) => {
+                assert!(!x.low_limit_enable());
+                assert!(x.high_limit_enable());
+            }
+            x => panic!("unexpected bit string {:?}", x),
+        }
+        let mut output = [0; 3];
+        let mut writer = Writer::new(&mut output);
+        decoded.encode_application(&mut writer);
+        assert_eq!(writer.to_bytes(), &[0x82, 6, 0b0100_0000]);
+
+        // to-offnormal and to-normal
+        match decode(PropertyId::PropEventEnable, &[0x82, 5, 0b1010_0000]) {
+            BitString::EventEnable(x) => {
+                assert!(x.to_offnormal());
+                assert!(!x.to_fault());
+                assert!(x.to_normal());
+            }
+            x => panic!("unexpected bit string {:?}", x),
+        }
+
+        // to-fault not yet acknowledged
+        let decoded = decode(PropertyId::PropAckedTransitions, &[0x82, 5, 0b1010_0000]);
+        match &decoded {
+            BitString::AckedTransitions(x) => {
+                assert!(x.to_offnormal());
+                assert!(!x.to_fault());
+                assert!(x.to_normal());
+            }
+            x => panic!("unexpected bit string {:?}", x),
+        }
+        let mut output = [0; 3];
+        let mut writer = Writer::new(&mut output);
+        decoded.encode_context(2, &mut writer);
+        assert_eq!(writer.to_bytes(), &[0x2A, 5, 0b1010_0000]);
+    }
+
+    #[test]
+    fn raw_character_strings() {
+        // ucs-2 "hi" and utf8 with an invalid byte are passed through untouched
+        let input = [
+            0x75, 0x05, 0x04, 0x00, b'h', 0x00, b'i', 0x73, 0x00, b'a', 0xFF,
+        ];
+        let mut reader = Reader::new_with_len(input.len());
+        let ucs2 = CharacterStringRaw::decode_application(&mut reader, &input).unwrap();
+        assert_eq!(ucs2.encoding, 4);
+        assert_eq!(ucs2.bytes, &[0x00, b'h', 0x00, b'i']);
+        assert!(ucs2.to_character_string().is_err());
+        let invalid = CharacterStringRaw::decode_application(&mut reader, &input).unwrap();
+        assert_eq!(invalid.encoding, CharacterStringRaw::UTF8);
+        assert!(matches!(
+            invalid.to_character_string(),
+            Err(Error::InvalidValue(_))
+        ));
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        ucs2.encode(&mut writer);
+        invalid.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), input);
+
+        // the validating path is unchanged
+        let input = [0x73, 0x00, b'h', b'i'];
+        let mut reader = Reader::new_with_len(input.len());
+        let raw = CharacterStringRaw::decode_application(&mut reader, &input).unwrap();
+        assert_eq!(raw.to_character_string().unwrap().inner, "hi");
+    }
+}
@@ -1 +1,3 @@
+#[cfg(feature = "alloc")]
+pub mod any_value;
 pub mod data_value;
@@ -1,5 +1,3 @@
-use core::usize;
-
 use crate::common::{
     error::Error,
     io::{Reader, Writer},
@@ -42,7 +40,7 @@ impl<'a> Segment<'a> {
             window_size,
             service_choice,
 
-            data: &buf[reader.index..],
+            data: reader.remaining(buf),
         })
     }
 
@@ -101,6 +99,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn decoding() {
         // decoding
         let input: [u8; 6] = [1, 12, 1, 1, 2, 3];
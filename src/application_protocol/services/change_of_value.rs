@@ -1,16 +1,22 @@
 // change of value
 
 use crate::{
-    application_protocol::primitives::data_value::ApplicationDataValue,
+    application_protocol::{
+        confirmed::{ConfirmedServiceChoice, SimpleAck},
+        primitives::data_value::{ApplicationDataValue, BitString},
+        unconfirmed::UnconfirmedRequest,
+    },
     common::{
         error::Error,
         helper::{
-            decode_unsigned, encode_context_bool, encode_context_object_id,
-            encode_context_unsigned, get_tagged_body_for_tag,
+            decode_unsigned, encode_closing_tag, encode_context_bool, encode_context_enumerated,
+            encode_context_object_id, encode_context_unsigned, encode_opening_tag,
+            get_tagged_body_for_tag,
         },
         io::{Reader, Writer},
         object_id::{ObjectId, ObjectType},
         property_id::PropertyId,
+        spec::Status,
         tag::{Tag, TagNumber},
     },
 };
@@ -28,48 +34,155 @@ pub struct CovNotification<'a> {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct CovNotificationValues<'a> {
-    _property_results: &'a [PropertyResult<'a>], // use this when encoding is implemented
+    property_results: &'a [PropertyResult<'a>],
     object_id: ObjectId,
     buf: &'a [u8],
 }
 
+impl<'a> CovNotificationValues<'a> {
+    pub fn new(object_id: ObjectId, property_results: &'a [PropertyResult<'a>]) -> Self {
+        Self {
+            property_results,
+            object_id,
+            buf: &[],
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        if self.property_results.is_empty() {
+            // decoded values are written back out as is
+            writer.extend_from_slice(self.buf);
+        }
+        for item in self.property_results {
+            item.encode(writer);
+        }
+    }
+
+    // almost every cov notification carries the present value and status flags of the monitored object
+    pub fn present_value_and_status(
+        &self,
+    ) -> Result<(Option<ApplicationDataValue<'a>>, Option<Status>), Error> {
+        let mut present_value = None;
+        let mut status = None;
+        for item in self {
+            let item = item?;
+            match (item.id, item.value) {
+                (PropertyId::PropPresentValue, value) => present_value = Some(value),
+                (
+                    PropertyId::PropStatusFlags,
+                    ApplicationDataValue::BitString(BitString::Status(x)),
+                ) => status = Some(x),
+                _ => {}
+            }
+        }
+        Ok((present_value, status))
+    }
+}
+
+// BACnetPropertyValue - the priority is only used when the value is written (1 is the highest, 16 the lowest)
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PropertyResult<'a> {
     pub id: PropertyId,
+    pub array_index: Option<u32>,
     pub value: ApplicationDataValue<'a>,
+    pub priority: Option<u8>,
 }
 
 impl<'a> PropertyResult<'a> {
+    const TAG_PROPERTY_ID: u8 = 0;
+    const TAG_ARRAY_INDEX: u8 = 1;
+    const TAG_VALUE: u8 = 2;
+    const TAG_PRIORITY: u8 = 3;
+
+    pub fn new(id: PropertyId, value: ApplicationDataValue<'a>) -> Self {
+        Self {
+            id,
+            array_index: None,
+            value,
+            priority: None,
+        }
+    }
+
+    pub fn with_array_index(mut self, array_index: u32) -> Self {
+        self.array_index = Some(array_index);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+        encode_opening_tag(writer, Self::TAG_VALUE);
+        self.value.encode(writer);
+        encode_closing_tag(writer, Self::TAG_VALUE);
+        if let Some(priority) = self.priority {
+            encode_context_unsigned(writer, Self::TAG_PRIORITY, priority as u32);
+        }
+    }
+
     pub fn decode(reader: &mut Reader, buf: &'a [u8], object_id: &ObjectId) -> Result<Self, Error> {
         // property id
         let tag = Tag::decode_expected(
             reader,
             buf,
-            TagNumber::ContextSpecific(0),
+            TagNumber::ContextSpecific(Self::TAG_PROPERTY_ID),
             "CovNotification next property_id",
         )?;
         let property_id: PropertyId = (decode_unsigned(tag.value, reader, buf)? as u32).into();
 
+        // array index (optional)
+        let mut tag = Tag::decode(reader, buf)?;
+        let mut array_index = None;
+        if let TagNumber::ContextSpecific(Self::TAG_ARRAY_INDEX) = tag.number {
+            array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+            tag = Tag::decode(reader, buf)?;
+        }
+
         // value
-        Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::ContextSpecificOpening(2),
+        tag.expect_number(
             "CovNotification next expected value opening tag",
+            TagNumber::ContextSpecificOpening(Self::TAG_VALUE),
         )?;
         let tag = Tag::decode(reader, buf)?;
         let value = ApplicationDataValue::decode(&tag, object_id, &property_id, reader, buf)?;
         Tag::decode_expected(
             reader,
             buf,
-            TagNumber::ContextSpecificClosing(2),
+            TagNumber::ContextSpecificClosing(Self::TAG_VALUE),
             "CovNotification next expected value closing tag",
         )?;
 
+        // priority (optional)
+        let mut priority = None;
+        if !reader.eof() {
+            let index = reader.index;
+            let tag = Tag::decode(reader, buf)?;
+            if let TagNumber::ContextSpecific(Self::TAG_PRIORITY) = tag.number {
+                let value = decode_unsigned(tag.value, reader, buf)?;
+                if !(1..=16).contains(&value) {
+                    return Err(Error::InvalidValue(
+                        "CovNotification priority should be between 1 and 16",
+                    ));
+                }
+                priority = Some(value as u8);
+            } else {
+                // not ours, leave it for the next property
+                reader.index = index;
+            }
+        }
+
         Ok(PropertyResult {
             id: property_id,
+            array_index,
             value,
+            priority,
         })
     }
 }
@@ -81,6 +194,58 @@ impl<'a> CovNotification<'a> {
     const TAG_LIFETIME: u8 = 3;
     const TAG_LIST_OF_VALUES: u8 = 4;
 
+    pub fn new(
+        process_id: u32,
+        device_id: ObjectId,
+        object_id: ObjectId,
+        time_remaining_seconds: u32,
+        values: CovNotificationValues<'a>,
+    ) -> Self {
+        Self {
+            process_id,
+            device_id,
+            object_id,
+            time_remaining_seconds,
+            values,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+        encode_context_object_id(writer, Self::TAG_DEVICE_ID, &self.device_id);
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_context_unsigned(writer, Self::TAG_LIFETIME, self.time_remaining_seconds);
+        encode_opening_tag(writer, Self::TAG_LIST_OF_VALUES);
+        self.values.encode(writer);
+        encode_closing_tag(writer, Self::TAG_LIST_OF_VALUES);
+    }
+
+    // an unconfirmed notification pushed by a server to a subscriber
+    pub fn new_unconfirmed(
+        process_id: u32,
+        device_id: ObjectId,
+        object_id: ObjectId,
+        time_remaining_seconds: u32,
+        property_results: &'a [PropertyResult<'a>],
+    ) -> UnconfirmedRequest<'a> {
+        let values = CovNotificationValues::new(object_id, property_results);
+        UnconfirmedRequest::CovNotification(Self::new(
+            process_id,
+            device_id,
+            object_id,
+            time_remaining_seconds,
+            values,
+        ))
+    }
+
+    // the response to a confirmed cov notification
+    pub fn ack(invoke_id: u8) -> SimpleAck {
+        SimpleAck {
+            invoke_id,
+            service_choice: ConfirmedServiceChoice::CovNotification,
+        }
+    }
+
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         // parse a tag, starting from after the pdu type and service choice
 
@@ -134,7 +299,7 @@ impl<'a> CovNotification<'a> {
         )?;
         let values = CovNotificationValues {
             buf,
-            _property_results: &[],
+            property_results: &[],
             object_id,
         };
 
@@ -226,4 +391,175 @@ impl SubscribeCov {
         // lifetime of subscription
         encode_context_unsigned(writer, Self::TAG_LIFETIME, self.lifetime_seconds);
     }
+
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{ConfirmedRequest, ConfirmedRequestService, ConfirmedServiceChoice},
+            primitives::data_value::{ApplicationDataValue, BitString, Enumerated},
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{Binary, Status},
+        },
+    };
+
+    use super::{CovNotification, CovNotificationValues, PropertyResult};
+
+    #[test]
+    fn confirmed_cov_notification() {
+        // present value and status flags of analog input 3 from device 1234, invoke id 4
+        let input = [
+            0x00, 0x05, 0x04, 0x01, 0x09, 0x09, 0x1C, 0x02, 0x00, 0x04, 0xD2, 0x2C, 0x00, 0x00,
+            0x00, 0x03, 0x39, 0x3C, 0x4E, 0x09, 0x55, 0x2E, 0x44, 0x41, 0xB4, 0x00, 0x00, 0x2F,
+            0x09, 0x6F, 0x2E, 0x82, 0x04, 0x40, 0x2F, 0x4F,
+        ];
+        let mut reader = Reader::new_with_len(input.len());
+        let decoded = ApplicationPdu::decode(&mut reader, &input).unwrap();
+        let (invoke_id, notification) = match decoded {
+            ApplicationPdu::ConfirmedRequest(ConfirmedRequest {
+                invoke_id,
+                service: ConfirmedRequestService::CovNotification(x),
+                ..
+            }) => (invoke_id, x),
+            x => panic!("expected confirmed cov notification: {:?}", x),
+        };
+        assert_eq!(notification.process_id, 9);
+        assert_eq!(notification.device_id.id, 1234);
+        assert_eq!(notification.object_id.id, 3);
+        assert_eq!(notification.time_remaining_seconds, 60);
+
+        let (present_value, status) = notification.values.present_value_and_status().unwrap();
+        assert!(matches!(present_value, Some(ApplicationDataValue::Real(x)) if x == 22.5));
+        assert!(status.unwrap().fault());
+
+        let ack = CovNotification::ack(invoke_id);
+        assert_eq!(ack.invoke_id, 4);
+        assert!(matches!(
+            ack.service_choice,
+            ConfirmedServiceChoice::CovNotification
+        ));
+    }
+
+    #[test]
+    fn unconfirmed_cov_notification() {
+        let values = [
+            PropertyResult::new(
+                PropertyId::PropPresentValue,
+                ApplicationDataValue::Enumerated(Enumerated::Binary(Binary::On)),
+            ),
+            PropertyResult::new(
+                PropertyId::PropStatusFlags,
+                ApplicationDataValue::BitString(BitString::Status(Status { inner: 0 })),
+            ),
+        ];
+        let request = CovNotification::new_unconfirmed(
+            1,
+            ObjectId::new(ObjectType::ObjectDevice, 77),
+            ObjectId::new(ObjectType::ObjectBinaryValue, 2),
+            0,
+            &values,
+        );
+        let apdu = ApplicationPdu::UnconfirmedRequest(request);
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        apdu.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(encoded[..2], [0x10, 0x02]);
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = ApplicationPdu::decode(&mut reader, encoded).unwrap();
+        let notification = match &decoded {
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::CovNotification(x)) => x,
+            x => panic!("expected unconfirmed cov notification: {:?}", x),
+        };
+        assert_eq!(notification.process_id, 1);
+        assert_eq!(notification.device_id.id, 77);
+        assert_eq!(notification.object_id.id, 2);
+        assert_eq!(notification.time_remaining_seconds, 0);
+
+        let (present_value, status) = notification.values.present_value_and_status().unwrap();
+        assert!(matches!(
+            present_value,
+            Some(ApplicationDataValue::Enumerated(Enumerated::Binary(
+                Binary::On
+            )))
+        ));
+        assert!(!status.unwrap().in_alarm());
+
+        // a decoded notification encodes back to the same bytes
+        let mut buf2 = [0; 64];
+        let mut writer = Writer::new(&mut buf2);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), encoded);
+    }
+
+    #[test]
+    fn property_value_priority() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 1);
+        let values = [
+            PropertyResult::new(
+                PropertyId::PropPresentValue,
+                ApplicationDataValue::Real(1.5),
+            )
+            .with_priority(8),
+            PropertyResult::new(
+                PropertyId::PropPriorityArray,
+                ApplicationDataValue::Real(2.5),
+            )
+            .with_array_index(16),
+            PropertyResult::new(
+                PropertyId::PropPresentValue,
+                ApplicationDataValue::Real(3.5),
+            ),
+        ];
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        CovNotificationValues::new(object_id, &values).encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let decoded = CovNotificationValues {
+            property_results: &[],
+            object_id,
+            buf: encoded,
+        };
+        let mut decoded = decoded.into_iter().map(|x| x.unwrap());
+        let first = decoded.next().unwrap();
+        assert!(matches!(first.value, ApplicationDataValue::Real(x) if x == 1.5));
+        assert_eq!(first.array_index, None);
+        assert_eq!(first.priority, Some(8));
+        let second = decoded.next().unwrap();
+        assert_eq!(second.id, PropertyId::PropPriorityArray);
+        assert_eq!(second.array_index, Some(16));
+        assert_eq!(second.priority, None);
+        let third = decoded.next().unwrap();
+        assert!(matches!(third.value, ApplicationDataValue::Real(x) if x == 3.5));
+        assert_eq!(third.priority, None);
+        assert!(decoded.next().is_none());
+
+        // priority out of range
+        let values = [PropertyResult::new(
+            PropertyId::PropPresentValue,
+            ApplicationDataValue::Real(1.5),
+        )
+        .with_priority(17)];
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        CovNotificationValues::new(object_id, &values).encode(&mut writer);
+        let encoded = writer.to_bytes();
+        let mut reader = Reader::new_with_len(encoded.len());
+        assert!(PropertyResult::decode(&mut reader, encoded, &object_id).is_err());
+    }
 }
@@ -0,0 +1,358 @@
+// event notification (alarms and events)
+
+use crate::{
+    application_protocol::{
+        confirmed::{ConfirmedServiceChoice, SimpleAck},
+        primitives::data_value::CharacterString,
+    },
+    common::{
+        error::Error,
+        helper::{
+            check_len, decode_constructed_value, decode_context_object_id, decode_unsigned,
+            encode_closing_tag, encode_context_bool, encode_context_object_id,
+            encode_context_unsigned, encode_opening_tag,
+        },
+        io::{Reader, Writer},
+        notification_parameters::NotificationParameters,
+        object_id::{ObjectId, ObjectType},
+        spec::{EventState, NotifyType},
+        tag::{Tag, TagNumber},
+        time_stamp::TimeStamp,
+    },
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EventNotification<'a> {
+    pub process_id: u32,
+    pub device_id: ObjectId,
+    pub object_id: ObjectId,
+    pub time_stamp: TimeStamp,
+    pub notification_class: u32,
+    pub priority: u8,
+    pub event_type: u32, // BACnetEventType (e.g. 5 is out-of-range)
+    pub message_text: Option<&'a str>,
+    pub notify_type: NotifyType,
+    // ack required and from state are only left out of an ack notification
+    pub ack_required: Option<bool>,
+    pub from_state: Option<EventState>,
+    pub to_state: EventState,
+    // why the event fired (see NotificationParameters)
+    pub event_values: Option<NotificationParameters<'a>>,
+}
+
+impl<'a> EventNotification<'a> {
+    const TAG_PROCESS_ID: u8 = 0;
+    const TAG_DEVICE_ID: u8 = 1;
+    const TAG_OBJECT_ID: u8 = 2;
+    const TAG_TIME_STAMP: u8 = 3;
+    const TAG_NOTIFICATION_CLASS: u8 = 4;
+    const TAG_PRIORITY: u8 = 5;
+    const TAG_EVENT_TYPE: u8 = 6;
+    const TAG_MESSAGE_TEXT: u8 = 7;
+    const TAG_NOTIFY_TYPE: u8 = 8;
+    const TAG_ACK_REQUIRED: u8 = 9;
+    const TAG_FROM_STATE: u8 = 10;
+    const TAG_TO_STATE: u8 = 11;
+    const TAG_EVENT_VALUES: u8 = 12;
+
+    // the response to a confirmed event notification
+    pub fn ack(invoke_id: u8) -> SimpleAck {
+        SimpleAck {
+            invoke_id,
+            service_choice: ConfirmedServiceChoice::EventNotification,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+        encode_context_object_id(writer, Self::TAG_DEVICE_ID, &self.device_id);
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_opening_tag(writer, Self::TAG_TIME_STAMP);
+        self.time_stamp.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIME_STAMP);
+        encode_context_unsigned(
+            writer,
+            Self::TAG_NOTIFICATION_CLASS,
+            self.notification_class,
+        );
+        encode_context_unsigned(writer, Self::TAG_PRIORITY, self.priority as u32);
+        encode_context_unsigned(writer, Self::TAG_EVENT_TYPE, self.event_type);
+        if let Some(message_text) = self.message_text {
+            let utf8_encoded = message_text.as_bytes();
+            Tag::new(
+                TagNumber::ContextSpecific(Self::TAG_MESSAGE_TEXT),
+                utf8_encoded.len() as u32 + 1, // keep space for encoding byte
+            )
+            .encode(writer);
+            writer.push(0); // utf8 encoding
+            writer.extend_from_slice(utf8_encoded);
+        }
+        encode_context_unsigned(
+            writer,
+            Self::TAG_NOTIFY_TYPE,
+            self.notify_type.clone() as u32,
+        );
+        if let Some(ack_required) = self.ack_required {
+            encode_context_bool(writer, Self::TAG_ACK_REQUIRED, ack_required);
+        }
+        if let Some(from_state) = &self.from_state {
+            encode_context_unsigned(writer, Self::TAG_FROM_STATE, from_state.clone() as u32);
+        }
+        encode_context_unsigned(writer, Self::TAG_TO_STATE, self.to_state.clone() as u32);
+        if let Some(event_values) = &self.event_values {
+            encode_opening_tag(writer, Self::TAG_EVENT_VALUES);
+            event_values.encode(writer);
+            encode_closing_tag(writer, Self::TAG_EVENT_VALUES);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // parse a tag, starting from after the pdu type and service choice
+        let process_id = decode_context_u32(
+            reader,
+            buf,
+            Self::TAG_PROCESS_ID,
+            "EventNotification process_id",
+        )?;
+
+        let device_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_DEVICE_ID,
+            "EventNotification device_id",
+        )?;
+        if device_id.object_type != ObjectType::ObjectDevice {
+            return Err(Error::InvalidValue(
+                "expected device object type for EventNotification device_id field",
+            ));
+        }
+
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "EventNotification object_id",
+        )?;
+
+        let time_stamp = decode_constructed_value(
+            reader,
+            buf,
+            Self::TAG_TIME_STAMP,
+            "EventNotification time_stamp",
+            TimeStamp::decode,
+        )?;
+
+        let notification_class = decode_context_u32(
+            reader,
+            buf,
+            Self::TAG_NOTIFICATION_CLASS,
+            "EventNotification notification_class",
+        )?;
+
+        let priority = decode_context_u32(
+            reader,
+            buf,
+            Self::TAG_PRIORITY,
+            "EventNotification priority",
+        )?;
+        let priority = u8::try_from(priority)
+            .map_err(|_| Error::InvalidValue("EventNotification priority should be a u8"))?;
+
+        let event_type = decode_context_u32(
+            reader,
+            buf,
+            Self::TAG_EVENT_TYPE,
+            "EventNotification event_type",
+        )?;
+
+        // message text (optional)
+        let mut tag = Tag::decode(reader, buf)?;
+        let mut message_text = None;
+        if let TagNumber::ContextSpecific(Self::TAG_MESSAGE_TEXT) = tag.number {
+            message_text = Some(CharacterString::decode(tag.value, reader, buf)?.inner);
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        tag.expect_number(
+            "EventNotification notify_type",
+            TagNumber::ContextSpecific(Self::TAG_NOTIFY_TYPE),
+        )?;
+        let notify_type = decode_unsigned(tag.value, reader, buf)? as u32;
+        let notify_type = NotifyType::try_from(notify_type)
+            .map_err(|x| Error::InvalidVariant(("NotifyType", x)))?;
+
+        // ack required (optional)
+        let mut tag = Tag::decode(reader, buf)?;
+        let mut ack_required = None;
+        if let TagNumber::ContextSpecific(Self::TAG_ACK_REQUIRED) = tag.number {
+            check_len(tag.value, 1, "EventNotification ack_required")?;
+            ack_required = Some(reader.read_byte(buf)? > 0);
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        // from state (optional)
+        let mut from_state = None;
+        if let TagNumber::ContextSpecific(Self::TAG_FROM_STATE) = tag.number {
+            from_state = Some(decode_event_state(tag.value, reader, buf)?);
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        tag.expect_number(
+            "EventNotification to_state",
+            TagNumber::ContextSpecific(Self::TAG_TO_STATE),
+        )?;
+        let to_state = decode_event_state(tag.value, reader, buf)?;
+
+        // event values (optional)
+        let mut event_values = None;
+        if !reader.eof() {
+            event_values = Some(decode_constructed_value(
+                reader,
+                buf,
+                Self::TAG_EVENT_VALUES,
+                "EventNotification event_values",
+                NotificationParameters::decode,
+            )?);
+        }
+
+        Ok(Self {
+            process_id,
+            device_id,
+            object_id,
+            time_stamp,
+            notification_class,
+            priority,
+            event_type,
+            message_text,
+            notify_type,
+            ack_required,
+            from_state,
+            to_state,
+            event_values,
+        })
+    }
+}
+
+fn decode_context_u32(
+    reader: &mut Reader,
+    buf: &[u8],
+    tag_number: u8,
+    context: &'static str,
+) -> Result<u32, Error> {
+    let tag = Tag::decode_expected(reader, buf, TagNumber::ContextSpecific(tag_number), context)?;
+    let value = decode_unsigned(tag.value, reader, buf)?;
+    u32::try_from(value).map_err(|_| Error::InvalidValue(context))
+}
+
+fn decode_event_state(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<EventState, Error> {
+    let value = decode_unsigned(len, reader, buf)? as u32;
+    EventState::try_from(value).map_err(|x| Error::InvalidVariant(("EventState", x)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        notification_parameters::NotificationParameters,
+        object_id::{ObjectId, ObjectType},
+        spec::{EventState, NotifyType},
+        time_stamp::TimeStamp,
+    };
+
+    use super::EventNotification;
+
+    // an out-of-range alarm from analog input 1 on device 1234 that went to high-limit
+    const OUT_OF_RANGE: [u8; 55] = [
+        0x09, 0x01, // process id
+        0x1C, 0x02, 0x00, 0x04, 0xD2, // initiating device 1234
+        0x2C, 0x00, 0x00, 0x00, 0x01, // analog-input 1
+        0x3E, 0x19, 0x05, 0x3F, // time stamp (sequence number)
+        0x49, 0x01, // notification class
+        0x59, 0x64, // priority
+        0x69, 0x05, // out-of-range
+        0x7A, 0x00, 0x48, // message text "H"
+        0x89, 0x00, // alarm
+        0x99, 0x01, // ack required
+        0xA9, 0x00, // from normal
+        0xB9, 0x03, // to high-limit
+        0xCE, 0x5E, 0x0C, 0x41, 0xFC, 0x00, 0x00, 0x1A, 0x00, 0x80, 0x2C, 0x3F, 0x80, 0x00, 0x00,
+        0x3C, 0x41, 0xF0, 0x00, 0x00, 0x5F, 0xCF, // event values
+    ];
+
+    fn decode(input: &[u8]) -> EventNotification<'_> {
+        let mut reader = Reader::new_with_len(input.len());
+        let notification = EventNotification::decode(&mut reader, input).unwrap();
+        assert!(reader.eof());
+        notification
+    }
+
+    #[test]
+    fn out_of_range_high_limit() {
+        let notification = decode(&OUT_OF_RANGE);
+        assert_eq!(notification.process_id, 1);
+        assert_eq!(notification.device_id.id, 1234);
+        assert_eq!(notification.object_id.id, 1);
+        assert!(matches!(
+            notification.time_stamp,
+            TimeStamp::SequenceNumber(5)
+        ));
+        assert_eq!(notification.notification_class, 1);
+        assert_eq!(notification.priority, 100);
+        assert_eq!(notification.event_type, 5);
+        assert_eq!(notification.message_text, Some("H"));
+        assert!(matches!(notification.notify_type, NotifyType::Alarm));
+        assert_eq!(notification.ack_required, Some(true));
+        assert!(matches!(notification.from_state, Some(EventState::Normal)));
+        assert!(matches!(notification.to_state, EventState::HighLimit));
+        assert!(matches!(
+            &notification.event_values,
+            Some(NotificationParameters::OutOfRange {
+                exceeding_value,
+                status_flags,
+                deadband,
+                exceeded_limit,
+            }) if *exceeding_value == 31.5
+                && status_flags.in_alarm()
+                && *deadband == 1.0
+                && *exceeded_limit == 30.0
+        ));
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        notification.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), OUT_OF_RANGE);
+    }
+
+    #[test]
+    fn ack_notification() {
+        // an ack notification leaves out the message text, ack required, from state and event values
+        let notification = EventNotification {
+            process_id: 1,
+            device_id: ObjectId::new(ObjectType::ObjectDevice, 1234),
+            object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            time_stamp: TimeStamp::SequenceNumber(6),
+            notification_class: 1,
+            priority: 100,
+            event_type: 5,
+            message_text: None,
+            notify_type: NotifyType::AckNotification,
+            ack_required: None,
+            from_state: None,
+            to_state: EventState::HighLimit,
+            event_values: None,
+        };
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        notification.encode(&mut writer);
+
+        let decoded = decode(writer.to_bytes());
+        assert!(matches!(decoded.time_stamp, TimeStamp::SequenceNumber(6)));
+        assert!(decoded.message_text.is_none());
+        assert!(matches!(decoded.notify_type, NotifyType::AckNotification));
+        assert!(decoded.ack_required.is_none());
+        assert!(decoded.from_state.is_none());
+        assert!(matches!(decoded.to_state, EventState::HighLimit));
+        assert!(decoded.event_values.is_none());
+    }
+}
@@ -31,6 +31,10 @@ impl IAm {
         encode_application_unsigned(writer, self.vendor_id as u64);
     }
 
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
         // parse a tag, starting from after the pdu type and service choice, then the object_id
         let tag = Tag::decode(reader, buf)?;
@@ -87,3 +91,94 @@ impl IAm {
         })
     }
 }
+
+// what a device said it can do in its IAm
+// cache this per device and check it before sending a request that could need segmentation
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CapabilityProfile {
+    pub device_id: ObjectId,
+    pub max_apdu: usize,
+    pub segmentation: Segmentation,
+    pub vendor_id: u16,
+}
+
+impl CapabilityProfile {
+    // the device can reassemble a segmented request sent to it
+    pub fn supports_segmented_receive(&self) -> bool {
+        matches!(
+            self.segmentation,
+            Segmentation::Both | Segmentation::Receive
+        )
+    }
+
+    // the device can send a segmented response
+    pub fn supports_segmented_transmit(&self) -> bool {
+        matches!(
+            self.segmentation,
+            Segmentation::Both | Segmentation::Transmit
+        )
+    }
+
+    // true if an apdu of this many bytes can be sent without segmentation
+    pub fn fits_in_one_apdu(&self, len: usize) -> bool {
+        len <= self.max_apdu
+    }
+}
+
+impl From<&IAm> for CapabilityProfile {
+    fn from(value: &IAm) -> Self {
+        Self {
+            device_id: value.device_id,
+            max_apdu: value.max_apdu,
+            segmentation: value.segmentation.clone(),
+            vendor_id: value.vendor_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        spec::Segmentation,
+    };
+
+    use super::{CapabilityProfile, IAm};
+
+    #[test]
+    fn capability_profile_from_i_am() {
+        let i_am = IAm {
+            device_id: ObjectId::new(ObjectType::ObjectDevice, 1234),
+            max_apdu: 480,
+            segmentation: Segmentation::Transmit,
+            vendor_id: 5,
+        };
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        i_am.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        // skip the service choice
+        let mut reader = Reader::new_with_len(encoded.len());
+        reader.index = 1;
+        let decoded = IAm::decode(&mut reader, encoded).unwrap();
+
+        let profile = CapabilityProfile::from(&decoded);
+        assert_eq!(profile.device_id.id, 1234);
+        assert_eq!(profile.max_apdu, 480);
+        assert_eq!(profile.vendor_id, 5);
+        assert!(!profile.supports_segmented_receive());
+        assert!(profile.supports_segmented_transmit());
+        assert!(profile.fits_in_one_apdu(480));
+        assert!(!profile.fits_in_one_apdu(481));
+
+        let mut both = profile.clone();
+        both.segmentation = Segmentation::Both;
+        assert!(both.supports_segmented_receive());
+        both.segmentation = Segmentation::None;
+        assert!(!both.supports_segmented_receive());
+        assert!(!both.supports_segmented_transmit());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    application_protocol::{
+        primitives::data_value::{ApplicationDataValue, CharacterString},
+        unconfirmed::UnconfirmedServiceChoice,
+    },
+    common::{
+        error::Error,
+        helper::encode_application_object_id,
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+};
+
+// the response to a WhoHas
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct IHave<'a> {
+    pub device_id: ObjectId,
+    pub object_id: ObjectId,
+    pub object_name: &'a str,
+}
+
+impl<'a> IHave<'a> {
+    pub fn new(device_id: ObjectId, object_id: ObjectId, object_name: &'a str) -> Self {
+        Self {
+            device_id,
+            object_id,
+            object_name,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(UnconfirmedServiceChoice::IHave as u8);
+        encode_application_object_id(writer, &self.device_id);
+        encode_application_object_id(writer, &self.object_id);
+        ApplicationDataValue::CharacterString(CharacterString {
+            inner: self.object_name,
+        })
+        .encode(writer);
+    }
+
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "IHave decode device_id",
+        )?;
+        let device_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "IHave decode object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::CharacterString),
+            "IHave decode object_name",
+        )?;
+        let object_name = CharacterString::decode(tag.value, reader, buf)?.inner;
+
+        Ok(Self {
+            device_id,
+            object_id,
+            object_name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu, services::i_have::IHave,
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+        },
+    };
+
+    #[test]
+    fn encode_then_decode() {
+        let i_have = IHave::new(
+            ObjectId::new(ObjectType::ObjectDevice, 5),
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            "T",
+        );
+        let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IHave(i_have.clone()));
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        apdu.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(
+            encoded,
+            &[
+                0x10, 0x01, 0xC4, 0x02, 0x00, 0x00, 0x05, 0xC4, 0x00, 0x00, 0x00, 0x01, 0x72, 0x00,
+                0x54
+            ]
+        );
+        assert_eq!(i_have.encoded_len(), encoded.len() - 1);
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        match ApplicationPdu::decode(&mut reader, encoded).unwrap() {
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IHave(x)) => {
+                assert_eq!(x.device_id, i_have.device_id);
+                assert_eq!(x.object_id, i_have.object_id);
+                assert_eq!(x.object_name, "T");
+            }
+            x => panic!("expected IHave: {:?}", x),
+        }
+    }
+}
@@ -1,8 +1,13 @@
 pub mod change_of_value;
+pub mod event_notification;
 pub mod i_am;
+pub mod i_have;
 pub mod read_property;
+pub mod read_property_conditional;
 pub mod read_property_multiple;
 pub mod read_range;
 pub mod time_synchronization;
+pub mod virtual_terminal;
+pub mod who_has;
 pub mod who_is;
 pub mod write_property;
@@ -2,19 +2,20 @@ use crate::{
     application_protocol::{
         confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
         primitives::data_value::ApplicationDataValue,
+        services::read_property_multiple::ReadPropertyMultiple,
     },
     common::{
+        cov_subscription::CovSubscriptionList,
         error::Error,
-        helper::{
-            decode_context_object_id, decode_context_property_id, encode_closing_tag,
-            encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
-            encode_opening_tag, get_tagged_body_for_tag,
-        },
+        helper::{decode_context_object_id, decode_context_property_id, get_tagged_body_for_tag},
         io::{Reader, Writer},
         object_id::ObjectId,
         property_id::PropertyId,
+        recipient::AddressBindingList,
+        scale::Scale,
         spec::BACNET_ARRAY_ALL,
         tag::{ApplicationTagNumber, Tag, TagNumber},
+        time_stamp::EventTimeStamps,
     },
     network_protocol::data_link::DataLink,
 };
@@ -24,6 +25,13 @@ use crate::{
 pub enum ReadPropertyValue<'a> {
     ObjectIdList(ObjectIdList<'a>),
     ApplicationDataValue(ApplicationDataValue<'a>),
+    ApplicationDataValueList(ApplicationDataValueList<'a>),
+    AddressBindingList(AddressBindingList<'a>),
+    EventTimeStamps(EventTimeStamps),
+    Scale(Scale),
+    CovSubscriptionList(CovSubscriptionList<'a>),
+    // the members of a group object (a list of read access specifications)
+    GroupMembers(ReadPropertyMultiple<'a>),
 }
 
 #[derive(Debug, Clone)]
@@ -108,6 +116,98 @@ impl<'a> Iterator for ObjectIdIter<'a> {
     }
 }
 
+// used when a property (e.g. PropPriorityArray) returns more than one value
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ApplicationDataValueList<'a> {
+    object_id: ObjectId,
+    property_id: PropertyId,
+    values: &'a [ApplicationDataValue<'a>],
+    buf: &'a [u8],
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ApplicationDataValueIter<'a> {
+    object_id: ObjectId,
+    property_id: PropertyId,
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> ApplicationDataValueList<'a> {
+    pub fn new(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        values: &'a [ApplicationDataValue<'a>],
+    ) -> Self {
+        Self {
+            object_id,
+            property_id,
+            values,
+            buf: &[],
+        }
+    }
+
+    pub fn new_from_buf(object_id: ObjectId, property_id: PropertyId, buf: &'a [u8]) -> Self {
+        Self {
+            object_id,
+            property_id,
+            values: &[],
+            buf,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        for value in self.values {
+            value.encode(writer);
+        }
+    }
+}
+
+impl<'a> ApplicationDataValueIter<'a> {
+    pub fn new(object_id: ObjectId, property_id: PropertyId, buf: &'a [u8]) -> Self {
+        Self {
+            object_id,
+            property_id,
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+        }
+    }
+
+    fn next_internal(&mut self) -> Result<ApplicationDataValue<'a>, Error> {
+        let tag = Tag::decode(&mut self.reader, self.buf)?;
+        ApplicationDataValue::decode(
+            &tag,
+            &self.object_id,
+            &self.property_id,
+            &mut self.reader,
+            self.buf,
+        )
+    }
+}
+
+impl<'a> IntoIterator for &'_ ApplicationDataValueList<'a> {
+    type Item = Result<ApplicationDataValue<'a>, Error>;
+    type IntoIter = ApplicationDataValueIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ApplicationDataValueIter::new(self.object_id, self.property_id, self.buf)
+    }
+}
+
+impl<'a> Iterator for ApplicationDataValueIter<'a> {
+    type Item = Result<ApplicationDataValue<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            None
+        } else {
+            Some(self.next_internal())
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadPropertyAck<'a> {
@@ -133,9 +233,9 @@ impl<'a> TryFrom<DataLink<'a>> for ReadPropertyAck<'a> {
 impl<'a> ReadPropertyAck<'a> {
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(ConfirmedServiceChoice::ReadProperty as u8);
-        encode_context_object_id(writer, 0, &self.object_id);
-        encode_context_enumerated(writer, 1, &self.property_id);
-        encode_opening_tag(writer, 3);
+        writer.push_context_object_id(0, &self.object_id);
+        writer.push_context_property_id(1, &self.property_id);
+        writer.push_opening_tag(3);
         match &self.property_value {
             ReadPropertyValue::ApplicationDataValue(value) => {
                 value.encode(writer);
@@ -143,8 +243,26 @@ impl<'a> ReadPropertyAck<'a> {
             ReadPropertyValue::ObjectIdList(value) => {
                 value.encode(writer);
             }
+            ReadPropertyValue::ApplicationDataValueList(value) => {
+                value.encode(writer);
+            }
+            ReadPropertyValue::AddressBindingList(value) => {
+                value.encode(writer);
+            }
+            ReadPropertyValue::EventTimeStamps(value) => {
+                value.encode(writer);
+            }
+            ReadPropertyValue::Scale(value) => {
+                value.encode(writer);
+            }
+            ReadPropertyValue::CovSubscriptionList(value) => {
+                value.encode(writer);
+            }
+            ReadPropertyValue::GroupMembers(value) => {
+                value.encode(writer);
+            }
         }
-        encode_closing_tag(writer, 3);
+        writer.push_closing_tag(3);
     }
 
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
@@ -170,11 +288,68 @@ impl<'a> ReadPropertyAck<'a> {
                     property_value,
                 })
             }
+            PropertyId::PropDeviceAddressBinding => {
+                let property_value =
+                    ReadPropertyValue::AddressBindingList(AddressBindingList::new_from_buf(buf));
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    property_value,
+                })
+            }
+            PropertyId::PropEventTimeStamps => {
+                let property_value =
+                    ReadPropertyValue::EventTimeStamps(EventTimeStamps::decode(&mut reader, buf)?);
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    property_value,
+                })
+            }
+            PropertyId::PropActiveCovSubscriptions => {
+                let property_value =
+                    ReadPropertyValue::CovSubscriptionList(CovSubscriptionList::new_from_buf(buf));
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    property_value,
+                })
+            }
+            PropertyId::PropListOfGroupMembers => {
+                let property_value =
+                    ReadPropertyValue::GroupMembers(ReadPropertyMultiple::new_from_buf(buf));
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    property_value,
+                })
+            }
+            PropertyId::PropScale => {
+                let property_value = ReadPropertyValue::Scale(Scale::decode(&mut reader, buf)?);
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    property_value,
+                })
+            }
             property_id => {
                 let tag = Tag::decode(&mut reader, buf)?;
                 let value =
                     ApplicationDataValue::decode(&tag, &object_id, &property_id, &mut reader, buf)?;
-                let property_value = ReadPropertyValue::ApplicationDataValue(value);
+
+                // more than one value means the property is a list or an array
+                let property_value = if reader.eof() {
+                    ReadPropertyValue::ApplicationDataValue(value)
+                } else {
+                    ReadPropertyValue::ApplicationDataValueList(
+                        ApplicationDataValueList::new_from_buf(object_id, property_id, buf),
+                    )
+                };
 
                 Ok(Self {
                     object_id,
@@ -205,17 +380,21 @@ impl ReadProperty {
 
     pub fn encode(&self, writer: &mut Writer) {
         // object_id
-        encode_context_object_id(writer, 0, &self.object_id);
+        writer.push_context_object_id(0, &self.object_id);
 
         // property_id
-        encode_context_enumerated(writer, 1, &self.property_id);
+        writer.push_context_property_id(1, &self.property_id);
 
         // array_index
         if self.array_index != BACNET_ARRAY_ALL {
-            encode_context_unsigned(writer, 2, self.array_index);
+            writer.push_context_unsigned(2, self.array_index);
         }
     }
 
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
         // object_id
         let object_id = decode_context_object_id(reader, buf, 0, "ReadProperty decode object_id")?;
@@ -231,3 +410,222 @@ impl ReadProperty {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::{ApplicationDataValue, Date, Time},
+            services::read_property_multiple::{ReadAccessSpecification, ReadPropertyMultiple},
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{ApplicationDataValueList, ReadPropertyAck, ReadPropertyValue};
+
+    #[test]
+    fn accumulator_present_value() {
+        // an energy meter count that no longer fits in a u32 (5 bytes)
+        let input = [
+            0x0C, 0x05, 0xC0, 0x00, 0x01, // accumulator 1
+            0x19, 0x55, // present value
+            0x3E, 0x25, 0x05, 0x01, 0x00, 0x00, 0x00, 0x2A, 0x3F,
+        ];
+        let mut reader = Reader::new_with_len(input.len());
+        let ack = ReadPropertyAck::decode(&mut reader, &input).unwrap();
+        assert_eq!(ack.object_id.object_type, ObjectType::ObjectAccumulator);
+        assert!(matches!(
+            ack.property_value,
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::BigUnsignedInt(
+                0x01_0000_002A
+            ))
+        ));
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+        assert_eq!(&writer.to_bytes()[1..], &input); // skip the service choice
+    }
+
+    #[test]
+    fn priority_array_ack() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogOutput, 1);
+        let property_id = PropertyId::PropPriorityArray;
+        let values: [ApplicationDataValue; 16] = core::array::from_fn(|i| match i {
+            7 => ApplicationDataValue::Real(50.0),
+            _ => ApplicationDataValue::Null,
+        });
+        let ack = ReadPropertyAck {
+            object_id,
+            property_id,
+            property_value: ReadPropertyValue::ApplicationDataValueList(
+                ApplicationDataValueList::new(object_id, property_id, &values),
+            ),
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        // skip the service choice which is decoded by the ComplexAck
+        let mut reader = Reader::new_with_len(encoded.len());
+        reader.index = 1;
+        let decoded = ReadPropertyAck::decode(&mut reader, encoded).unwrap();
+        let list = match decoded.property_value {
+            ReadPropertyValue::ApplicationDataValueList(list) => list,
+            x => panic!("expected a list of values: {:?}", x),
+        };
+
+        let mut count = 0;
+        for (i, value) in list.into_iter().enumerate() {
+            match (i, value.unwrap()) {
+                (7, ApplicationDataValue::Real(x)) => assert_eq!(x, 50.0),
+                (7, x) => panic!("expected real at priority 8: {:?}", x),
+                (_, ApplicationDataValue::Null) => {}
+                (_, x) => panic!("expected null: {:?}", x),
+            }
+            count += 1;
+        }
+        assert_eq!(count, 16);
+    }
+
+    #[test]
+    fn local_date_and_time_acks() {
+        let object_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+        let values = [
+            (
+                PropertyId::PropLocalDate,
+                ApplicationDataValue::Date(Date::new(2024, 3, 14, 4)),
+            ),
+            (
+                PropertyId::PropLocalTime,
+                ApplicationDataValue::Time(Time::new(9, 26, 53, 12)),
+            ),
+        ];
+
+        for (property_id, value) in values {
+            let ack = ReadPropertyAck {
+                object_id,
+                property_id,
+                property_value: ReadPropertyValue::ApplicationDataValue(value),
+            };
+            let mut buf = [0; 32];
+            let mut writer = Writer::new(&mut buf);
+            ack.encode(&mut writer);
+            let encoded = writer.to_bytes();
+
+            // skip the service choice
+            let mut reader = Reader::new_with_len(encoded.len());
+            reader.index = 1;
+            let decoded = ReadPropertyAck::decode(&mut reader, encoded).unwrap();
+            match decoded.property_value {
+                ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Date(date)) => {
+                    assert_eq!(
+                        (date.year, date.month, date.day, date.wday),
+                        (2024, 3, 14, 4)
+                    )
+                }
+                ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Time(time)) => {
+                    assert_eq!(
+                        (time.hour, time.minute, time.second, time.hundredths),
+                        (9, 26, 53, 12)
+                    )
+                }
+                x => panic!("unexpected value: {:?}", x),
+            }
+        }
+    }
+
+    #[test]
+    fn group_members_ack() {
+        fn decode(encoded: &[u8]) -> ReadPropertyAck<'_> {
+            let mut reader = Reader::new_with_len(encoded.len());
+            reader.index = 1; // skip the service choice
+            ReadPropertyAck::decode(&mut reader, encoded).unwrap()
+        }
+
+        let group_id = ObjectId::new(ObjectType::ObjectGroup, 1);
+        let analog_properties = [PropertyId::PropPresentValue, PropertyId::PropStatusFlags];
+        let binary_properties = [PropertyId::PropPresentValue];
+        let members = [
+            ReadAccessSpecification::new(
+                ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                &analog_properties,
+            ),
+            ReadAccessSpecification::new(
+                ObjectId::new(ObjectType::ObjectBinaryValue, 2),
+                &binary_properties,
+            ),
+        ];
+        let ack = ReadPropertyAck {
+            object_id: group_id,
+            property_id: PropertyId::PropListOfGroupMembers,
+            property_value: ReadPropertyValue::GroupMembers(ReadPropertyMultiple::new(&members)),
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let decoded = decode(encoded);
+        let ReadPropertyValue::GroupMembers(members) = &decoded.property_value else {
+            panic!("expected group members: {:?}", decoded.property_value);
+        };
+        let mut members = members.into_iter().map(|x| x.unwrap());
+        let member = members.next().unwrap();
+        assert_eq!(
+            member.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1)
+        );
+        let mut property_ids = member.property_ids.into_iter().map(|x| x.unwrap());
+        assert_eq!(property_ids.next(), Some(PropertyId::PropPresentValue));
+        assert_eq!(property_ids.next(), Some(PropertyId::PropStatusFlags));
+        assert_eq!(property_ids.next(), None);
+        let member = members.next().unwrap();
+        assert_eq!(
+            member.object_id,
+            ObjectId::new(ObjectType::ObjectBinaryValue, 2)
+        );
+        assert_eq!(member.property_ids.into_iter().count(), 1);
+        assert!(members.next().is_none());
+
+        // a decoded list encodes back to the same bytes
+        let mut buf2 = [0; 64];
+        let mut writer = Writer::new(&mut buf2);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), encoded);
+
+        // a member property with an array index (priority-array[8] of analog-output 3)
+        let input = [
+            0x0C, 0x0C, 0x02, 0xC0, 0x00, 0x01, // group 1
+            0x19, 0x35, // list-of-group-members
+            0x3E, 0x0C, 0x00, 0x40, 0x00, 0x03, 0x1E, 0x09, 0x57, 0x19, 0x08, 0x1F, 0x3F,
+        ];
+        let decoded = decode(&input);
+        let ReadPropertyValue::GroupMembers(members) = &decoded.property_value else {
+            panic!("expected group members: {:?}", decoded.property_value);
+        };
+        let member = members.into_iter().next().unwrap().unwrap();
+        assert_eq!(
+            member.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogOutput, 3)
+        );
+        let mut property_ids = member.property_ids.into_iter().map(|x| x.unwrap());
+        assert_eq!(property_ids.next(), Some(PropertyId::PropPriorityArray));
+        assert_eq!(property_ids.next(), None);
+
+        // an empty group
+        let input = [0x0C, 0x0C, 0x02, 0xC0, 0x00, 0x01, 0x19, 0x35, 0x3E, 0x3F];
+        let decoded = decode(&input);
+        assert!(matches!(
+            &decoded.property_value,
+            ReadPropertyValue::GroupMembers(members) if members.into_iter().next().is_none()
+        ));
+    }
+}
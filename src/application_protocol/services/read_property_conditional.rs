@@ -0,0 +1,177 @@
+use crate::{
+    application_protocol::primitives::data_value::ApplicationDataValue,
+    common::{
+        helper::{
+            encode_closing_tag, encode_context_enumerated, encode_context_unsigned,
+            encode_opening_tag,
+        },
+        io::Writer,
+        property_id::PropertyId,
+    },
+};
+
+// ReadPropertyConditional has been removed from the standard but some old devices still use it.
+// Only encoding the request is supported. The ack has the same format as the ReadPropertyMultiple ack.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum SelectionLogic {
+    And = 0,
+    Or = 1,
+    All = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum RelationSpecifier {
+    Equal = 0,
+    NotEqual = 1,
+    LessThan = 2,
+    GreaterThan = 3,
+    LessThanOrEqual = 4,
+    GreaterThanOrEqual = 5,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelectionCriteria<'a> {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub relation: RelationSpecifier,
+    pub value: ApplicationDataValue<'a>,
+}
+
+impl<'a> SelectionCriteria<'a> {
+    const PROPERTY_ID_TAG: u8 = 0;
+    const ARRAY_INDEX_TAG: u8 = 1;
+    const RELATION_TAG: u8 = 2;
+    const VALUE_TAG: u8 = 3;
+
+    pub fn new(
+        property_id: PropertyId,
+        relation: RelationSpecifier,
+        value: ApplicationDataValue<'a>,
+    ) -> Self {
+        Self {
+            property_id,
+            array_index: None,
+            relation,
+            value,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_enumerated(writer, Self::PROPERTY_ID_TAG, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::ARRAY_INDEX_TAG, array_index);
+        }
+        encode_context_unsigned(writer, Self::RELATION_TAG, self.relation as u32);
+        encode_opening_tag(writer, Self::VALUE_TAG);
+        self.value.encode(writer);
+        encode_closing_tag(writer, Self::VALUE_TAG);
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadPropertyConditional<'a> {
+    pub selection_logic: SelectionLogic,
+    pub criteria: &'a [SelectionCriteria<'a>],
+    pub property_ids: &'a [PropertyId], // empty means only return the object ids
+}
+
+impl<'a> ReadPropertyConditional<'a> {
+    const SELECTION_CRITERIA_TAG: u8 = 0;
+    const SELECTION_LOGIC_TAG: u8 = 0;
+    const LIST_OF_CRITERIA_TAG: u8 = 1;
+    const PROPERTY_REFERENCES_TAG: u8 = 1;
+    const PROPERTY_ID_TAG: u8 = 0;
+
+    pub fn new(
+        selection_logic: SelectionLogic,
+        criteria: &'a [SelectionCriteria<'a>],
+        property_ids: &'a [PropertyId],
+    ) -> Self {
+        Self {
+            selection_logic,
+            criteria,
+            property_ids,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::SELECTION_CRITERIA_TAG);
+        encode_context_unsigned(
+            writer,
+            Self::SELECTION_LOGIC_TAG,
+            self.selection_logic as u32,
+        );
+        if !self.criteria.is_empty() {
+            encode_opening_tag(writer, Self::LIST_OF_CRITERIA_TAG);
+            for criteria in self.criteria {
+                criteria.encode(writer);
+            }
+            encode_closing_tag(writer, Self::LIST_OF_CRITERIA_TAG);
+        }
+        encode_closing_tag(writer, Self::SELECTION_CRITERIA_TAG);
+
+        if !self.property_ids.is_empty() {
+            encode_opening_tag(writer, Self::PROPERTY_REFERENCES_TAG);
+            for property_id in self.property_ids {
+                encode_context_enumerated(writer, Self::PROPERTY_ID_TAG, property_id);
+            }
+            encode_closing_tag(writer, Self::PROPERTY_REFERENCES_TAG);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequestService, ConfirmedServiceChoice},
+            services::read_property_conditional::{ReadPropertyConditional, SelectionLogic},
+        },
+        common::{
+            error::{Error, Unimplemented},
+            io::{Reader, Writer},
+            property_id::PropertyId,
+        },
+    };
+
+    #[test]
+    fn encode_all_objects_present_value() {
+        let property_ids = [PropertyId::PropPresentValue];
+        let request = ReadPropertyConditional::new(SelectionLogic::All, &[], &property_ids);
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        assert_eq!(
+            writer.to_bytes(),
+            &[0x0E, 0x09, 0x02, 0x0F, 0x1E, 0x09, 0x55, 0x1F]
+        );
+    }
+
+    #[test]
+    fn decode_request_is_unimplemented() {
+        // a server should be able to reply with a reject rather than panic
+        let buf = [0x0E, 0x09, 0x02, 0x0F];
+        let mut reader = Reader::new_with_len(buf.len());
+        let result = ConfirmedRequestService::decode(
+            ConfirmedServiceChoice::ReadPropConditional,
+            &mut reader,
+            &buf,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Unimplemented(Unimplemented::ConfirmedServiceChoice(
+                ConfirmedServiceChoice::ReadPropConditional
+            )))
+        ));
+    }
+}
@@ -1,22 +1,29 @@
 use core::fmt::Display;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use crate::{
     application_protocol::{
         confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
-        primitives::data_value::ApplicationDataValue,
+        primitives::data_value::{ApplicationDataValue, BitString},
+        services::read_property::ApplicationDataValueList,
     },
     common::{
+        cov_subscription::CovSubscriptionList,
         daily_schedule::WeeklySchedule,
         error::Error,
         helper::{
             decode_context_object_id, decode_context_property_id, decode_unsigned,
-            encode_closing_tag, encode_context_enumerated, encode_context_object_id,
-            encode_context_unsigned, encode_opening_tag, get_tagged_body, get_tagged_body_for_tag,
+            encode_application_enumerated, encode_closing_tag, encode_context_enumerated,
+            encode_context_object_id, encode_context_unsigned, encode_opening_tag, get_tagged_body,
+            get_tagged_body_for_tag,
         },
         io::{Reader, Writer},
         object_id::{ObjectId, ObjectType},
         property_id::PropertyId,
-        spec::{ErrorClass, ErrorCode, BACNET_ARRAY_ALL},
+        recipient::RecipientList,
+        spec::{ErrorClass, ErrorCode, EventTransitionBits, BACNET_ARRAY_ALL},
         tag::{ApplicationTagNumber, Tag, TagNumber},
     },
     network_protocol::data_link::DataLink,
@@ -25,17 +32,17 @@ use crate::{
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadPropertyMultipleAck<'a> {
-    pub objects_with_results: &'a [ObjectWithResults<'a>],
+    pub objects_with_results: &'a [ReadAccessResult<'a>],
     buf: &'a [u8],
 }
 
 impl<'a> IntoIterator for &'_ ReadPropertyMultipleAck<'a> {
-    type Item = Result<ObjectWithResults<'a>, Error>;
+    type Item = Result<ReadAccessResult<'a>, Error>;
 
-    type IntoIter = ObjectWithResultsIter<'a>;
+    type IntoIter = ReadAccessResultIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
-        ObjectWithResultsIter {
+        ReadAccessResultIter {
             buf: self.buf,
             reader: Reader::new_with_len(self.buf.len()),
         }
@@ -56,26 +63,49 @@ impl<'a> TryFrom<DataLink<'a>> for ReadPropertyMultipleAck<'a> {
     }
 }
 
+// BACnetReadAccessResult - an object and the results of reading its properties
+// the ReadPropertyMultiple ack is a list of these
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct ObjectWithResults<'a> {
+pub struct ReadAccessResult<'a> {
     pub object_id: ObjectId,
     pub property_results: PropertyResultList<'a>,
 }
 
-impl<'a> ObjectWithResults<'a> {
+// the name used before ReadAccessResult (kept for compatibility)
+pub type ObjectWithResults<'a> = ReadAccessResult<'a>;
+
+impl<'a> ReadAccessResult<'a> {
+    const OBJECT_ID_TAG: u8 = 0;
+    const RESULTS_TAG: u8 = 1;
+
+    pub fn new(object_id: ObjectId, property_results: &'a [PropertyResult<'a>]) -> Self {
+        Self {
+            object_id,
+            property_results: PropertyResultList::new(property_results),
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
-        encode_context_object_id(writer, 0, &self.object_id);
-        encode_opening_tag(writer, 1);
+        encode_context_object_id(writer, Self::OBJECT_ID_TAG, &self.object_id);
+        encode_opening_tag(writer, Self::RESULTS_TAG);
         self.property_results.encode(writer);
-        encode_closing_tag(writer, 1);
+        encode_closing_tag(writer, Self::RESULTS_TAG);
     }
 
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
-        let object_id =
-            decode_context_object_id(reader, buf, 0, "ObjectWithResults decode object_id")?;
-        let buf =
-            get_tagged_body_for_tag(reader, buf, 1, "ObjectWithResults decode list of results")?;
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::OBJECT_ID_TAG,
+            "ReadAccessResult decode object_id",
+        )?;
+        let buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::RESULTS_TAG,
+            "ReadAccessResult decode list of results",
+        )?;
 
         let property_results = PropertyResultList {
             object_id,
@@ -83,11 +113,24 @@ impl<'a> ObjectWithResults<'a> {
             property_results: &[],
         };
 
-        Ok(ObjectWithResults {
+        Ok(ReadAccessResult {
             object_id,
             property_results,
         })
     }
+
+    // calls `visit` with the id and value of every property result in turn
+    // stops at the first result that can't be decoded or that `visit` returns an error for
+    pub fn visit_results(
+        &self,
+        mut visit: impl FnMut(PropertyId, PropertyValue<'a>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        for result in &self.property_results {
+            let result = result?;
+            visit(result.id, result.value)?;
+        }
+        Ok(())
+    }
 }
 
 impl<'a> IntoIterator for &'_ PropertyResultList<'a> {
@@ -219,6 +262,12 @@ impl<'a> PropertyResult<'a> {
 pub enum PropertyValue<'a> {
     PropValue(ApplicationDataValue<'a>),
     PropError(PropertyAccessError),
+    // more than one value means the property is a list or an array
+    PropValueList(ApplicationDataValueList<'a>),
+    RecipientList(RecipientList<'a>),
+    CovSubscriptionList(CovSubscriptionList<'a>),
+    // the members of a group object (a list of read access specifications)
+    GroupMembers(ReadPropertyMultiple<'a>),
     // TODO: figure out if we need these
     PropDescription(&'a str),
     PropObjectName(&'a str),
@@ -235,7 +284,31 @@ impl<'a> PropertyValue<'a> {
                 val.encode(writer);
                 encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
             }
-            Self::PropError(_) => todo!(),
+            Self::PropError(err) => {
+                encode_opening_tag(writer, Self::PROPERTY_ERROR_TAG);
+                err.encode(writer);
+                encode_closing_tag(writer, Self::PROPERTY_ERROR_TAG);
+            }
+            Self::PropValueList(values) => {
+                encode_opening_tag(writer, Self::PROPERTY_VALUE_TAG);
+                values.encode(writer);
+                encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
+            }
+            Self::RecipientList(recipients) => {
+                encode_opening_tag(writer, Self::PROPERTY_VALUE_TAG);
+                recipients.encode(writer);
+                encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
+            }
+            Self::CovSubscriptionList(subscriptions) => {
+                encode_opening_tag(writer, Self::PROPERTY_VALUE_TAG);
+                subscriptions.encode(writer);
+                encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
+            }
+            Self::GroupMembers(members) => {
+                encode_opening_tag(writer, Self::PROPERTY_VALUE_TAG);
+                members.encode(writer);
+                encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
+            }
             Self::PropObjectName(_) => todo!(),
             Self::PropDescription(_) => todo!(),
         }
@@ -266,6 +339,15 @@ impl<'a> PropertyValue<'a> {
                             weekly_schedule,
                         ))
                     }
+                    PropertyId::PropRecipientList => {
+                        PropertyValue::RecipientList(RecipientList::new_from_buf(buf))
+                    }
+                    PropertyId::PropActiveCovSubscriptions => {
+                        PropertyValue::CovSubscriptionList(CovSubscriptionList::new_from_buf(buf))
+                    }
+                    PropertyId::PropListOfGroupMembers => {
+                        PropertyValue::GroupMembers(ReadPropertyMultiple::new_from_buf(buf))
+                    }
                     property_id => {
                         let tag = Tag::decode(&mut reader, buf)?;
                         let value = ApplicationDataValue::decode(
@@ -275,7 +357,15 @@ impl<'a> PropertyValue<'a> {
                             &mut reader,
                             buf,
                         )?;
-                        PropertyValue::PropValue(value)
+                        if reader.eof() {
+                            PropertyValue::PropValue(value)
+                        } else {
+                            PropertyValue::PropValueList(ApplicationDataValueList::new_from_buf(
+                                *object_id,
+                                **property_id,
+                                buf,
+                            ))
+                        }
                     }
                 }
             }
@@ -303,6 +393,13 @@ pub struct PropertyAccessError {
     pub error_code: ErrorCode,
 }
 
+impl PropertyAccessError {
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_enumerated(writer, self.error_class.clone().into());
+        encode_application_enumerated(writer, self.error_code.clone().into());
+    }
+}
+
 impl<'a> Display for PropertyValue<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match &self {
@@ -313,7 +410,7 @@ impl<'a> Display for PropertyValue<'a> {
 }
 
 impl<'a> ReadPropertyMultipleAck<'a> {
-    pub fn new(objects_with_results: &'a [ObjectWithResults<'a>]) -> Self {
+    pub fn new(objects_with_results: &'a [ReadAccessResult<'a>]) -> Self {
         Self {
             objects_with_results,
             buf: &[],
@@ -333,22 +430,52 @@ impl<'a> ReadPropertyMultipleAck<'a> {
             item.encode(writer);
         }
     }
+
+    // flattens the objects and their results into one list of (object, property, result)
+    // stops at the first object or result that can't be decoded (use the iterator to skip over bad ones)
+    #[cfg(feature = "alloc")]
+    pub fn collect_results(&self) -> Result<Vec<(ObjectId, PropertyId, PropertyValue<'a>)>, Error> {
+        let mut results = Vec::new();
+        for object in self {
+            let object = object?;
+            for result in &object.property_results {
+                let result = result?;
+                results.push((object.object_id, result.id, result.value));
+            }
+        }
+        Ok(results)
+    }
+
+    // the results for the first object in the ack (e.g. the only object that was requested)
+    pub fn first_object(&self) -> Result<ReadAccessResult<'a>, Error> {
+        match self.into_iter().next() {
+            Some(object) => object,
+            None => Err(Error::InvalidValue(
+                "ReadPropertyMultipleAck has no objects",
+            )),
+        }
+    }
+
+    // the undecoded list of results (used when reassembling a response from several pdus)
+    pub(crate) fn body(&self) -> &'a [u8] {
+        self.buf
+    }
 }
 
-pub struct ObjectWithResultsIter<'a> {
+pub struct ReadAccessResultIter<'a> {
     buf: &'a [u8],
     reader: Reader,
 }
 
-impl<'a> Iterator for ObjectWithResultsIter<'a> {
-    type Item = Result<ObjectWithResults<'a>, Error>;
+impl<'a> Iterator for ReadAccessResultIter<'a> {
+    type Item = Result<ReadAccessResult<'a>, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.reader.eof() {
             return None;
         }
 
-        let object_with_results = ObjectWithResults::decode(&mut self.reader, self.buf);
+        let object_with_results = ReadAccessResult::decode(&mut self.reader, self.buf);
         Some(object_with_results)
     }
 }
@@ -357,7 +484,7 @@ impl<'a> Iterator for ObjectWithResultsIter<'a> {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadPropertyMultiple<'a> {
     _array_index: u32, // use BACNET_ARRAY_ALL for all
-    objects: &'a [ReadPropertyMultipleObject<'a>],
+    objects: &'a [ReadAccessSpecification<'a>],
     buf: &'a [u8],
 }
 
@@ -399,13 +526,30 @@ impl<'a> Iterator for PropertyIdIter<'a> {
                 0,
                 "PropertyIdList next property_id",
             ) {
-                Ok(property_id) => Some(Ok(property_id)),
+                Ok(property_id) => Some(self.skip_array_index().map(|_| property_id)),
                 Err(e) => Some(Err(e)),
             }
         }
     }
 }
 
+impl<'a> PropertyIdIter<'a> {
+    // the array index of a property reference is optional and not exposed (the whole property is read)
+    fn skip_array_index(&mut self) -> Result<(), Error> {
+        if self.reader.eof() {
+            return Ok(());
+        }
+        let index = self.reader.index;
+        let tag = Tag::decode(&mut self.reader, self.buf)?;
+        if let TagNumber::ContextSpecific(1) = tag.number {
+            decode_unsigned(tag.value, &mut self.reader, self.buf)?;
+        } else {
+            self.reader.index = index;
+        }
+        Ok(())
+    }
+}
+
 impl<'a> PropertyIdList<'a> {
     pub fn new(property_ids: &'a [PropertyId]) -> Self {
         Self {
@@ -417,6 +561,11 @@ impl<'a> PropertyIdList<'a> {
     pub fn encode(&self, writer: &mut Writer) {
         encode_opening_tag(writer, 1);
 
+        // decoded property references are written back out as is
+        if self.property_ids.is_empty() {
+            writer.extend_from_slice(self.buf);
+        }
+
         for property_id in self.property_ids {
             // property_id
             encode_context_enumerated(writer, 0, property_id);
@@ -431,14 +580,21 @@ impl<'a> PropertyIdList<'a> {
     }
 }
 
+// BACnetReadAccessSpecification - an object and the properties to read from it
+// the ReadPropertyMultiple request is a list of these (and so is the member list of a group object)
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct ReadPropertyMultipleObject<'a> {
+pub struct ReadAccessSpecification<'a> {
     pub object_id: ObjectId, // e.g ObjectDevice:20088
     pub property_ids: PropertyIdList<'a>,
 }
 
-impl<'a> ReadPropertyMultipleObject<'a> {
+// the name used before ReadAccessSpecification (kept for compatibility)
+pub type ReadPropertyMultipleObject<'a> = ReadAccessSpecification<'a>;
+
+impl<'a> ReadAccessSpecification<'a> {
+    const OBJECT_ID_TAG: u8 = 0;
+
     pub fn new(object_id: ObjectId, property_ids: &'a [PropertyId]) -> Self {
         let property_ids = PropertyIdList::new(property_ids);
         Self {
@@ -449,35 +605,31 @@ impl<'a> ReadPropertyMultipleObject<'a> {
 
     pub fn encode(&self, writer: &mut Writer) {
         // object_id
-        encode_context_object_id(writer, 0, &self.object_id);
-
-        encode_opening_tag(writer, 1);
-
-        for property_id in self.property_ids.property_ids {
-            // property_id
-            encode_context_enumerated(writer, 0, property_id);
-
-            // array_index
-            //if self.array_index != BACNET_ARRAY_ALL {
-            //    encode_context_unsigned(writer, 1, self.array_index);
-            //}
-        }
+        encode_context_object_id(writer, Self::OBJECT_ID_TAG, &self.object_id);
 
-        encode_closing_tag(writer, 1);
+        self.property_ids.encode(writer);
     }
 
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
-        let object_id =
-            decode_context_object_id(reader, buf, 0, "ReadPropertyMultiple next object_id")?;
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::OBJECT_ID_TAG,
+            "ReadAccessSpecification decode object_id",
+        )?;
 
-        let buf =
-            get_tagged_body_for_tag(reader, buf, 1, "ReadPropertyMultiple next list of results")?;
+        let buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            1,
+            "ReadAccessSpecification decode list of property references",
+        )?;
         let property_ids = PropertyIdList {
             property_ids: &[],
             buf,
         };
 
-        Ok(ReadPropertyMultipleObject {
+        Ok(ReadAccessSpecification {
             object_id,
             property_ids,
         })
@@ -485,7 +637,7 @@ impl<'a> ReadPropertyMultipleObject<'a> {
 }
 
 impl<'a> ReadPropertyMultiple<'a> {
-    pub fn new(objects: &'a [ReadPropertyMultipleObject]) -> Self {
+    pub fn new(objects: &'a [ReadAccessSpecification]) -> Self {
         Self {
             objects,
             _array_index: BACNET_ARRAY_ALL,
@@ -502,13 +654,25 @@ impl<'a> ReadPropertyMultiple<'a> {
     }
 
     pub fn encode(&self, writer: &mut Writer) {
+        if self.objects.is_empty() {
+            // decoded objects are written back out as is
+            writer.extend_from_slice(self.buf);
+        }
         for object in self.objects {
             object.encode(writer)
         }
     }
 
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
+    pub(crate) fn objects(&self) -> &'a [ReadAccessSpecification<'a>] {
+        self.objects
+    }
+
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Self {
-        let buf = &buf[reader.index..reader.end];
+        let buf = reader.remaining(buf);
         Self {
             buf,
             _array_index: BACNET_ARRAY_ALL,
@@ -518,7 +682,7 @@ impl<'a> ReadPropertyMultiple<'a> {
 }
 
 impl<'a> IntoIterator for &'_ ReadPropertyMultiple<'a> {
-    type Item = Result<ReadPropertyMultipleObject<'a>, Error>;
+    type Item = Result<ReadAccessSpecification<'a>, Error>;
 
     type IntoIter = ReadPropertyMultipleIter<'a>;
 
@@ -536,15 +700,512 @@ pub struct ReadPropertyMultipleIter<'a> {
 }
 
 impl<'a> Iterator for ReadPropertyMultipleIter<'a> {
-    type Item = Result<ReadPropertyMultipleObject<'a>, Error>;
+    type Item = Result<ReadAccessSpecification<'a>, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.reader.eof() {
             return None;
         }
 
-        let object_with_property_ids =
-            ReadPropertyMultipleObject::decode(&mut self.reader, self.buf);
+        let object_with_property_ids = ReadAccessSpecification::decode(&mut self.reader, self.buf);
         Some(object_with_property_ids)
     }
 }
+
+// the properties read from a Device object to identify it
+pub const DEVICE_IDENTITY_PROPERTIES: [PropertyId; 8] = [
+    PropertyId::PropObjectName,
+    PropertyId::PropVendorName,
+    PropertyId::PropVendorIdentifier,
+    PropertyId::PropModelName,
+    PropertyId::PropFirmwareRevision,
+    PropertyId::PropApplicationSoftwareVersion,
+    PropertyId::PropProtocolVersion,
+    PropertyId::PropProtocolRevision,
+];
+
+// the "who are you" request for a device, send it with ReadPropertyMultiple::new(&[object])
+// and decode the ReadAccessResult in the ack with DeviceIdentity::decode
+pub fn device_identity_request(device_id: u32) -> ReadAccessSpecification<'static> {
+    ReadAccessSpecification::new(
+        ObjectId::new(ObjectType::ObjectDevice, device_id),
+        &DEVICE_IDENTITY_PROPERTIES,
+    )
+}
+
+// properties that the device does not have (or returned an error for) are None
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceIdentity<'a> {
+    pub object_name: Option<&'a str>,
+    pub vendor_name: Option<&'a str>,
+    pub vendor_identifier: Option<u32>,
+    pub model_name: Option<&'a str>,
+    pub firmware_revision: Option<&'a str>,
+    pub application_software_version: Option<&'a str>,
+    pub protocol_version: Option<u32>,
+    pub protocol_revision: Option<u32>,
+}
+
+impl<'a> DeviceIdentity<'a> {
+    pub fn decode(object: &ReadAccessResult<'a>) -> Result<Self, Error> {
+        let mut identity = Self::default();
+        object.visit_results(|id, value| {
+            let value = match value {
+                PropertyValue::PropValue(value) => value,
+                _ => return Ok(()),
+            };
+
+            match (id, value) {
+                (PropertyId::PropObjectName, ApplicationDataValue::CharacterString(x)) => {
+                    identity.object_name = Some(x.inner)
+                }
+                (PropertyId::PropVendorName, ApplicationDataValue::CharacterString(x)) => {
+                    identity.vendor_name = Some(x.inner)
+                }
+                (PropertyId::PropVendorIdentifier, ApplicationDataValue::UnsignedInt(x)) => {
+                    identity.vendor_identifier = Some(x)
+                }
+                (PropertyId::PropModelName, ApplicationDataValue::CharacterString(x)) => {
+                    identity.model_name = Some(x.inner)
+                }
+                (PropertyId::PropFirmwareRevision, ApplicationDataValue::CharacterString(x)) => {
+                    identity.firmware_revision = Some(x.inner)
+                }
+                (
+                    PropertyId::PropApplicationSoftwareVersion,
+                    ApplicationDataValue::CharacterString(x),
+                ) => identity.application_software_version = Some(x.inner),
+                (PropertyId::PropProtocolVersion, ApplicationDataValue::UnsignedInt(x)) => {
+                    identity.protocol_version = Some(x)
+                }
+                (PropertyId::PropProtocolRevision, ApplicationDataValue::UnsignedInt(x)) => {
+                    identity.protocol_revision = Some(x)
+                }
+                _ => {}
+            }
+            Ok(())
+        })?;
+
+        Ok(identity)
+    }
+}
+
+// the properties read from a Notification Class object to audit who gets alarms
+pub const NOTIFICATION_CLASS_PROPERTIES: [PropertyId; 4] = [
+    PropertyId::PropNotificationClass,
+    PropertyId::PropPriority,
+    PropertyId::PropAckRequired,
+    PropertyId::PropRecipientList,
+];
+
+// send it with ReadPropertyMultiple::new(&[object])
+// and decode the ReadAccessResult in the ack with NotificationClassConfig::decode
+pub fn notification_class_request(instance: u32) -> ReadAccessSpecification<'static> {
+    ReadAccessSpecification::new(
+        ObjectId::new(ObjectType::ObjectNotificationClass, instance),
+        &NOTIFICATION_CLASS_PROPERTIES,
+    )
+}
+
+// properties that the object does not have (or returned an error for) are None
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NotificationClassConfig<'a> {
+    pub notification_class: Option<u32>,
+    pub priority: Option<[u32; 3]>, // to-offnormal, to-fault and to-normal
+    pub ack_required: Option<EventTransitionBits>,
+    pub recipient_list: Option<RecipientList<'a>>,
+}
+
+impl<'a> NotificationClassConfig<'a> {
+    pub fn decode(object: &ReadAccessResult<'a>) -> Result<Self, Error> {
+        let mut config = Self::default();
+        object.visit_results(|id, value| {
+            match (id, value) {
+                (
+                    PropertyId::PropNotificationClass,
+                    PropertyValue::PropValue(ApplicationDataValue::UnsignedInt(x)),
+                ) => config.notification_class = Some(x),
+                (PropertyId::PropPriority, PropertyValue::PropValueList(values)) => {
+                    config.priority = Some(Self::decode_priority(&values)?)
+                }
+                (
+                    PropertyId::PropAckRequired,
+                    PropertyValue::PropValue(ApplicationDataValue::BitString(
+                        BitString::AckRequired(x),
+                    )),
+                ) => config.ack_required = Some(x),
+                (PropertyId::PropRecipientList, PropertyValue::RecipientList(x)) => {
+                    config.recipient_list = Some(x)
+                }
+                _ => {}
+            }
+            Ok(())
+        })?;
+
+        Ok(config)
+    }
+
+    fn decode_priority(values: &ApplicationDataValueList<'a>) -> Result<[u32; 3], Error> {
+        let mut priority = [0; 3];
+        let mut values = values.into_iter();
+        for slot in priority.iter_mut() {
+            match values.next().transpose()? {
+                Some(ApplicationDataValue::UnsignedInt(x)) => *slot = x,
+                _ => return Err(Error::InvalidValue("priority should be 3 unsigned values")),
+            }
+        }
+        if values.next().is_some() {
+            return Err(Error::InvalidValue("priority should be 3 unsigned values"));
+        }
+        Ok(priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::{ApplicationDataValue, BitString, CharacterString, Time},
+            services::read_property::ApplicationDataValueList,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            recipient::{Destination, Recipient, RecipientList},
+            spec::{DaysOfWeek, ErrorClass, ErrorCode, EventTransitionBits},
+        },
+    };
+
+    use super::{
+        device_identity_request, notification_class_request, DeviceIdentity,
+        NotificationClassConfig, ObjectWithResults, PropertyAccessError, PropertyResult,
+        PropertyResultList, PropertyValue, ReadAccessResult, ReadAccessSpecification,
+        ReadPropertyMultiple, ReadPropertyMultipleAck, ReadPropertyMultipleObject,
+    };
+
+    #[test]
+    fn read_access_types() {
+        let property_ids = [PropertyId::PropPresentValue, PropertyId::PropUnits];
+        let specification = ReadAccessSpecification::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            &property_ids,
+        );
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        specification.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(
+            encoded,
+            &[0x0C, 0x00, 0x00, 0x00, 0x01, 0x1E, 0x09, 0x55, 0x09, 0x75, 0x1F]
+        );
+
+        // the old name still works
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded: ReadPropertyMultipleObject =
+            ReadAccessSpecification::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.object_id, specification.object_id);
+        assert_eq!(decoded.property_ids.into_iter().count(), 2);
+
+        let results = [PropertyResult {
+            id: PropertyId::PropPresentValue,
+            value: PropertyValue::PropValue(ApplicationDataValue::Real(21.5)),
+        }];
+        let result =
+            ReadAccessResult::new(ObjectId::new(ObjectType::ObjectAnalogInput, 1), &results);
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        result.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded: ObjectWithResults = ReadAccessResult::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.object_id, result.object_id);
+        let decoded_result = decoded
+            .property_results
+            .into_iter()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            decoded_result.value,
+            PropertyValue::PropValue(ApplicationDataValue::Real(x)) if x == 21.5
+        ));
+    }
+
+    #[test]
+    fn mixed_success_and_error_ack() {
+        let present_value = |value: f32| {
+            [PropertyResult {
+                id: PropertyId::PropPresentValue,
+                value: PropertyValue::PropValue(ApplicationDataValue::Real(value)),
+            }]
+        };
+        let first = present_value(1.0);
+        let missing = [PropertyResult {
+            id: PropertyId::PropPresentValue,
+            value: PropertyValue::PropError(PropertyAccessError {
+                error_class: ErrorClass::Object,
+                error_code: ErrorCode::UnknownObject,
+            }),
+        }];
+        let last = present_value(3.0);
+        let objects = [
+            ReadAccessResult {
+                object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                property_results: PropertyResultList::new(&first),
+            },
+            ReadAccessResult {
+                object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 99),
+                property_results: PropertyResultList::new(&missing),
+            },
+            ReadAccessResult {
+                object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 3),
+                property_results: PropertyResultList::new(&last),
+            },
+        ];
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ReadPropertyMultipleAck::new(&objects).encode(&mut writer);
+
+        // skip the service choice which is decoded by the ComplexAck
+        let encoded = &writer.to_bytes()[1..];
+        let ack = ReadPropertyMultipleAck::new_from_buf(encoded);
+
+        let mut count = 0;
+        for object in &ack {
+            let object = object.unwrap();
+            for result in &object.property_results {
+                let result = result.unwrap();
+                match (object.object_id.id, result.value) {
+                    (1, PropertyValue::PropValue(ApplicationDataValue::Real(x))) => {
+                        assert_eq!(x, 1.0)
+                    }
+                    (3, PropertyValue::PropValue(ApplicationDataValue::Real(x))) => {
+                        assert_eq!(x, 3.0)
+                    }
+                    (99, PropertyValue::PropError(err)) => {
+                        assert!(matches!(err.error_class, ErrorClass::Object));
+                        assert!(matches!(err.error_code, ErrorCode::UnknownObject));
+                    }
+                    (id, value) => panic!("unexpected result for object {}: {:?}", id, value),
+                }
+                count += 1;
+            }
+        }
+        assert_eq!(count, 3);
+
+        let object = ack.first_object().unwrap();
+        assert_eq!(object.object_id.id, 1);
+        let mut visited = 0;
+        object
+            .visit_results(|id, value| {
+                assert_eq!(id, PropertyId::PropPresentValue);
+                assert!(matches!(
+                    value,
+                    PropertyValue::PropValue(ApplicationDataValue::Real(x)) if x == 1.0
+                ));
+                visited += 1;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(visited, 1);
+
+        assert!(ReadPropertyMultipleAck::new_from_buf(&[])
+            .first_object()
+            .is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn collect_results() {
+        let first = [
+            PropertyResult {
+                id: PropertyId::PropPresentValue,
+                value: PropertyValue::PropValue(ApplicationDataValue::Real(1.0)),
+            },
+            PropertyResult {
+                id: PropertyId::PropDescription,
+                value: PropertyValue::PropError(PropertyAccessError {
+                    error_class: ErrorClass::Property,
+                    error_code: ErrorCode::UnknownProperty,
+                }),
+            },
+        ];
+        let second = [PropertyResult {
+            id: PropertyId::PropPresentValue,
+            value: PropertyValue::PropValue(ApplicationDataValue::Real(2.0)),
+        }];
+        let objects = [
+            ReadAccessResult {
+                object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                property_results: PropertyResultList::new(&first),
+            },
+            ReadAccessResult {
+                object_id: ObjectId::new(ObjectType::ObjectAnalogValue, 2),
+                property_results: PropertyResultList::new(&second),
+            },
+        ];
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ReadPropertyMultipleAck::new(&objects).encode(&mut writer);
+        let encoded = &writer.to_bytes()[1..];
+        let ack = ReadPropertyMultipleAck::new_from_buf(encoded);
+
+        let results = ack.collect_results().unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(
+            &results[0],
+            (object_id, PropertyId::PropPresentValue, PropertyValue::PropValue(ApplicationDataValue::Real(x)))
+                if object_id.id == 1 && *x == 1.0
+        ));
+        assert!(matches!(
+            &results[1],
+            (object_id, PropertyId::PropDescription, PropertyValue::PropError(_)) if object_id.id == 1
+        ));
+        assert!(matches!(
+            &results[2],
+            (object_id, PropertyId::PropPresentValue, PropertyValue::PropValue(ApplicationDataValue::Real(x)))
+                if object_id.object_type == ObjectType::ObjectAnalogValue && *x == 2.0
+        ));
+
+        // a truncated ack is an error rather than a partial list
+        let truncated = ReadPropertyMultipleAck::new_from_buf(&encoded[..encoded.len() - 1]);
+        assert!(truncated.collect_results().is_err());
+    }
+
+    #[test]
+    fn device_identity() {
+        let object = device_identity_request(1234);
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ReadPropertyMultiple::new(&[object]).encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let request = ReadPropertyMultiple::decode(&mut reader, encoded);
+        let object = request.into_iter().next().unwrap().unwrap();
+        assert_eq!(
+            object.object_id,
+            ObjectId::new(ObjectType::ObjectDevice, 1234)
+        );
+        assert_eq!(object.property_ids.into_iter().count(), 8);
+
+        let name = |inner| ApplicationDataValue::CharacterString(CharacterString { inner });
+        let results = [
+            PropertyResult {
+                id: PropertyId::PropObjectName,
+                value: PropertyValue::PropValue(name("AHU-1")),
+            },
+            PropertyResult {
+                id: PropertyId::PropVendorIdentifier,
+                value: PropertyValue::PropValue(ApplicationDataValue::UnsignedInt(5)),
+            },
+            PropertyResult {
+                id: PropertyId::PropModelName,
+                value: PropertyValue::PropError(PropertyAccessError {
+                    error_class: ErrorClass::Property,
+                    error_code: ErrorCode::UnknownProperty,
+                }),
+            },
+            PropertyResult {
+                id: PropertyId::PropProtocolRevision,
+                value: PropertyValue::PropValue(ApplicationDataValue::UnsignedInt(14)),
+            },
+        ];
+        let objects = [ReadAccessResult {
+            object_id: ObjectId::new(ObjectType::ObjectDevice, 1234),
+            property_results: PropertyResultList::new(&results),
+        }];
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ReadPropertyMultipleAck::new(&objects).encode(&mut writer);
+        let ack = ReadPropertyMultipleAck::new_from_buf(&writer.to_bytes()[1..]);
+        let object = ack.into_iter().next().unwrap().unwrap();
+
+        let identity = DeviceIdentity::decode(&object).unwrap();
+        assert_eq!(identity.object_name, Some("AHU-1"));
+        assert_eq!(identity.vendor_identifier, Some(5));
+        assert_eq!(identity.model_name, None);
+        assert_eq!(identity.vendor_name, None);
+        assert_eq!(identity.protocol_revision, Some(14));
+    }
+
+    #[test]
+    fn notification_class_config() {
+        let priority = [
+            ApplicationDataValue::UnsignedInt(10),
+            ApplicationDataValue::UnsignedInt(20),
+            ApplicationDataValue::UnsignedInt(200),
+        ];
+        let destinations = [Destination {
+            valid_days: DaysOfWeek::new(DaysOfWeek::WEEKDAYS),
+            from_time: Time::new(0, 0, 0, 0),
+            to_time: Time::new(23, 59, 59, 99),
+            recipient: Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 1234)),
+            process_id: 1,
+            issue_confirmed_notifications: true,
+            transitions: EventTransitionBits::new(EventTransitionBits::ALL),
+        }];
+        let object_id = ObjectId::new(ObjectType::ObjectNotificationClass, 5);
+        let results = [
+            PropertyResult {
+                id: PropertyId::PropNotificationClass,
+                value: PropertyValue::PropValue(ApplicationDataValue::UnsignedInt(5)),
+            },
+            PropertyResult {
+                id: PropertyId::PropPriority,
+                value: PropertyValue::PropValueList(ApplicationDataValueList::new(
+                    object_id,
+                    PropertyId::PropPriority,
+                    &priority,
+                )),
+            },
+            PropertyResult {
+                id: PropertyId::PropAckRequired,
+                value: PropertyValue::PropValue(ApplicationDataValue::BitString(
+                    BitString::AckRequired(EventTransitionBits::new(0b1000_0000)),
+                )),
+            },
+            PropertyResult {
+                id: PropertyId::PropRecipientList,
+                value: PropertyValue::RecipientList(RecipientList::new(&destinations)),
+            },
+        ];
+        let objects = [ReadAccessResult {
+            object_id,
+            property_results: PropertyResultList::new(&results),
+        }];
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ReadPropertyMultipleAck::new(&objects).encode(&mut writer);
+
+        // skip the service choice which is decoded by the ComplexAck
+        let encoded = &writer.to_bytes()[1..];
+        let ack = ReadPropertyMultipleAck::new_from_buf(encoded);
+        let object = ack.into_iter().next().unwrap().unwrap();
+        assert_eq!(notification_class_request(5).object_id, object.object_id);
+
+        let config = NotificationClassConfig::decode(&object).unwrap();
+        assert_eq!(config.notification_class, Some(5));
+        assert_eq!(config.priority, Some([10, 20, 200]));
+        let ack_required = config.ack_required.unwrap();
+        assert!(ack_required.to_offnormal());
+        assert!(!ack_required.to_fault());
+
+        let mut recipients = config.recipient_list.as_ref().unwrap().into_iter();
+        let destination = recipients.next().unwrap().unwrap();
+        assert!(matches!(
+            destination.recipient,
+            Recipient::Device(x) if x.id == 1234
+        ));
+        assert!(destination.issue_confirmed_notifications);
+        assert!(recipients.next().is_none());
+    }
+}
@@ -1,20 +1,22 @@
 use crate::{
     application_protocol::{
-        confirmed::ConfirmedServiceChoice,
+        confirmed::{ConfirmedBacnetError, ConfirmedServiceChoice},
         primitives::data_value::{BitString, Date, Time},
     },
     common::{
         error::{Error, Unimplemented},
         helper::{
-            decode_context_object_id, decode_context_property_id, decode_signed, decode_unsigned,
-            encode_application_signed, encode_application_unsigned, encode_closing_tag,
-            encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
-            encode_opening_tag, get_tagged_body_for_tag,
+            check_len, decode_context_object_id, decode_context_property_id, decode_signed,
+            decode_unsigned, encode_application_enumerated, encode_application_signed,
+            encode_application_unsigned, encode_closing_tag, encode_context_bool,
+            encode_context_enumerated, encode_context_object_id, encode_context_signed,
+            encode_context_unsigned, encode_context_unsigned_u64, encode_opening_tag,
+            get_tagged_body_for_tag,
         },
         io::{Reader, Writer},
         object_id::ObjectId,
         property_id::PropertyId,
-        spec::BACNET_ARRAY_ALL,
+        spec::{ErrorClass, ErrorCode, LogStatusBits, BACNET_ARRAY_ALL},
         tag::{ApplicationTagNumber, Tag, TagNumber},
     },
 };
@@ -41,14 +43,14 @@ pub enum ReadRangeRequestType {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadRangeByPosition {
     pub index: u32,
-    pub count: u32,
+    pub count: i32, // negative to read backwards from the reference
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadRangeBySequence {
     pub sequence_num: u32,
-    pub count: u32,
+    pub count: i32, // negative to read backwards from the reference
 }
 
 #[derive(Debug, Clone)]
@@ -56,7 +58,7 @@ pub struct ReadRangeBySequence {
 pub struct ReadRangeByTime {
     pub date: Date,
     pub time: Time,
-    pub count: u32,
+    pub count: i32, // negative to read backwards from the reference
 }
 
 #[derive(Debug, Clone)]
@@ -174,16 +176,20 @@ pub struct ReadRangeItems<'a> {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadRangeValue {
-    Status,
+    Status(LogStatusBits), // e.g. a gap in the log while logging was disabled
     Bool(bool),
     Real(f32),
     Enum(u32),
-    Unsigned(u32),
+    Unsigned(u64), // e.g. a log of accumulator counts which can exceed a u32
     Signed(i32),
     Bits,
     Null,
-    Error,
-    Delta,
+    Error {
+        // the monitored property could not be read
+        error_class: ErrorClass,
+        error_code: ErrorCode,
+    },
+    Delta(f32), // the clock was changed by this many seconds
     Any,
 }
 
@@ -279,7 +285,7 @@ pub struct ReadRangeItem<'a> {
     pub date: Date,
     pub time: Time,
     pub value: ReadRangeValue,
-    pub status_flags: BitString<'a>,
+    pub status_flags: Option<BitString<'a>>,
 }
 
 impl<'a> ReadRangeItem<'a> {
@@ -306,7 +312,16 @@ impl<'a> ReadRangeItem<'a> {
 
         // value
         Tag::new(TagNumber::ContextSpecificOpening(Self::VALUE_TAG), 0).encode(writer);
-        match self.value {
+        match &self.value {
+            ReadRangeValue::Status(status) => {
+                Tag::new(
+                    TagNumber::ContextSpecific(ReadRangeValueType::Status as u8),
+                    2,
+                )
+                .encode(writer);
+                writer.push(LogStatusBits::UNUSED_BITS);
+                writer.push(status.inner);
+            }
             ReadRangeValue::Real(value) => {
                 Tag::new(
                     TagNumber::ContextSpecific(ReadRangeValueType::Real as u8),
@@ -315,13 +330,63 @@ impl<'a> ReadRangeItem<'a> {
                 .encode(writer);
                 writer.extend_from_slice(&value.to_be_bytes());
             }
-            _ => todo!("{:?}", self.value),
+            ReadRangeValue::Error {
+                error_class,
+                error_code,
+            } => {
+                encode_opening_tag(writer, ReadRangeValueType::Error as u8);
+                encode_application_enumerated(writer, error_class.clone().into());
+                encode_application_enumerated(writer, error_code.clone().into());
+                encode_closing_tag(writer, ReadRangeValueType::Error as u8);
+            }
+            ReadRangeValue::Delta(value) => {
+                Tag::new(
+                    TagNumber::ContextSpecific(ReadRangeValueType::Delta as u8),
+                    4,
+                )
+                .encode(writer);
+                writer.extend_from_slice(&value.to_be_bytes());
+            }
+            ReadRangeValue::Unsigned(value) => {
+                encode_context_unsigned_u64(writer, ReadRangeValueType::Unsigned as u8, *value);
+            }
+            ReadRangeValue::Bool(value) => {
+                encode_context_bool(writer, ReadRangeValueType::Bool as u8, *value);
+            }
+            ReadRangeValue::Enum(value) => {
+                // enumerations are encoded the same way as unsigned values
+                encode_context_unsigned(writer, ReadRangeValueType::Enum as u8, *value);
+            }
+            ReadRangeValue::Signed(value) => {
+                encode_context_signed(writer, ReadRangeValueType::Signed as u8, *value);
+            }
+            ReadRangeValue::Null => {
+                Tag::new(
+                    TagNumber::ContextSpecific(ReadRangeValueType::Null as u8),
+                    0,
+                )
+                .encode(writer);
+            }
+            // the contents of these records are not kept so they are encoded empty
+            ReadRangeValue::Bits => {
+                Tag::new(
+                    TagNumber::ContextSpecific(ReadRangeValueType::Bits as u8),
+                    1,
+                )
+                .encode(writer);
+                writer.push(0); // no unused bits
+            }
+            ReadRangeValue::Any => {
+                encode_opening_tag(writer, ReadRangeValueType::Any as u8);
+                encode_closing_tag(writer, ReadRangeValueType::Any as u8);
+            }
         }
         Tag::new(TagNumber::ContextSpecificClosing(Self::VALUE_TAG), 0).encode(writer);
 
-        // status
-        self.status_flags
-            .encode_context(Self::STATUS_FLAGS_TAG, writer);
+        // status (optional)
+        if let Some(status_flags) = &self.status_flags {
+            status_flags.encode_context(Self::STATUS_FLAGS_TAG, writer);
+        }
     }
 
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
@@ -332,19 +397,21 @@ impl<'a> ReadRangeItem<'a> {
             TagNumber::ContextSpecificOpening(Self::DATE_TIME_TAG),
             "ReadRangeItem decode",
         )?;
-        Tag::decode_expected(
+        let tag = Tag::decode_expected(
             reader,
             buf,
             TagNumber::Application(ApplicationTagNumber::Date),
             "ReadRangeItem decode",
         )?;
+        check_len(tag.value, Date::LEN, "ReadRangeItem decode date")?;
         let date = Date::decode(reader, buf)?;
-        Tag::decode_expected(
+        let tag = Tag::decode_expected(
             reader,
             buf,
             TagNumber::Application(ApplicationTagNumber::Time),
             "ReadRangeItem decode",
         )?;
+        check_len(tag.value, Time::LEN, "ReadRangeItem decode time")?;
         let time = Time::decode(reader, buf)?;
         Tag::decode_expected(
             reader,
@@ -360,19 +427,36 @@ impl<'a> ReadRangeItem<'a> {
             TagNumber::ContextSpecificOpening(Self::VALUE_TAG),
             "ReadRangeItem decode",
         )?;
+        let index = reader.index;
         let tag = Tag::decode(reader, buf)?;
-        let value_type: ReadRangeValueType = match tag.number {
-            TagNumber::ContextSpecific(tag_number) => tag_number
-                .try_into()
-                .map_err(|x| Error::InvalidVariant(("ReadRangeValueType", x as u32)))?,
-            x => return Err(Error::TagNotSupported(("ReadRangeItems next value", x))),
-        };
-        let value = match value_type {
-            ReadRangeValueType::Real => {
-                let value = f32::from_be_bytes(reader.read_bytes(buf)?);
-                ReadRangeValue::Real(value)
+        let value = match tag.number {
+            // failure and any-value are the only constructed choices
+            TagNumber::ContextSpecificOpening(x) if x == ReadRangeValueType::Any as u8 => {
+                reader.index = index;
+                get_tagged_body_for_tag(reader, buf, x, "ReadRangeItem decode any value")?;
+                ReadRangeValue::Any
             }
-            x => return Err(Error::Unimplemented(Unimplemented::ReadRangeValueType(x))),
+            TagNumber::ContextSpecificOpening(x) if x == ReadRangeValueType::Error as u8 => {
+                let (error_class, error_code) =
+                    ConfirmedBacnetError::decode_error_type(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(x),
+                    "ReadRangeItem decode failure",
+                )?;
+                ReadRangeValue::Error {
+                    error_class,
+                    error_code,
+                }
+            }
+            TagNumber::ContextSpecific(tag_number) => {
+                let value_type: ReadRangeValueType = tag_number
+                    .try_into()
+                    .map_err(|x| Error::InvalidVariant(("ReadRangeValueType", x as u32)))?;
+                Self::decode_value(value_type, &tag, reader, buf)?
+            }
+            x => return Err(Error::TagNotSupported(("ReadRangeItems next value", x))),
         };
         Tag::decode_expected(
             reader,
@@ -381,14 +465,23 @@ impl<'a> ReadRangeItem<'a> {
             "ReadRangeItem decode",
         )?;
 
-        // status flags
-        Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::ContextSpecific(Self::STATUS_FLAGS_TAG),
-            "ReadRangeItem decode",
-        )?;
-        let status_flags = BitString::decode(&PropertyId::PropStatusFlags, tag.value, reader, buf)?;
+        // status flags (optional)
+        let mut status_flags = None;
+        if !reader.eof() {
+            let index = reader.index;
+            let tag = Tag::decode(reader, buf)?;
+            if let TagNumber::ContextSpecific(Self::STATUS_FLAGS_TAG) = tag.number {
+                status_flags = Some(BitString::decode(
+                    &PropertyId::PropStatusFlags,
+                    tag.value,
+                    reader,
+                    buf,
+                )?);
+            } else {
+                // the start of the next item
+                reader.index = index;
+            }
+        }
 
         Ok(ReadRangeItem {
             date,
@@ -397,6 +490,60 @@ impl<'a> ReadRangeItem<'a> {
             status_flags,
         })
     }
+
+    fn decode_value(
+        value_type: ReadRangeValueType,
+        tag: &Tag,
+        reader: &mut Reader,
+        buf: &[u8],
+    ) -> Result<ReadRangeValue, Error> {
+        let value = match value_type {
+            ReadRangeValueType::Status => {
+                check_len(tag.value, 2, "ReadRangeItem decode log status")?;
+                let _unused_bits = reader.read_byte(buf)?;
+                ReadRangeValue::Status(LogStatusBits::new(reader.read_byte(buf)?))
+            }
+            ReadRangeValueType::Real => {
+                check_len(tag.value, 4, "ReadRangeItem decode real")?;
+                let value = f32::from_be_bytes(reader.read_bytes(buf)?);
+                ReadRangeValue::Real(value)
+            }
+            ReadRangeValueType::Delta => {
+                check_len(tag.value, 4, "ReadRangeItem decode time change")?;
+                let value = f32::from_be_bytes(reader.read_bytes(buf)?);
+                ReadRangeValue::Delta(value)
+            }
+            ReadRangeValueType::Unsigned => {
+                let value = decode_unsigned(tag.value, reader, buf)?;
+                ReadRangeValue::Unsigned(value)
+            }
+            ReadRangeValueType::Bool => {
+                check_len(tag.value, 1, "ReadRangeItem decode boolean")?;
+                ReadRangeValue::Bool(reader.read_byte(buf)? > 0)
+            }
+            ReadRangeValueType::Enum => {
+                let value = decode_unsigned(tag.value, reader, buf)?;
+                let value = u32::try_from(value)
+                    .map_err(|_| Error::InvalidValue("ReadRangeItem enumerated out of range"))?;
+                ReadRangeValue::Enum(value)
+            }
+            ReadRangeValueType::Signed => {
+                let value = decode_signed(tag.value, reader, buf)?;
+                ReadRangeValue::Signed(value)
+            }
+            ReadRangeValueType::Null => {
+                check_len(tag.value, 0, "ReadRangeItem decode null")?;
+                ReadRangeValue::Null
+            }
+            ReadRangeValueType::Bits => {
+                // the bits are skipped (not kept)
+                reader.read_slice(tag.value as usize, buf)?;
+                ReadRangeValue::Bits
+            }
+            x => return Err(Error::Unimplemented(Unimplemented::ReadRangeValueType(x))),
+        };
+        Ok(value)
+    }
 }
 
 impl ReadRange {
@@ -457,16 +604,14 @@ impl ReadRange {
                 // count
                 let count_tag = Tag::decode(reader, buf)?;
                 let count = match count_tag.number {
+                    // some devices send a positive count as unsigned
                     TagNumber::Application(ApplicationTagNumber::UnsignedInt) => {
-                        decode_unsigned(count_tag.value, reader, buf)? as u32
+                        let count = decode_unsigned(count_tag.value, reader, buf)?;
+                        i32::try_from(count)
+                            .map_err(|_| Error::InvalidValue("ReadRange count out of range"))?
                     }
                     TagNumber::Application(ApplicationTagNumber::SignedInt) => {
-                        let count = decode_signed(count_tag.value, reader, buf)?;
-                        if count < 0 {
-                            return Err(Error::InvalidValue("ReadRange count cannot be negative"));
-                        }
-
-                        count as u32
+                        decode_signed(count_tag.value, reader, buf)?
                     }
                     _ => {
                         return Err(Error::TagNotSupported((
@@ -484,10 +629,7 @@ impl ReadRange {
                     "ReadRange decode closing position",
                 )?;
 
-                ReadRangeRequestType::ByPosition(ReadRangeByPosition {
-                    count: count as u32,
-                    index,
-                })
+                ReadRangeRequestType::ByPosition(ReadRangeByPosition { count, index })
             }
             number => return Err(Error::TagNotSupported(("ReadRange opening tag", number))),
         };
@@ -516,20 +658,20 @@ impl ReadRange {
             ReadRangeRequestType::ByPosition(x) => {
                 encode_opening_tag(writer, Self::BY_POSITION_TAG);
                 encode_application_unsigned(writer, x.index as u64);
-                encode_application_signed(writer, x.count as i32);
+                encode_application_signed(writer, x.count);
                 encode_closing_tag(writer, Self::BY_POSITION_TAG);
             }
             ReadRangeRequestType::BySequence(x) => {
                 encode_opening_tag(writer, Self::BY_SEQUENCE_TAG);
                 encode_application_unsigned(writer, x.sequence_num as u64);
-                encode_application_signed(writer, x.count as i32);
+                encode_application_signed(writer, x.count);
                 encode_closing_tag(writer, Self::BY_SEQUENCE_TAG);
             }
             ReadRangeRequestType::ByTime(x) => {
                 encode_opening_tag(writer, Self::BY_TIME_TAG);
                 x.date.encode(writer);
                 x.time.encode(writer);
-                encode_application_signed(writer, x.count as i32);
+                encode_application_signed(writer, x.count);
                 encode_closing_tag(writer, Self::BY_TIME_TAG);
             }
             ReadRangeRequestType::All => {
@@ -537,4 +679,230 @@ impl ReadRange {
             }
         }
     }
+
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::{BitString, Date, Time},
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{ErrorClass, ErrorCode, LogStatusBits, Status},
+        },
+    };
+
+    use super::{
+        ReadRange, ReadRangeByPosition, ReadRangeItem, ReadRangeItems, ReadRangeRequestType,
+        ReadRangeValue,
+    };
+
+    fn decode_count(buf: &[u8]) -> i32 {
+        let mut reader = Reader::new_with_len(buf.len());
+        let request = ReadRange::decode(&mut reader, buf).unwrap();
+        match request.request_type {
+            ReadRangeRequestType::ByPosition(x) => x.count,
+            x => panic!("expected ByPosition: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn backwards_count() {
+        let request = ReadRange::new(
+            ObjectId::new(ObjectType::ObjectTrendlog, 1),
+            PropertyId::PropLogBuffer,
+            ReadRangeRequestType::ByPosition(ReadRangeByPosition {
+                index: 500,
+                count: -100,
+            }),
+        );
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        // opening tag, index 500, count -100 as a single signed byte, closing tag
+        assert_eq!(
+            &encoded[encoded.len() - 7..],
+            &[0x3E, 0x22, 0x01, 0xF4, 0x31, 0x9C, 0x3F]
+        );
+        assert_eq!(decode_count(encoded), -100);
+
+        // the sign is preserved for 2, 3 and 4 byte counts
+        let header = &encoded[..encoded.len() - 7];
+        let mut input = [0; 32];
+        for (count, expected) in [
+            (&[0x32, 0xFF, 0x9C][..], -100),
+            (&[0x33, 0xFE, 0x79, 0x60][..], -100000),
+            (&[0x34, 0x80, 0x00, 0x00, 0x00][..], i32::MIN),
+            (&[0x21, 0x64][..], 100),
+        ] {
+            let mut writer = Writer::new(&mut input);
+            writer.extend_from_slice(header);
+            writer.extend_from_slice(&[0x3E, 0x21, 0x01]);
+            writer.extend_from_slice(count);
+            writer.push(0x3F);
+            assert_eq!(decode_count(writer.to_bytes()), expected);
+        }
+    }
+
+    #[test]
+    fn item_with_malformed_real() {
+        let decode = |value: &[u8]| {
+            let mut input = [0; 32];
+            let mut writer = Writer::new(&mut input);
+            writer.extend_from_slice(&[0x0E, 0xA4, 124, 6, 1, 6, 0xB4, 12, 0, 0, 0, 0x0F, 0x1E]);
+            writer.extend_from_slice(value);
+            writer.extend_from_slice(&[0x1F, 0x2A, 0x00, 0x00]);
+            let len = writer.index;
+            let mut reader = Reader::new_with_len(len);
+            ReadRangeItem::decode(&mut reader, &input[..len]).map(|x| match x.value {
+                ReadRangeValue::Real(x) => x,
+                x => panic!("expected Real: {:?}", x),
+            })
+        };
+
+        assert_eq!(decode(&[0x2C, 0x41, 0xAC, 0x00, 0x00]).unwrap(), 21.5);
+
+        // a real that claims to be 3 bytes long is an error rather than a panic
+        assert!(matches!(
+            decode(&[0x2B, 0x41, 0xAC, 0x00]),
+            Err(Error::Length(_))
+        ));
+    }
+
+    #[test]
+    fn log_with_status_and_time_change_records() {
+        let item = |minute, value, status_flags| ReadRangeItem {
+            date: Date::new(2024, 3, 14, 4),
+            time: Time::new(9, minute, 0, 0),
+            value,
+            status_flags,
+        };
+        let status = Some(BitString::Status(Status::default()));
+        let items = [
+            item(0, ReadRangeValue::Real(21.5), status.clone()),
+            // logging was disabled (no status flags)
+            item(
+                15,
+                ReadRangeValue::Status(LogStatusBits::new(0b1000_0000)),
+                None,
+            ),
+            item(30, ReadRangeValue::Delta(-3600.0), None),
+            item(
+                45,
+                ReadRangeValue::Error {
+                    error_class: ErrorClass::Object,
+                    error_code: ErrorCode::UnknownObject,
+                },
+                None,
+            ),
+            item(50, ReadRangeValue::Real(22.0), status),
+        ];
+
+        let mut buf = [0; 256];
+        let mut writer = Writer::new(&mut buf);
+        ReadRangeItems::new(&items).encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let decoded = ReadRangeItems::new_from_buf(encoded);
+        let mut iter = decoded.into_iter();
+
+        let first = iter.next().unwrap().unwrap();
+        assert!(matches!(first.value, ReadRangeValue::Real(x) if x == 21.5));
+        assert!(first.status_flags.is_some());
+
+        let second = iter.next().unwrap().unwrap();
+        assert_eq!(second.time.minute, 15);
+        match second.value {
+            ReadRangeValue::Status(x) => {
+                assert!(x.log_disabled());
+                assert!(!x.buffer_purged());
+                assert!(!x.log_interrupted());
+            }
+            x => panic!("expected Status: {:?}", x),
+        }
+        assert!(second.status_flags.is_none());
+
+        let third = iter.next().unwrap().unwrap();
+        assert!(matches!(third.value, ReadRangeValue::Delta(x) if x == -3600.0));
+
+        let fourth = iter.next().unwrap().unwrap();
+        assert!(matches!(
+            fourth.value,
+            ReadRangeValue::Error {
+                error_class: ErrorClass::Object,
+                error_code: ErrorCode::UnknownObject
+            }
+        ));
+
+        let fifth = iter.next().unwrap().unwrap();
+        assert!(matches!(fifth.value, ReadRangeValue::Real(x) if x == 22.0));
+        assert!(fifth.status_flags.is_some());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn every_value_type_round_trip() {
+        let item = |minute, value| ReadRangeItem {
+            date: Date::new(2024, 3, 14, 4),
+            time: Time::new(9, minute, 0, 0),
+            value,
+            status_flags: None,
+        };
+        let items = [
+            item(0, ReadRangeValue::Bool(true)),
+            item(1, ReadRangeValue::Enum(3)),
+            item(2, ReadRangeValue::Signed(-40)),
+            item(3, ReadRangeValue::Null),
+            item(4, ReadRangeValue::Bits),
+            item(5, ReadRangeValue::Any),
+        ];
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ReadRangeItems::new(&items).encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        // the value of the first item
+        assert_eq!(&encoded[12..17], &[0x1E, 0x19, 0x01, 0x1F, 0x0E]);
+
+        let decoded = ReadRangeItems::new_from_buf(encoded);
+        let mut iter = decoded.into_iter();
+        let mut next = || iter.next().unwrap().unwrap().value;
+        assert!(matches!(next(), ReadRangeValue::Bool(true)));
+        assert!(matches!(next(), ReadRangeValue::Enum(3)));
+        assert!(matches!(next(), ReadRangeValue::Signed(-40)));
+        assert!(matches!(next(), ReadRangeValue::Null));
+        assert!(matches!(next(), ReadRangeValue::Bits));
+        assert!(matches!(next(), ReadRangeValue::Any));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn accumulator_count_log() {
+        // a trend log of an energy meter where the count no longer fits in a u32
+        let input = [
+            0x0E, 0xA4, 124, 6, 1, 6, 0xB4, 12, 0, 0, 0, 0x0F, // date and time
+            0x1E, 0x4D, 0x05, 0x01, 0x00, 0x00, 0x00, 0x2A, 0x1F, // 5 byte count
+        ];
+        let mut reader = Reader::new_with_len(input.len());
+        let item = ReadRangeItem::decode(&mut reader, &input).unwrap();
+        assert!(matches!(
+            item.value,
+            ReadRangeValue::Unsigned(0x01_0000_002A)
+        ));
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        item.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &input);
+    }
 }
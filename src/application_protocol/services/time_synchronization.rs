@@ -4,7 +4,9 @@ use crate::{
         unconfirmed::UnconfirmedServiceChoice,
     },
     common::{
-        io::Writer,
+        error::Error,
+        helper::check_len,
+        io::{Reader, Writer},
         tag::{ApplicationTagNumber, Tag, TagNumber},
     },
 };
@@ -19,7 +21,16 @@ pub struct TimeSynchronization {
 impl TimeSynchronization {
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(UnconfirmedServiceChoice::TimeSynchronization as u8);
+        self.encode_payload(writer);
+    }
+
+    // UtcTimeSynchronization has the same payload as TimeSynchronization
+    pub fn encode_utc(&self, writer: &mut Writer) {
+        writer.push(UnconfirmedServiceChoice::UtcTimeSynchronization as u8);
+        self.encode_payload(writer);
+    }
 
+    fn encode_payload(&self, writer: &mut Writer) {
         // date
         let tag = Tag::new(TagNumber::Application(ApplicationTagNumber::Date), 4);
         tag.encode(writer);
@@ -30,4 +41,30 @@ impl TimeSynchronization {
         tag.encode(writer);
         self.time.encode(writer);
     }
+
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Date),
+            "TimeSynchronization decode date",
+        )?;
+        check_len(tag.value, Date::LEN, "TimeSynchronization decode date")?;
+        let date = Date::decode(reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Time),
+            "TimeSynchronization decode time",
+        )?;
+        check_len(tag.value, Time::LEN, "TimeSynchronization decode time")?;
+        let time = Time::decode(reader, buf)?;
+
+        Ok(Self { date, time })
+    }
 }
@@ -0,0 +1,422 @@
+use crate::{
+    application_protocol::confirmed::ConfirmedServiceChoice,
+    common::{
+        error::Error,
+        helper::{
+            decode_application_octet_string, decode_application_unsigned, decode_unsigned,
+            encode_application_enumerated, encode_application_octet_string,
+            encode_application_unsigned, encode_context_bool, encode_context_unsigned,
+        },
+        io::{Reader, Writer},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+};
+
+// Virtual Terminal services (VT-Open, VT-Close, VT-Data)
+// the terminal data itself is treated as opaque octets
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtOpen {
+    pub vt_class: u32, // BACnetVTClass e.g. 0 = default-terminal
+    pub local_vt_session_id: u32,
+}
+
+impl VtOpen {
+    pub fn new(vt_class: u32, local_vt_session_id: u32) -> Self {
+        Self {
+            vt_class,
+            local_vt_session_id,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_enumerated(writer, self.vt_class);
+        encode_application_unsigned(writer, self.local_vt_session_id as u64);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "VtOpen decode vt_class",
+        )?;
+        let vt_class = decode_unsigned(tag.value, reader, buf)? as u32;
+        let local_vt_session_id =
+            decode_application_unsigned(reader, buf, "VtOpen decode local_vt_session_id")? as u32;
+
+        Ok(Self {
+            vt_class,
+            local_vt_session_id,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtOpenAck {
+    pub remote_vt_session_id: u32,
+}
+
+impl VtOpenAck {
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::VtOpen as u8);
+        encode_application_unsigned(writer, self.remote_vt_session_id as u64);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let remote_vt_session_id =
+            decode_application_unsigned(reader, buf, "VtOpenAck decode remote_vt_session_id")?
+                as u32;
+        Ok(Self {
+            remote_vt_session_id,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtClose<'a> {
+    remote_vt_session_ids: &'a [u32],
+    buf: &'a [u8],
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtSessionIdIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> VtClose<'a> {
+    pub fn new(remote_vt_session_ids: &'a [u32]) -> Self {
+        Self {
+            remote_vt_session_ids,
+            buf: &[],
+        }
+    }
+
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self {
+            remote_vt_session_ids: &[],
+            buf,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        for id in self.remote_vt_session_ids {
+            encode_application_unsigned(writer, *id as u64);
+        }
+    }
+
+    // the rest of the request is the list of session ids
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Self {
+        let buf = reader.remaining(buf);
+        reader.index = reader.end;
+        Self::new_from_buf(buf)
+    }
+}
+
+impl<'a> IntoIterator for &'_ VtClose<'a> {
+    type Item = Result<u32, Error>;
+    type IntoIter = VtSessionIdIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        VtSessionIdIter {
+            reader: Reader::new_with_len(self.buf.len()),
+            buf: self.buf,
+        }
+    }
+}
+
+impl<'a> Iterator for VtSessionIdIter<'a> {
+    type Item = Result<u32, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        let id = decode_application_unsigned(&mut self.reader, self.buf, "VtClose next")
+            .map(|x| x as u32);
+        Some(id)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtData<'a> {
+    pub vt_session_id: u32,
+    pub vt_new_data: &'a [u8],
+    pub vt_data_flag: u32, // 0 or 1
+}
+
+impl<'a> VtData<'a> {
+    pub fn new(vt_session_id: u32, vt_new_data: &'a [u8], vt_data_flag: u32) -> Self {
+        Self {
+            vt_session_id,
+            vt_new_data,
+            vt_data_flag,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_unsigned(writer, self.vt_session_id as u64);
+        encode_application_octet_string(writer, self.vt_new_data);
+        encode_application_unsigned(writer, self.vt_data_flag as u64);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let vt_session_id =
+            decode_application_unsigned(reader, buf, "VtData decode vt_session_id")? as u32;
+        let vt_new_data =
+            decode_application_octet_string(reader, buf, "VtData decode vt_new_data")?;
+        let vt_data_flag =
+            decode_application_unsigned(reader, buf, "VtData decode vt_data_flag")? as u32;
+
+        Ok(Self {
+            vt_session_id,
+            vt_new_data,
+            vt_data_flag,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtDataAck {
+    pub all_new_data_accepted: bool,
+    pub accepted_octet_count: Option<u32>, // only present if not all new data was accepted
+}
+
+impl VtDataAck {
+    const ALL_NEW_DATA_ACCEPTED_TAG: u8 = 0;
+    const ACCEPTED_OCTET_COUNT_TAG: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::VtData as u8);
+        encode_context_bool(
+            writer,
+            Self::ALL_NEW_DATA_ACCEPTED_TAG,
+            self.all_new_data_accepted,
+        );
+        if let Some(count) = self.accepted_octet_count {
+            encode_context_unsigned(writer, Self::ACCEPTED_OCTET_COUNT_TAG, count);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::ALL_NEW_DATA_ACCEPTED_TAG),
+            "VtDataAck decode all_new_data_accepted",
+        )?;
+        let all_new_data_accepted = decode_unsigned(tag.value, reader, buf)? > 0;
+
+        let accepted_octet_count = if reader.eof() {
+            None
+        } else {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecific(Self::ACCEPTED_OCTET_COUNT_TAG),
+                "VtDataAck decode accepted_octet_count",
+            )?;
+            Some(decode_unsigned(tag.value, reader, buf)? as u32)
+        };
+
+        Ok(Self {
+            all_new_data_accepted,
+            accepted_octet_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{
+                ComplexAck, ComplexAckService, ConfirmedRequest, ConfirmedRequestService,
+                ConfirmedServiceChoice, SimpleAck,
+            },
+            services::virtual_terminal::{VtClose, VtData, VtDataAck, VtOpen, VtOpenAck},
+        },
+        common::io::{Reader, Writer},
+        network_protocol::{data_link::DataLink, network_pdu::NetworkMessage},
+    };
+
+    // encodes the apdu, checks the bytes after the header and decodes it again
+    fn round_trip<'a>(
+        apdu: ApplicationPdu,
+        buf: &'a mut [u8],
+        header_len: usize,
+        service: &[u8],
+    ) -> ApplicationPdu<'a> {
+        let mut writer = Writer::new(buf);
+        apdu.encode(&mut writer);
+        let len = writer.index;
+        let encoded = &buf[..len];
+        assert_eq!(&encoded[header_len..], service);
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = ApplicationPdu::decode(&mut reader, encoded).unwrap();
+        assert!(reader.eof());
+        decoded
+    }
+
+    #[test]
+    fn vt_open_round_trip() {
+        // open a default-terminal session with our local session id 3
+        let service = ConfirmedRequestService::VtOpen(VtOpen::new(0, 3));
+        let apdu = ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(1, service));
+        let mut buf = [0; 32];
+        match round_trip(apdu, &mut buf, 3, &[0x15, 0x91, 0x00, 0x21, 0x03]) {
+            ApplicationPdu::ConfirmedRequest(ConfirmedRequest {
+                service: ConfirmedRequestService::VtOpen(x),
+                ..
+            }) => {
+                assert_eq!(x.vt_class, 0);
+                assert_eq!(x.local_vt_session_id, 3);
+            }
+            x => panic!("expected VtOpen request: {:?}", x),
+        }
+
+        // the device answers with its own session id
+        let ack = ComplexAck {
+            invoke_id: 1,
+            service: ComplexAckService::VtOpen(VtOpenAck {
+                remote_vt_session_id: 29,
+            }),
+        };
+        let mut buf = [0; 32];
+        match round_trip(
+            ApplicationPdu::ComplexAck(ack),
+            &mut buf,
+            2,
+            &[0x15, 0x21, 0x1D],
+        ) {
+            ApplicationPdu::ComplexAck(ComplexAck {
+                invoke_id: 1,
+                service: ComplexAckService::VtOpen(x),
+            }) => assert_eq!(x.remote_vt_session_id, 29),
+            x => panic!("expected VtOpen ack: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn vt_close_round_trip() {
+        let ids = [29, 300];
+        let service = ConfirmedRequestService::VtClose(VtClose::new(&ids));
+        let apdu = ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(2, service));
+        let mut buf = [0; 32];
+        match round_trip(apdu, &mut buf, 3, &[0x16, 0x21, 0x1D, 0x22, 0x01, 0x2C]) {
+            ApplicationPdu::ConfirmedRequest(ConfirmedRequest {
+                service: ConfirmedRequestService::VtClose(x),
+                ..
+            }) => {
+                let mut iter = x.into_iter().map(|x| x.unwrap());
+                assert_eq!(iter.next(), Some(29));
+                assert_eq!(iter.next(), Some(300));
+                assert_eq!(iter.next(), None);
+            }
+            x => panic!("expected VtClose request: {:?}", x),
+        }
+
+        // a VtClose is answered with a simple ack
+        let ack = SimpleAck {
+            invoke_id: 2,
+            service_choice: ConfirmedServiceChoice::VtClose,
+        };
+        let mut buf = [0; 32];
+        match round_trip(ApplicationPdu::SimpleAck(ack), &mut buf, 2, &[0x16]) {
+            ApplicationPdu::SimpleAck(SimpleAck {
+                invoke_id: 2,
+                service_choice: ConfirmedServiceChoice::VtClose,
+            }) => {}
+            x => panic!("expected VtClose simple ack: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn vt_data_ack_round_trip() {
+        // only 4 of the octets were accepted
+        let ack = ComplexAck {
+            invoke_id: 3,
+            service: ComplexAckService::VtData(VtDataAck {
+                all_new_data_accepted: false,
+                accepted_octet_count: Some(4),
+            }),
+        };
+        let mut buf = [0; 32];
+        match round_trip(
+            ApplicationPdu::ComplexAck(ack),
+            &mut buf,
+            2,
+            &[0x17, 0x09, 0x00, 0x19, 0x04],
+        ) {
+            ApplicationPdu::ComplexAck(ComplexAck {
+                service: ComplexAckService::VtData(x),
+                ..
+            }) => {
+                assert!(!x.all_new_data_accepted);
+                assert_eq!(x.accepted_octet_count, Some(4));
+            }
+            x => panic!("expected VtData ack: {:?}", x),
+        }
+
+        let ack = ComplexAck {
+            invoke_id: 3,
+            service: ComplexAckService::VtData(VtDataAck {
+                all_new_data_accepted: true,
+                accepted_octet_count: None,
+            }),
+        };
+        let mut buf = [0; 32];
+        match round_trip(
+            ApplicationPdu::ComplexAck(ack),
+            &mut buf,
+            2,
+            &[0x17, 0x09, 0x01],
+        ) {
+            ApplicationPdu::ComplexAck(ComplexAck {
+                service: ComplexAckService::VtData(x),
+                ..
+            }) => {
+                assert!(x.all_new_data_accepted);
+                assert_eq!(x.accepted_octet_count, None);
+            }
+            x => panic!("expected VtData ack: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn vt_data_round_trip() {
+        let data = b"show config\r\n";
+        let service = ConfirmedRequestService::VtData(VtData::new(5, data, 0));
+        let data_link = DataLink::new_confirmed_req(ConfirmedRequest::new(1, service));
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::default();
+        let decoded = DataLink::decode(&mut reader, encoded).unwrap();
+        let apdu = decoded.npdu.unwrap().network_message;
+        match apdu {
+            NetworkMessage::Apdu(ApplicationPdu::ConfirmedRequest(ConfirmedRequest {
+                service: ConfirmedRequestService::VtData(x),
+                ..
+            })) => {
+                assert_eq!(x.vt_session_id, 5);
+                assert_eq!(x.vt_new_data, data);
+                assert_eq!(x.vt_data_flag, 0);
+            }
+            x => panic!("expected VtData request: {:?}", x),
+        }
+    }
+}
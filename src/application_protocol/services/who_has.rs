@@ -0,0 +1,97 @@
+use crate::{
+    application_protocol::{
+        primitives::data_value::CharacterString, unconfirmed::UnconfirmedServiceChoice,
+    },
+    common::{
+        error::Error,
+        helper::{decode_unsigned, encode_context_object_id, encode_context_unsigned},
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        tag::{Tag, TagNumber},
+    },
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WhoHasObject<'a> {
+    ObjectId(ObjectId),
+    ObjectName(&'a str),
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WhoHas<'a> {
+    pub device_instance_range: Option<(u32, u32)>, // low and high limit (inclusive)
+    pub object: WhoHasObject<'a>,
+}
+
+impl<'a> WhoHas<'a> {
+    const LOW_LIMIT_TAG: u8 = 0;
+    const HIGH_LIMIT_TAG: u8 = 1;
+    const OBJECT_ID_TAG: u8 = 2;
+    const OBJECT_NAME_TAG: u8 = 3;
+
+    pub fn new(object: WhoHasObject<'a>) -> Self {
+        Self {
+            device_instance_range: None,
+            object,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(UnconfirmedServiceChoice::WhoHas as u8);
+        if let Some((low, high)) = self.device_instance_range {
+            encode_context_unsigned(writer, Self::LOW_LIMIT_TAG, low);
+            encode_context_unsigned(writer, Self::HIGH_LIMIT_TAG, high);
+        }
+        match &self.object {
+            WhoHasObject::ObjectId(object_id) => {
+                encode_context_object_id(writer, Self::OBJECT_ID_TAG, object_id);
+            }
+            WhoHasObject::ObjectName(name) => {
+                let utf8_encoded = name.as_bytes();
+                Tag::new(
+                    TagNumber::ContextSpecific(Self::OBJECT_NAME_TAG),
+                    utf8_encoded.len() as u32 + 1, // keep space for encoding byte
+                )
+                .encode(writer);
+                writer.push(0); // utf8 encoding
+                writer.extend_from_slice(utf8_encoded);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // device instance range (optional)
+        let mut tag = Tag::decode(reader, buf)?;
+        let mut device_instance_range = None;
+        if let TagNumber::ContextSpecific(Self::LOW_LIMIT_TAG) = tag.number {
+            let low = decode_unsigned(tag.value, reader, buf)? as u32;
+            let tag_high = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecific(Self::HIGH_LIMIT_TAG),
+                "WhoHas decode high limit",
+            )?;
+            let high = decode_unsigned(tag_high.value, reader, buf)? as u32;
+            device_instance_range = Some((low, high));
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        let object = match tag.number {
+            TagNumber::ContextSpecific(Self::OBJECT_ID_TAG) => {
+                WhoHasObject::ObjectId(ObjectId::decode(tag.value, reader, buf)?)
+            }
+            TagNumber::ContextSpecific(Self::OBJECT_NAME_TAG) => {
+                let name = CharacterString::decode(tag.value, reader, buf)?;
+                WhoHasObject::ObjectName(name.inner)
+            }
+            number => return Err(Error::TagNotSupported(("WhoHas decode object", number))),
+        };
+
+        Ok(Self {
+            device_instance_range,
+            object,
+        })
+    }
+}
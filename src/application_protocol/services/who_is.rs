@@ -1,18 +1,155 @@
 use crate::{
     application_protocol::unconfirmed::UnconfirmedServiceChoice,
-    common::io::{Reader, Writer},
+    common::{
+        error::Error,
+        helper::{decode_unsigned, encode_context_unsigned},
+        io::{Reader, Writer},
+        spec::BACNET_MAX_INSTANCE,
+        tag::{Tag, TagNumber},
+    },
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct WhoIs {}
+pub struct WhoIs {
+    pub device_instance_range: Option<(u32, u32)>, // low and high limit (inclusive)
+}
 
 impl WhoIs {
+    const LOW_LIMIT_TAG: u8 = 0;
+    const HIGH_LIMIT_TAG: u8 = 1;
+
+    // all devices should respond
+    pub fn new() -> Self {
+        Self {
+            device_instance_range: None,
+        }
+    }
+
+    // only devices with an instance number between low and high (inclusive) should respond
+    pub fn new_with_range(low: u32, high: u32) -> Self {
+        Self {
+            device_instance_range: Some((low, high)),
+        }
+    }
+
+    // true if a device with this instance number should answer with an IAm
+    pub fn matches(&self, device_instance: u32) -> bool {
+        match self.device_instance_range {
+            Some((low, high)) => (low..=high).contains(&device_instance),
+            None => true,
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
-        writer.push(UnconfirmedServiceChoice::WhoIs as u8)
+        writer.push(UnconfirmedServiceChoice::WhoIs as u8);
+        if let Some((low, high)) = self.device_instance_range {
+            encode_context_unsigned(writer, Self::LOW_LIMIT_TAG, low);
+            encode_context_unsigned(writer, Self::HIGH_LIMIT_TAG, high);
+        }
+    }
+
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        // device instance range (optional)
+        if reader.eof() {
+            return Ok(Self::new());
+        }
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::LOW_LIMIT_TAG),
+            "WhoIs decode low limit",
+        )?;
+        let low = Self::decode_limit(tag.value, reader, buf)?;
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::HIGH_LIMIT_TAG),
+            "WhoIs decode high limit",
+        )?;
+        let high = Self::decode_limit(tag.value, reader, buf)?;
+
+        Ok(Self::new_with_range(low, high))
+    }
+
+    // a device instance number (0 to 4194303)
+    fn decode_limit(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<u32, Error> {
+        let limit = decode_unsigned(len, reader, buf)?;
+        if limit > BACNET_MAX_INSTANCE as u64 {
+            return Err(Error::InvalidValue(
+                "WhoIs decode device instance range limit out of range",
+            ));
+        }
+        Ok(limit as u32)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        error::Error,
+        io::{Reader, Writer},
+    };
+
+    use super::WhoIs;
+
+    #[test]
+    fn range_round_trip_and_matches() {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        WhoIs::new_with_range(1000, 1999).encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(encoded, &[0x08, 0x0A, 0x03, 0xE8, 0x1A, 0x07, 0xCF]);
+
+        // skip the service choice
+        let mut reader = Reader::new_with_len(encoded.len());
+        reader.index = 1;
+        let who_is = WhoIs::decode(&mut reader, encoded).unwrap();
+        assert_eq!(who_is.device_instance_range, Some((1000, 1999)));
+
+        assert!(who_is.matches(1000));
+        assert!(who_is.matches(1999));
+        assert!(!who_is.matches(999));
+        assert!(!who_is.matches(2000));
+
+        // no range means everyone
+        let mut reader = Reader::new_with_len(1);
+        reader.index = 1;
+        let who_is = WhoIs::decode(&mut reader, &[0x08]).unwrap();
+        assert!(who_is.device_instance_range.is_none());
+        assert!(who_is.matches(4194302));
+    }
+
+    #[test]
+    fn range_limits_out_of_range() {
+        // 0 to 4194303 (the largest instance number)
+        let input = [0x08, 0x09, 0x00, 0x1B, 0x3F, 0xFF, 0xFF];
+        let mut reader = Reader::new_with_len(input.len());
+        reader.index = 1;
+        let who_is = WhoIs::decode(&mut reader, &input).unwrap();
+        assert_eq!(who_is.device_instance_range, Some((0, 4194303)));
+
+        // a high limit of 4194304 would have matched instance 0 if it was truncated
+        let input = [0x08, 0x09, 0x00, 0x1B, 0x40, 0x00, 0x00];
+        let mut reader = Reader::new_with_len(input.len());
+        reader.index = 1;
+        assert!(matches!(
+            WhoIs::decode(&mut reader, &input),
+            Err(Error::InvalidValue(_))
+        ));
 
-    pub fn decode(_reader: &mut Reader, _buf: &[u8]) -> Self {
-        Self {}
+        // a 5 byte low limit
+        let input = [0x08, 0x0D, 0x05, 0x01, 0x00, 0x00, 0x00, 0x00, 0x19, 0x01];
+        let mut reader = Reader::new_with_len(input.len());
+        reader.index = 1;
+        assert!(matches!(
+            WhoIs::decode(&mut reader, &input),
+            Err(Error::InvalidValue(_))
+        ));
     }
 }
@@ -1,11 +1,12 @@
 use crate::{
     application_protocol::primitives::data_value::ApplicationDataValueWrite,
     common::{
+        daily_schedule::WeeklySchedule,
         error::Error,
         helper::{
-            decode_context_object_id, decode_context_property_id, decode_unsigned,
-            encode_closing_tag, encode_context_enumerated, encode_context_object_id,
-            encode_context_unsigned, encode_opening_tag,
+            decode_constructed_value, decode_context_object_id, decode_context_property_id,
+            decode_unsigned, encode_closing_tag, encode_context_enumerated,
+            encode_context_object_id, encode_context_unsigned, encode_opening_tag,
         },
         io::{Reader, Writer},
         object_id::ObjectId,
@@ -21,6 +22,9 @@ pub struct WriteProperty<'a> {
     pub object_id: ObjectId,
     pub property_id: PropertyId,
     pub priority: Option<u8>,
+    // None writes the whole array (or a property that is not an array)
+    // Some(0) writes the number of elements which resizes the array on devices that allow it
+    // Some(n) writes element n (elements are numbered from 1)
     pub array_index: Option<u32>,
     pub value: ApplicationDataValueWrite<'a>,
 }
@@ -32,6 +36,7 @@ impl<'a> WriteProperty<'a> {
     const TAG_VALUE: u8 = 3;
     const TAG_PRIORITY: u8 = 4;
     const LOWEST_PRIORITY: u8 = 16;
+    const ARRAY_LEN_INDEX: u32 = 0;
 
     pub fn new(
         object_id: ObjectId,
@@ -49,6 +54,28 @@ impl<'a> WriteProperty<'a> {
         }
     }
 
+    // resizes an array property by writing its length to index 0 (e.g. to add or remove state text)
+    pub fn new_array_len(object_id: ObjectId, property_id: PropertyId, len: u32) -> Self {
+        Self::new(
+            object_id,
+            property_id,
+            None,
+            Some(Self::ARRAY_LEN_INDEX),
+            ApplicationDataValueWrite::UnsignedInt(len),
+        )
+    }
+
+    // replaces the whole weekly schedule of a schedule object (a schedule has no priority array)
+    pub fn new_weekly_schedule(object_id: ObjectId, weekly_schedule: WeeklySchedule<'a>) -> Self {
+        Self::new(
+            object_id,
+            PropertyId::PropWeeklySchedule,
+            None,
+            None,
+            ApplicationDataValueWrite::WeeklySchedule(weekly_schedule),
+        )
+    }
+
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         let object_id = decode_context_object_id(
             reader,
@@ -63,45 +90,42 @@ impl<'a> WriteProperty<'a> {
             "WriteProperty decode property_id",
         )?;
 
-        // array_index
-        let mut tag = Tag::decode(reader, buf)?;
+        // array_index (optional)
         let mut array_index = None;
+        let index = reader.index;
+        let tag = Tag::decode(reader, buf)?;
         if let TagNumber::ContextSpecific(Self::TAG_ARRAY_INDEX) = tag.number {
             let array_index_tmp = decode_unsigned(tag.value, reader, buf)? as u32;
             if array_index_tmp != BACNET_ARRAY_ALL {
                 array_index = Some(array_index_tmp)
             }
-
-            // read another tag
-            tag = Tag::decode(reader, buf)?;
+        } else {
+            reader.index = index;
         }
 
         // value
-        tag.expect_number(
-            "WriteProperty decode value",
-            TagNumber::ContextSpecificOpening(Self::TAG_VALUE),
-        )?;
-        let value = ApplicationDataValueWrite::decode(&object_id, &property_id, reader, buf)?;
-        Tag::decode_expected(
+        let value = decode_constructed_value(
             reader,
             buf,
-            TagNumber::ContextSpecificClosing(Self::TAG_VALUE),
+            Self::TAG_VALUE,
             "WriteProperty decode value",
+            |reader, buf| ApplicationDataValueWrite::decode(&object_id, &property_id, reader, buf),
         )?;
 
-        // priority
-        let tag = Tag::decode_expected(
-            reader,
-            buf,
-            TagNumber::ContextSpecific(Self::TAG_PRIORITY),
-            "WriteProperty decode priority",
-        )?;
-        let priority = tag.value as u8;
-        let priority = if priority == Self::LOWEST_PRIORITY {
-            None
-        } else {
-            Some(priority)
-        };
+        // priority (optional)
+        let mut priority = None;
+        if !reader.eof() {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecific(Self::TAG_PRIORITY),
+                "WriteProperty decode priority",
+            )?;
+            let value = decode_unsigned(tag.value, reader, buf)? as u8;
+            if value != Self::LOWEST_PRIORITY {
+                priority = Some(value);
+            }
+        }
 
         Ok(Self {
             object_id,
@@ -136,4 +160,290 @@ impl<'a> WriteProperty<'a> {
             .min(Self::LOWEST_PRIORITY) as u32;
         encode_context_unsigned(writer, Self::TAG_PRIORITY, priority);
     }
+
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::{
+            ApplicationDataValueWrite, CharacterString, Enumerated, Time,
+        },
+        common::{
+            daily_schedule::WeeklySchedule,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            time_value::{SimpleApplicationDataValue, TimeValue},
+        },
+    };
+
+    use super::WriteProperty;
+
+    #[test]
+    fn write_array_element() {
+        let object_id = ObjectId::new(ObjectType::ObjectMultiStateValue, 1);
+        let value = ApplicationDataValueWrite::CharacterString(CharacterString { inner: "Auto" });
+        let element =
+            WriteProperty::new(object_id, PropertyId::PropStateText, None, Some(2), value);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        element.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(
+            encoded,
+            &[
+                0x0C, 0x04, 0xC0, 0x00, 0x01, // object id
+                0x19, 0x6E, // property id
+                0x29, 0x02, // array index
+                0x3E, 0x75, 0x05, 0x00, b'A', b'u', b't', b'o', 0x3F, // value
+                0x49, 0x10, // priority
+            ]
+        );
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = WriteProperty::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.array_index, Some(2));
+        assert!(matches!(
+            decoded.value,
+            ApplicationDataValueWrite::CharacterString(CharacterString { inner: "Auto" })
+        ));
+
+        // without an index the whole array is written
+        let mut whole = element.clone();
+        whole.array_index = None;
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        whole.encode(&mut writer);
+        assert_eq!(writer.to_bytes().len(), encoded.len() - 2);
+        assert!(!writer.to_bytes().contains(&0x29));
+    }
+
+    #[test]
+    fn resize_array() {
+        // index 0 of an array is its length, writing it adds or removes state text
+        let object_id = ObjectId::new(ObjectType::ObjectMultiStateValue, 1);
+        let request = WriteProperty::new_array_len(object_id, PropertyId::PropStateText, 3);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(
+            &encoded[5..],
+            &[
+                0x19, 0x6E, // property id
+                0x29, 0x00, // array index 0
+                0x3E, 0x21, 0x03, 0x3F, // value
+                0x49, 0x10, // priority
+            ]
+        );
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = WriteProperty::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.array_index, Some(0));
+        assert!(matches!(
+            decoded.value,
+            ApplicationDataValueWrite::UnsignedInt(3)
+        ));
+    }
+
+    #[test]
+    fn integer_values() {
+        // max master of the ms/tp port of a router
+        let object_id = ObjectId::new(ObjectType::ObjectNetworkPort, 1);
+        let request = WriteProperty::new(
+            object_id,
+            PropertyId::PropMaxMaster,
+            None,
+            None,
+            ApplicationDataValueWrite::UnsignedInt(127),
+        );
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(
+            encoded,
+            &[
+                0x0C, 0x0E, 0x00, 0x00, 0x01, // network port 1
+                0x19, 0x40, // property id
+                0x3E, 0x21, 0x7F, 0x3F, // value
+                0x49, 0x10, // priority
+            ]
+        );
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = WriteProperty::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.property_id, PropertyId::PropMaxMaster);
+        assert!(matches!(
+            decoded.value,
+            ApplicationDataValueWrite::UnsignedInt(127)
+        ));
+
+        // utc offset of a device in minutes
+        let object_id = ObjectId::new(ObjectType::ObjectDevice, 1);
+        let request = WriteProperty::new(
+            object_id,
+            PropertyId::PropUtcOffset,
+            None,
+            None,
+            ApplicationDataValueWrite::SignedInt(-60),
+        );
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(&encoded[7..], &[0x3E, 0x31, 0xC4, 0x3F, 0x49, 0x10]);
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = WriteProperty::decode(&mut reader, encoded).unwrap();
+        assert!(matches!(
+            decoded.value,
+            ApplicationDataValueWrite::SignedInt(-60)
+        ));
+    }
+
+    #[test]
+    fn real_value_round_trip() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 5);
+        let request = WriteProperty::new(
+            object_id,
+            PropertyId::PropPresentValue,
+            Some(8),
+            None,
+            ApplicationDataValueWrite::Real(21.5),
+        );
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(
+            &encoded[7..],
+            &[0x3E, 0x44, 0x41, 0xAC, 0x00, 0x00, 0x3F, 0x49, 0x08]
+        );
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = WriteProperty::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.object_id, object_id);
+        assert_eq!(decoded.array_index, None);
+        assert_eq!(decoded.priority, Some(8));
+        assert!(matches!(decoded.value, ApplicationDataValueWrite::Real(x) if x == 21.5));
+        assert!(reader.eof());
+    }
+
+    #[test]
+    fn priority_is_optional() {
+        // present value of binary value 1 = active without a priority
+        let input = [
+            0x0C, 0x01, 0x40, 0x00, 0x01, 0x19, 0x55, 0x3E, 0x91, 0x01, 0x3F,
+        ];
+        let mut reader = Reader::new_with_len(input.len());
+        let decoded = WriteProperty::decode(&mut reader, &input).unwrap();
+        assert_eq!(decoded.priority, None);
+        assert!(matches!(
+            decoded.value,
+            ApplicationDataValueWrite::Enumerated(Enumerated::Binary(_))
+        ));
+    }
+
+    #[test]
+    fn proprietary_units_round_trip() {
+        // a gateway echoing back a vendor specific unit (50123) that it read from a device
+        let input = [
+            0x0C, 0x00, 0x00, 0x00, 0x01, // object id
+            0x19, 0x75, // property id
+            0x3E, 0x92, 0xC3, 0xCB, 0x3F, // value
+            0x49, 0x10, // priority
+        ];
+        let mut reader = Reader::new_with_len(input.len());
+        let decoded = WriteProperty::decode(&mut reader, &input).unwrap();
+        let units = match &decoded.value {
+            ApplicationDataValueWrite::Enumerated(x) => x,
+            x => panic!("expected enumerated: {:?}", x),
+        };
+        assert!(matches!(units, Enumerated::Unknown(50123)));
+        assert_eq!(units.value(), 50123);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &input);
+    }
+
+    #[test]
+    #[allow(clippy::useless_conversion)]
+    fn write_weekly_schedule() {
+        let time = |hour| Time {
+            hour,
+            minute: 0,
+            second: 0,
+            hundredths: 0,
+        };
+        let weekday = [
+            TimeValue {
+                time: time(7),
+                value: SimpleApplicationDataValue::Real(21.0),
+            },
+            TimeValue {
+                time: time(18),
+                value: SimpleApplicationDataValue::Real(16.0),
+            },
+        ];
+        let weekend: &[TimeValue] = &[];
+        // into() so that this works with and without the alloc feature
+        let weekly_schedule = WeeklySchedule::new(
+            weekday[..].into(),
+            weekday[..].into(),
+            weekday[..].into(),
+            weekday[..].into(),
+            weekday[..].into(),
+            weekend.into(),
+            weekend.into(),
+        );
+        let object_id = ObjectId::new(ObjectType::ObjectSchedule, 1);
+        let request = WriteProperty::new_weekly_schedule(object_id, weekly_schedule);
+
+        let mut buf = [0; 256];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        #[rustfmt::skip]
+        let day = [
+            0x0E, // day opening tag
+            0xB4, 0x07, 0x00, 0x00, 0x00, 0x44, 0x41, 0xA8, 0x00, 0x00, // 07:00 21.0
+            0xB4, 0x12, 0x00, 0x00, 0x00, 0x44, 0x41, 0x80, 0x00, 0x00, // 18:00 16.0
+            0x0F, // day closing tag
+        ];
+        assert_eq!(&encoded[..5], &[0x0C, 0x04, 0x40, 0x00, 0x01]); // object id
+        assert_eq!(&encoded[5..8], &[0x19, 0x7B, 0x3E]); // property id and value opening tag
+        for i in 0..5 {
+            let start = 8 + i * day.len();
+            assert_eq!(&encoded[start..start + day.len()], &day);
+        }
+        // empty weekend days, value closing tag and priority
+        assert_eq!(
+            &encoded[8 + 5 * day.len()..],
+            &[0x0E, 0x0F, 0x0E, 0x0F, 0x3F, 0x49, 0x10]
+        );
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = WriteProperty::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.property_id, PropertyId::PropWeeklySchedule);
+        let ApplicationDataValueWrite::WeeklySchedule(decoded_schedule) = &decoded.value else {
+            panic!("expected a weekly schedule: {:?}", decoded.value);
+        };
+        let mut buf2 = [0; 256];
+        let mut writer = Writer::new(&mut buf2);
+        decoded_schedule.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &encoded[8..encoded.len() - 3]);
+    }
 }
@@ -1,13 +1,13 @@
 use crate::common::{
-    error::{Error, Unimplemented},
+    error::Error,
     io::{Reader, Writer},
 };
 
 use super::{
     application_pdu::ApduType,
     services::{
-        change_of_value::CovNotification, i_am::IAm, time_synchronization::TimeSynchronization,
-        who_is::WhoIs,
+        change_of_value::CovNotification, event_notification::EventNotification, i_am::IAm,
+        i_have::IHave, time_synchronization::TimeSynchronization, who_has::WhoHas, who_is::WhoIs,
     },
 };
 
@@ -16,8 +16,14 @@ use super::{
 pub enum UnconfirmedRequest<'a> {
     WhoIs(WhoIs),
     IAm(IAm),
+    WhoHas(WhoHas<'a>),
+    IHave(IHave<'a>),
     CovNotification(CovNotification<'a>),
+    EventNotification(EventNotification<'a>),
     TimeSynchronization(TimeSynchronization),
+    UtcTimeSynchronization(TimeSynchronization),
+    // a service this library does not decode (yet), choice is the raw service choice
+    Unknown { choice: u8, data: &'a [u8] },
 }
 
 impl<'a> UnconfirmedRequest<'a> {
@@ -27,33 +33,78 @@ impl<'a> UnconfirmedRequest<'a> {
         match &self {
             Self::IAm(payload) => payload.encode(writer),
             Self::WhoIs(payload) => payload.encode(writer),
-            Self::CovNotification(_) => todo!(),
+            Self::CovNotification(payload) => {
+                writer.push(UnconfirmedServiceChoice::CovNotification as u8);
+                payload.encode(writer);
+            }
+            Self::EventNotification(payload) => {
+                writer.push(UnconfirmedServiceChoice::EventNotification as u8);
+                payload.encode(writer);
+            }
+            Self::WhoHas(payload) => payload.encode(writer),
+            Self::IHave(payload) => payload.encode(writer),
             Self::TimeSynchronization(payload) => payload.encode(writer),
+            Self::UtcTimeSynchronization(payload) => payload.encode_utc(writer),
+            Self::Unknown { choice, data } => {
+                writer.push(*choice);
+                writer.extend_from_slice(data);
+            }
         }
     }
+
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
+    // unknown or unsupported service choices are returned as UnconfirmedRequest::Unknown
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
-        let choice: UnconfirmedServiceChoice = reader
-            .read_byte(buf)?
-            .try_into()
-            .map_err(|x| Error::InvalidVariant(("UnconfirmedRequest choice", x as u32)))?;
+        let byte = reader.read_byte(buf)?;
+        let choice: UnconfirmedServiceChoice = match byte.try_into() {
+            Ok(choice) => choice,
+            Err(choice) => return Ok(Self::decode_unknown(choice, reader, buf)),
+        };
         match choice {
             UnconfirmedServiceChoice::IAm => {
                 let apdu = IAm::decode(reader, buf)?;
                 Ok(Self::IAm(apdu))
             }
             UnconfirmedServiceChoice::WhoIs => {
-                let apdu = WhoIs::decode(reader, buf);
+                let apdu = WhoIs::decode(reader, buf)?;
                 Ok(Self::WhoIs(apdu))
             }
+            UnconfirmedServiceChoice::WhoHas => {
+                let apdu = WhoHas::decode(reader, buf)?;
+                Ok(Self::WhoHas(apdu))
+            }
+            UnconfirmedServiceChoice::IHave => {
+                let apdu = IHave::decode(reader, buf)?;
+                Ok(Self::IHave(apdu))
+            }
             UnconfirmedServiceChoice::CovNotification => {
                 let apdu = CovNotification::decode(reader, buf)?;
                 Ok(Self::CovNotification(apdu))
             }
-            x => Err(Error::Unimplemented(
-                Unimplemented::UnconfirmedServiceChoice(x),
-            )),
+            UnconfirmedServiceChoice::EventNotification => {
+                let apdu = EventNotification::decode(reader, buf)?;
+                Ok(Self::EventNotification(apdu))
+            }
+            UnconfirmedServiceChoice::TimeSynchronization => {
+                let apdu = TimeSynchronization::decode(reader, buf)?;
+                Ok(Self::TimeSynchronization(apdu))
+            }
+            UnconfirmedServiceChoice::UtcTimeSynchronization => {
+                let apdu = TimeSynchronization::decode(reader, buf)?;
+                Ok(Self::UtcTimeSynchronization(apdu))
+            }
+            _ => Ok(Self::decode_unknown(byte, reader, buf)),
         }
     }
+
+    fn decode_unknown(choice: u8, reader: &mut Reader, buf: &'a [u8]) -> Self {
+        let data = reader.remaining(buf);
+        reader.index = reader.end;
+        Self::Unknown { choice, data }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -116,3 +167,68 @@ impl TryFrom<u8> for UnconfirmedServiceChoice {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::services::who_has::WhoHasObject,
+        common::{io::Reader, object_id::ObjectType},
+    };
+
+    use super::UnconfirmedRequest;
+
+    fn decode(input: &[u8]) -> UnconfirmedRequest<'_> {
+        let mut reader = Reader::new_with_len(input.len());
+        UnconfirmedRequest::decode(&mut reader, input).unwrap()
+    }
+
+    #[test]
+    fn classify_broadcasts() {
+        // who-has with a range looking for object name "AHU"
+        let who_has = decode(&[
+            0x07, 0x09, 0x01, 0x1A, 0x03, 0xE8, 0x3C, 0x00, 0x41, 0x48, 0x55,
+        ]);
+        match who_has {
+            UnconfirmedRequest::WhoHas(x) => {
+                assert_eq!(x.device_instance_range, Some((1, 1000)));
+                assert!(matches!(x.object, WhoHasObject::ObjectName("AHU")));
+            }
+            x => panic!("expected WhoHas: {:?}", x),
+        }
+
+        // i-have from device 5 for analog-input 1 named "T"
+        let i_have = decode(&[
+            0x01, 0xC4, 0x02, 0x00, 0x00, 0x05, 0xC4, 0x00, 0x00, 0x00, 0x01, 0x72, 0x00, 0x54,
+        ]);
+        match i_have {
+            UnconfirmedRequest::IHave(x) => {
+                assert_eq!(x.device_id.id, 5);
+                assert_eq!(x.object_id.object_type, ObjectType::ObjectAnalogInput);
+                assert_eq!(x.object_name, "T");
+            }
+            x => panic!("expected IHave: {:?}", x),
+        }
+
+        // utc-time-synchronization
+        let utc = decode(&[0x09, 0xA4, 124, 6, 1, 6, 0xB4, 12, 30, 0, 0]);
+        assert!(
+            matches!(utc, UnconfirmedRequest::UtcTimeSynchronization(x) if x.time.minute == 30)
+        );
+
+        // text-message is not supported and neither is choice 42
+        assert!(matches!(
+            decode(&[0x05, 0x01]),
+            UnconfirmedRequest::Unknown {
+                choice: 5,
+                data: [0x01]
+            }
+        ));
+        assert!(matches!(
+            decode(&[42]),
+            UnconfirmedRequest::Unknown {
+                choice: 42,
+                data: []
+            }
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+use crate::application_protocol::primitives::data_value::ApplicationDataValue;
+
+use super::{
+    error::Error,
+    helper::{
+        decode_unsigned, encode_closing_tag, encode_context_bool, encode_context_enumerated,
+        encode_context_object_id, encode_context_unsigned, encode_opening_tag,
+        get_tagged_body_for_tag,
+    },
+    io::{Reader, Writer},
+    object_id::ObjectId,
+    property_id::PropertyId,
+    tag::{Tag, TagNumber},
+};
+
+// BACnetActionList - the action property of a Command object
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ActionList<'a> {
+    pub commands: &'a [ActionCommand<'a>],
+    buf: &'a [u8],
+}
+
+impl<'a> ActionList<'a> {
+    const ACTION_TAG: u8 = 0;
+
+    pub fn new(commands: &'a [ActionCommand<'a>]) -> Self {
+        Self { commands, buf: &[] }
+    }
+
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self { commands: &[], buf }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::ACTION_TAG);
+        for command in self.commands {
+            command.encode(writer);
+        }
+        encode_closing_tag(writer, Self::ACTION_TAG);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let buf = get_tagged_body_for_tag(reader, buf, Self::ACTION_TAG, "ActionList decode")?;
+        Ok(Self::new_from_buf(buf))
+    }
+}
+
+impl<'a> IntoIterator for &'_ ActionList<'a> {
+    type Item = Result<ActionCommand<'a>, Error>;
+    type IntoIter = ActionCommandIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ActionCommandIter {
+            reader: Reader::new_with_len(self.buf.len()),
+            buf: self.buf,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ActionCommandIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> Iterator for ActionCommandIter<'a> {
+    type Item = Result<ActionCommand<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(ActionCommand::decode(&mut self.reader, self.buf))
+    }
+}
+
+// BACnetActionCommand - a single write performed by a Command object
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ActionCommand<'a> {
+    pub device_id: Option<ObjectId>, // None means the local device
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub value: ApplicationDataValue<'a>,
+    pub priority: Option<u8>,
+    pub post_delay: Option<u32>, // seconds
+    pub quit_on_failure: bool,
+    pub write_successful: bool,
+}
+
+impl<'a> ActionCommand<'a> {
+    const DEVICE_ID_TAG: u8 = 0;
+    const OBJECT_ID_TAG: u8 = 1;
+    const PROPERTY_ID_TAG: u8 = 2;
+    const ARRAY_INDEX_TAG: u8 = 3;
+    const VALUE_TAG: u8 = 4;
+    const PRIORITY_TAG: u8 = 5;
+    const POST_DELAY_TAG: u8 = 6;
+    const QUIT_ON_FAILURE_TAG: u8 = 7;
+    const WRITE_SUCCESSFUL_TAG: u8 = 8;
+
+    pub fn new(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        value: ApplicationDataValue<'a>,
+    ) -> Self {
+        Self {
+            device_id: None,
+            object_id,
+            property_id,
+            array_index: None,
+            value,
+            priority: None,
+            post_delay: None,
+            quit_on_failure: false,
+            write_successful: false,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        if let Some(device_id) = &self.device_id {
+            encode_context_object_id(writer, Self::DEVICE_ID_TAG, device_id);
+        }
+        encode_context_object_id(writer, Self::OBJECT_ID_TAG, &self.object_id);
+        encode_context_enumerated(writer, Self::PROPERTY_ID_TAG, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::ARRAY_INDEX_TAG, array_index);
+        }
+        encode_opening_tag(writer, Self::VALUE_TAG);
+        self.value.encode(writer);
+        encode_closing_tag(writer, Self::VALUE_TAG);
+        if let Some(priority) = self.priority {
+            encode_context_unsigned(writer, Self::PRIORITY_TAG, priority as u32);
+        }
+        if let Some(post_delay) = self.post_delay {
+            encode_context_unsigned(writer, Self::POST_DELAY_TAG, post_delay);
+        }
+        encode_context_bool(writer, Self::QUIT_ON_FAILURE_TAG, self.quit_on_failure);
+        encode_context_bool(writer, Self::WRITE_SUCCESSFUL_TAG, self.write_successful);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // device_id (optional)
+        let mut tag = Tag::decode(reader, buf)?;
+        let mut device_id = None;
+        if let TagNumber::ContextSpecific(Self::DEVICE_ID_TAG) = tag.number {
+            device_id = Some(ObjectId::decode(tag.value, reader, buf)?);
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        // object_id
+        tag.expect_number(
+            "ActionCommand decode object_id",
+            TagNumber::ContextSpecific(Self::OBJECT_ID_TAG),
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        // property_id
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::PROPERTY_ID_TAG),
+            "ActionCommand decode property_id",
+        )?;
+        let property_id: PropertyId = (decode_unsigned(tag.value, reader, buf)? as u32).into();
+
+        // array_index (optional)
+        let mut tag = Tag::decode(reader, buf)?;
+        let mut array_index = None;
+        if let TagNumber::ContextSpecific(Self::ARRAY_INDEX_TAG) = tag.number {
+            array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        // value
+        tag.expect_number(
+            "ActionCommand decode value",
+            TagNumber::ContextSpecificOpening(Self::VALUE_TAG),
+        )?;
+        let tag = Tag::decode(reader, buf)?;
+        let value = ApplicationDataValue::decode(&tag, &object_id, &property_id, reader, buf)?;
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(Self::VALUE_TAG),
+            "ActionCommand decode value",
+        )?;
+
+        // priority (optional)
+        let mut tag = Tag::decode(reader, buf)?;
+        let mut priority = None;
+        if let TagNumber::ContextSpecific(Self::PRIORITY_TAG) = tag.number {
+            priority = Some(decode_unsigned(tag.value, reader, buf)? as u8);
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        // post_delay (optional)
+        let mut post_delay = None;
+        if let TagNumber::ContextSpecific(Self::POST_DELAY_TAG) = tag.number {
+            post_delay = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        // quit_on_failure
+        tag.expect_number(
+            "ActionCommand decode quit_on_failure",
+            TagNumber::ContextSpecific(Self::QUIT_ON_FAILURE_TAG),
+        )?;
+        let quit_on_failure = decode_unsigned(tag.value, reader, buf)? > 0;
+
+        // write_successful
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::WRITE_SUCCESSFUL_TAG),
+            "ActionCommand decode write_successful",
+        )?;
+        let write_successful = decode_unsigned(tag.value, reader, buf)? > 0;
+
+        Ok(Self {
+            device_id,
+            object_id,
+            property_id,
+            array_index,
+            value,
+            priority,
+            post_delay,
+            quit_on_failure,
+            write_successful,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::ApplicationDataValue,
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{ActionCommand, ActionList};
+
+    #[test]
+    fn action_list_round_trip() {
+        let mut remote = ActionCommand::new(
+            ObjectId::new(ObjectType::ObjectAnalogValue, 7),
+            PropertyId::PropPresentValue,
+            ApplicationDataValue::Real(21.5),
+        );
+        remote.device_id = Some(ObjectId::new(ObjectType::ObjectDevice, 1234));
+        remote.priority = Some(8);
+        remote.post_delay = Some(30);
+        remote.quit_on_failure = true;
+
+        let local = ActionCommand::new(
+            ObjectId::new(ObjectType::ObjectBinaryValue, 2),
+            PropertyId::PropOutOfService,
+            ApplicationDataValue::Boolean(true),
+        );
+
+        let commands = [remote, local];
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ActionList::new(&commands).encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = ActionList::decode(&mut reader, encoded).unwrap();
+        let mut iter = decoded.into_iter();
+
+        let first = iter.next().unwrap().unwrap();
+        assert_eq!(first.device_id.unwrap().id, 1234);
+        assert_eq!(first.object_id.id, 7);
+        assert_eq!(first.property_id, PropertyId::PropPresentValue);
+        assert!(matches!(first.value, ApplicationDataValue::Real(x) if x == 21.5));
+        assert_eq!(first.priority, Some(8));
+        assert_eq!(first.post_delay, Some(30));
+        assert!(first.quit_on_failure);
+        assert!(!first.write_successful);
+
+        let second = iter.next().unwrap().unwrap();
+        assert!(second.device_id.is_none());
+        assert_eq!(second.object_id.id, 2);
+        assert!(matches!(second.value, ApplicationDataValue::Boolean(true)));
+        assert_eq!(second.priority, None);
+        assert_eq!(second.post_delay, None);
+
+        assert!(iter.next().is_none());
+    }
+}
@@ -0,0 +1,343 @@
+use crate::application_protocol::primitives::data_value::{ApplicationDataValue, Date};
+
+use super::{
+    daily_schedule::TimeValueList,
+    error::Error,
+    helper::{
+        check_len, decode_unsigned, encode_closing_tag, encode_context_object_id,
+        encode_context_unsigned, encode_opening_tag, get_tagged_body_for_tag,
+    },
+    io::{Reader, Writer},
+    object_id::ObjectId,
+    tag::{ApplicationTagNumber, Tag, TagNumber},
+};
+
+// BACnetDateRange - either end may be unspecified (all wildcards) for an open ended range
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DateRange {
+    pub start: Date,
+    pub end: Date,
+}
+
+impl DateRange {
+    pub fn new(start: Date, end: Date) -> Self {
+        Self { start, end }
+    }
+
+    // wday is ignored because it is implied by the date
+    pub fn contains(&self, date: &Date) -> bool {
+        let key = |x: &Date| (x.year, x.month, x.day);
+        let after_start = self.start.is_unspecified() || key(date) >= key(&self.start);
+        let before_end = self.end.is_unspecified() || key(date) <= key(&self.end);
+        after_start && before_end
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        ApplicationDataValue::Date(self.start.clone()).encode(writer);
+        ApplicationDataValue::Date(self.end.clone()).encode(writer);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let start = decode_application_date(reader, buf, "DateRange decode start")?;
+        let end = decode_application_date(reader, buf, "DateRange decode end")?;
+        Ok(Self { start, end })
+    }
+}
+
+// BACnetWeekNDay - 0xFF in any field means any
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeekNDay {
+    pub month: u8,         // 1 = January, 13 = odd months, 14 = even months
+    pub week_of_month: u8, // 1 = days 1 to 7, 2 = days 8 to 14, ... 6 = last 7 days of the month
+    pub day_of_week: u8,   // 1 = Monday ... 7 = Sunday
+}
+
+impl WeekNDay {
+    pub const LEN: u32 = 3; // 3 bytes
+    pub const ANY: u8 = 0xFF;
+
+    pub fn new(month: u8, week_of_month: u8, day_of_week: u8) -> Self {
+        Self {
+            month,
+            week_of_month,
+            day_of_week,
+        }
+    }
+
+    // the value only (the tag is encoded by the caller)
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(self.month);
+        writer.push(self.week_of_month);
+        writer.push(self.day_of_week);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let [month, week_of_month, day_of_week] = reader.read_bytes(buf)?;
+        Ok(Self {
+            month,
+            week_of_month,
+            day_of_week,
+        })
+    }
+}
+
+// BACnetCalendarEntry - an entry in the date list of a Calendar object
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CalendarEntry {
+    Date(Date),
+    DateRange(DateRange),
+    WeekNDay(WeekNDay),
+}
+
+impl CalendarEntry {
+    const DATE_TAG: u8 = 0;
+    const DATE_RANGE_TAG: u8 = 1;
+    const WEEK_N_DAY_TAG: u8 = 2;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Date(date) => {
+                Tag::new(TagNumber::ContextSpecific(Self::DATE_TAG), Date::LEN).encode(writer);
+                date.encode(writer);
+            }
+            Self::DateRange(date_range) => {
+                encode_opening_tag(writer, Self::DATE_RANGE_TAG);
+                date_range.encode(writer);
+                encode_closing_tag(writer, Self::DATE_RANGE_TAG);
+            }
+            Self::WeekNDay(week_n_day) => {
+                Tag::new(
+                    TagNumber::ContextSpecific(Self::WEEK_N_DAY_TAG),
+                    WeekNDay::LEN,
+                )
+                .encode(writer);
+                week_n_day.encode(writer);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::DATE_TAG) => {
+                check_len(tag.value, Date::LEN, "CalendarEntry decode date")?;
+                let date = Date::decode(reader, buf)?;
+                Ok(Self::Date(date))
+            }
+            TagNumber::ContextSpecificOpening(Self::DATE_RANGE_TAG) => {
+                let date_range = DateRange::decode(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::DATE_RANGE_TAG),
+                    "CalendarEntry decode date range",
+                )?;
+                Ok(Self::DateRange(date_range))
+            }
+            TagNumber::ContextSpecific(Self::WEEK_N_DAY_TAG) => {
+                check_len(tag.value, WeekNDay::LEN, "CalendarEntry decode week n day")?;
+                let week_n_day = WeekNDay::decode(reader, buf)?;
+                Ok(Self::WeekNDay(week_n_day))
+            }
+            number => Err(Error::TagNotSupported(("CalendarEntry decode", number))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpecialEventPeriod {
+    CalendarEntry(CalendarEntry),
+    CalendarReference(ObjectId), // a Calendar object
+}
+
+// BACnetSpecialEvent - an entry in the exception schedule of a Schedule object
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SpecialEvent<'a> {
+    pub period: SpecialEventPeriod,
+    pub time_values: TimeValueList<'a>,
+    pub priority: u8, // 1 (highest) to 16 (lowest)
+}
+
+impl<'a> SpecialEvent<'a> {
+    const CALENDAR_ENTRY_TAG: u8 = 0;
+    const CALENDAR_REFERENCE_TAG: u8 = 1;
+    const TIME_VALUES_TAG: u8 = 2;
+    const PRIORITY_TAG: u8 = 3;
+
+    pub fn new(period: SpecialEventPeriod, time_values: TimeValueList<'a>, priority: u8) -> Self {
+        Self {
+            period,
+            time_values,
+            priority,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match &self.period {
+            SpecialEventPeriod::CalendarEntry(entry) => {
+                encode_opening_tag(writer, Self::CALENDAR_ENTRY_TAG);
+                entry.encode(writer);
+                encode_closing_tag(writer, Self::CALENDAR_ENTRY_TAG);
+            }
+            SpecialEventPeriod::CalendarReference(object_id) => {
+                encode_context_object_id(writer, Self::CALENDAR_REFERENCE_TAG, object_id);
+            }
+        }
+
+        encode_opening_tag(writer, Self::TIME_VALUES_TAG);
+        for time_value in self.time_values.time_values {
+            time_value.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TIME_VALUES_TAG);
+
+        encode_context_unsigned(writer, Self::PRIORITY_TAG, self.priority as u32);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        let period = match tag.number {
+            TagNumber::ContextSpecificOpening(Self::CALENDAR_ENTRY_TAG) => {
+                let entry = CalendarEntry::decode(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::CALENDAR_ENTRY_TAG),
+                    "SpecialEvent decode calendar entry",
+                )?;
+                SpecialEventPeriod::CalendarEntry(entry)
+            }
+            TagNumber::ContextSpecific(Self::CALENDAR_REFERENCE_TAG) => {
+                let object_id = ObjectId::decode(tag.value, reader, buf)?;
+                SpecialEventPeriod::CalendarReference(object_id)
+            }
+            number => {
+                return Err(Error::TagNotSupported((
+                    "SpecialEvent decode period",
+                    number,
+                )))
+            }
+        };
+
+        let time_values = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TIME_VALUES_TAG,
+            "SpecialEvent decode time values",
+        )?;
+        let time_values = TimeValueList::new_from_buf(time_values);
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::PRIORITY_TAG),
+            "SpecialEvent decode priority",
+        )?;
+        let priority = decode_unsigned(tag.value, reader, buf)? as u8;
+
+        Ok(Self {
+            period,
+            time_values,
+            priority,
+        })
+    }
+}
+
+fn decode_application_date(
+    reader: &mut Reader,
+    buf: &[u8],
+    context: &'static str,
+) -> Result<Date, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::Date),
+        context,
+    )?;
+    check_len(tag.value, Date::LEN, context)?;
+    Date::decode(reader, buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::{Date, Time},
+        common::{
+            daily_schedule::TimeValueList,
+            io::{Reader, Writer},
+            time_value::{SimpleApplicationDataValue, TimeValue},
+        },
+    };
+
+    use super::{CalendarEntry, DateRange, SpecialEvent, SpecialEventPeriod, WeekNDay};
+
+    #[test]
+    fn open_ended_date_range() {
+        let range = DateRange::new(Date::new(2024, 12, 24, 2), Date::unspecified());
+        assert!(range.contains(&Date::new(2030, 1, 1, 2)));
+        assert!(!range.contains(&Date::new(2024, 12, 23, 1)));
+
+        let entry = CalendarEntry::DateRange(range);
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        entry.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(
+            encoded,
+            &[0x1E, 0xA4, 124, 12, 24, 2, 0xA4, 0xFF, 0xFF, 0xFF, 0xFF, 0x1F]
+        );
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        match CalendarEntry::decode(&mut reader, encoded).unwrap() {
+            CalendarEntry::DateRange(x) => {
+                assert_eq!(x.start.year, 2024);
+                assert!(x.end.is_unspecified());
+            }
+            x => panic!("expected date range: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn special_event_round_trip() {
+        // every Monday in December
+        let entry = CalendarEntry::WeekNDay(WeekNDay::new(12, WeekNDay::ANY, 1));
+        let time_values = [TimeValue {
+            time: Time::new(8, 0, 0, 0),
+            value: SimpleApplicationDataValue::Real(21.0),
+        }];
+        let event = SpecialEvent::new(
+            SpecialEventPeriod::CalendarEntry(entry),
+            TimeValueList::new(&time_values),
+            10,
+        );
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        event.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = SpecialEvent::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.priority, 10);
+        match decoded.period {
+            SpecialEventPeriod::CalendarEntry(CalendarEntry::WeekNDay(x)) => {
+                assert_eq!(x.month, 12);
+                assert_eq!(x.week_of_month, WeekNDay::ANY);
+                assert_eq!(x.day_of_week, 1);
+            }
+            x => panic!("expected week n day: {:?}", x),
+        }
+        let mut iter = decoded.time_values.into_iter();
+        let time_value = iter.next().unwrap().unwrap();
+        assert_eq!(time_value.time.hour, 8);
+        assert!(iter.next().is_none());
+    }
+}
@@ -0,0 +1,304 @@
+use super::{
+    error::Error,
+    helper::{
+        check_len, decode_constructed_value, decode_unsigned, encode_closing_tag,
+        encode_context_bool, encode_context_unsigned, encode_opening_tag,
+    },
+    io::{Reader, Writer},
+    object_property_reference::ObjectPropertyReference,
+    recipient::Recipient,
+    tag::{Tag, TagNumber},
+};
+
+// BACnetCOVSubscription - an entry in the PropActiveCovSubscriptions of a device
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CovSubscription<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub recipient: Recipient<'a>,
+    pub process_id: u32,
+    pub monitored_property: ObjectPropertyReference,
+    pub issue_confirmed_notifications: bool,
+    pub time_remaining_seconds: u32,
+    pub cov_increment: Option<f32>, // only for subscriptions to analog values
+}
+
+impl<'a> CovSubscription<'a> {
+    const RECIPIENT_PROCESS_TAG: u8 = 0;
+    const MONITORED_PROPERTY_TAG: u8 = 1;
+    const ISSUE_CONFIRMED_TAG: u8 = 2;
+    const TIME_REMAINING_TAG: u8 = 3;
+    const COV_INCREMENT_TAG: u8 = 4;
+
+    // BACnetRecipientProcess
+    const RECIPIENT_TAG: u8 = 0;
+    const PROCESS_ID_TAG: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::RECIPIENT_PROCESS_TAG);
+        encode_opening_tag(writer, Self::RECIPIENT_TAG);
+        self.recipient.encode(writer);
+        encode_closing_tag(writer, Self::RECIPIENT_TAG);
+        encode_context_unsigned(writer, Self::PROCESS_ID_TAG, self.process_id);
+        encode_closing_tag(writer, Self::RECIPIENT_PROCESS_TAG);
+
+        encode_opening_tag(writer, Self::MONITORED_PROPERTY_TAG);
+        self.monitored_property.encode(writer);
+        encode_closing_tag(writer, Self::MONITORED_PROPERTY_TAG);
+
+        encode_context_bool(
+            writer,
+            Self::ISSUE_CONFIRMED_TAG,
+            self.issue_confirmed_notifications,
+        );
+        encode_context_unsigned(
+            writer,
+            Self::TIME_REMAINING_TAG,
+            self.time_remaining_seconds,
+        );
+        if let Some(cov_increment) = self.cov_increment {
+            Tag::new(TagNumber::ContextSpecific(Self::COV_INCREMENT_TAG), 4).encode(writer);
+            writer.extend_from_slice(&cov_increment.to_be_bytes());
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (recipient, process_id) = decode_constructed_value(
+            reader,
+            buf,
+            Self::RECIPIENT_PROCESS_TAG,
+            "CovSubscription decode recipient_process",
+            |reader, buf| {
+                let recipient = decode_constructed_value(
+                    reader,
+                    buf,
+                    Self::RECIPIENT_TAG,
+                    "CovSubscription decode recipient",
+                    Recipient::decode,
+                )?;
+                let tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecific(Self::PROCESS_ID_TAG),
+                    "CovSubscription decode process_id",
+                )?;
+                let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+                Ok((recipient, process_id))
+            },
+        )?;
+
+        let monitored_property = decode_constructed_value(
+            reader,
+            buf,
+            Self::MONITORED_PROPERTY_TAG,
+            "CovSubscription decode monitored_property",
+            ObjectPropertyReference::decode,
+        )?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::ISSUE_CONFIRMED_TAG),
+            "CovSubscription decode issue_confirmed_notifications",
+        )?;
+        let issue_confirmed_notifications = decode_unsigned(tag.value, reader, buf)? > 0;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TIME_REMAINING_TAG),
+            "CovSubscription decode time_remaining",
+        )?;
+        let time_remaining_seconds = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // cov_increment (optional)
+        let mut cov_increment = None;
+        if !reader.eof() {
+            let index = reader.index;
+            let tag = Tag::decode(reader, buf)?;
+            if let TagNumber::ContextSpecific(Self::COV_INCREMENT_TAG) = tag.number {
+                check_len(tag.value, 4, "CovSubscription decode cov_increment")?;
+                cov_increment = Some(f32::from_be_bytes(reader.read_bytes(buf)?));
+            } else {
+                // not ours, it is the start of the next subscription
+                reader.index = index;
+            }
+        }
+
+        Ok(Self {
+            recipient,
+            process_id,
+            monitored_property,
+            issue_confirmed_notifications,
+            time_remaining_seconds,
+            cov_increment,
+        })
+    }
+}
+
+// the value of PropActiveCovSubscriptions
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovSubscriptionList<'a> {
+    pub subscriptions: &'a [CovSubscription<'a>],
+    buf: &'a [u8],
+}
+
+impl<'a> CovSubscriptionList<'a> {
+    pub fn new(subscriptions: &'a [CovSubscription<'a>]) -> Self {
+        Self {
+            subscriptions,
+            buf: &[],
+        }
+    }
+
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self {
+            subscriptions: &[],
+            buf,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        if self.subscriptions.is_empty() {
+            // decoded subscriptions are written back out as is
+            writer.extend_from_slice(self.buf);
+        }
+        for subscription in self.subscriptions {
+            subscription.encode(writer);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'_ CovSubscriptionList<'a> {
+    type Item = Result<CovSubscription<'a>, Error>;
+    type IntoIter = CovSubscriptionIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CovSubscriptionIter {
+            reader: Reader::new_with_len(self.buf.len()),
+            buf: self.buf,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovSubscriptionIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> Iterator for CovSubscriptionIter<'a> {
+    type Item = Result<CovSubscription<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        let subscription = CovSubscription::decode(&mut self.reader, self.buf);
+        if subscription.is_err() {
+            // stop rather than trying to decode the rest of a corrupt list
+            self.reader.index = self.reader.end;
+        }
+        Some(subscription)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::services::read_property::{ReadPropertyAck, ReadPropertyValue},
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            object_property_reference::ObjectPropertyReference,
+            property_id::PropertyId,
+            recipient::Recipient,
+        },
+    };
+
+    use super::{CovSubscription, CovSubscriptionList};
+
+    #[test]
+    fn active_cov_subscriptions_ack() {
+        let subscriptions = [
+            CovSubscription {
+                recipient: Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 99)),
+                process_id: 7,
+                monitored_property: ObjectPropertyReference::new(
+                    ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                    PropertyId::PropPresentValue,
+                    None,
+                ),
+                issue_confirmed_notifications: true,
+                time_remaining_seconds: 300,
+                cov_increment: Some(0.5),
+            },
+            CovSubscription {
+                recipient: Recipient::Address {
+                    network: 0,
+                    mac: &[192, 168, 1, 10, 0xBA, 0xC0],
+                },
+                process_id: 1,
+                monitored_property: ObjectPropertyReference::new(
+                    ObjectId::new(ObjectType::ObjectBinaryInput, 2),
+                    PropertyId::PropPresentValue,
+                    None,
+                ),
+                issue_confirmed_notifications: false,
+                time_remaining_seconds: 0,
+                cov_increment: None,
+            },
+        ];
+        let ack = ReadPropertyAck {
+            object_id: ObjectId::new(ObjectType::ObjectDevice, 1234),
+            property_id: PropertyId::PropActiveCovSubscriptions,
+            property_value: ReadPropertyValue::CovSubscriptionList(CovSubscriptionList::new(
+                &subscriptions,
+            )),
+        };
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        reader.index = 1; // skip the service choice
+        let decoded = ReadPropertyAck::decode(&mut reader, encoded).unwrap();
+        let ReadPropertyValue::CovSubscriptionList(list) = &decoded.property_value else {
+            panic!("expected cov subscriptions: {:?}", decoded.property_value);
+        };
+
+        let mut list = list.into_iter().map(|x| x.unwrap());
+        let first = list.next().unwrap();
+        assert!(matches!(first.recipient, Recipient::Device(x) if x.id == 99));
+        assert_eq!(first.process_id, 7);
+        assert_eq!(first.monitored_property.object_id.id, 1);
+        assert!(first.issue_confirmed_notifications);
+        assert_eq!(first.time_remaining_seconds, 300);
+        assert_eq!(first.cov_increment, Some(0.5));
+
+        let second = list.next().unwrap();
+        assert!(matches!(
+            second.recipient,
+            Recipient::Address { network: 0, mac } if mac == [192, 168, 1, 10, 0xBA, 0xC0]
+        ));
+        assert_eq!(
+            second.monitored_property.object_id.object_type,
+            ObjectType::ObjectBinaryInput
+        );
+        assert!(!second.issue_confirmed_notifications);
+        assert_eq!(second.cov_increment, None);
+        assert!(list.next().is_none());
+
+        // a decoded list encodes back to the same bytes
+        let mut buf2 = [0; 128];
+        let mut writer = Writer::new(&mut buf2);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), encoded);
+    }
+}
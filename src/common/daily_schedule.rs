@@ -89,14 +89,7 @@ impl<'a> WeeklySchedule<'a> {
     }
 
     fn decode_day(reader: &mut Reader, buf: &'a [u8]) -> Result<Vec<TimeValue>, Error> {
-        let (body_buf, _tag_num) = get_tagged_body(reader, buf)?;
-        let mut inner_reader = Reader::new_with_len(body_buf.len());
-        let mut time_values = Vec::new();
-        while !inner_reader.eof() {
-            let time_value = TimeValue::decode(&mut inner_reader, &body_buf)?;
-            time_values.push(time_value);
-        }
-        Ok(time_values)
+        reader.list_of(0, buf, TimeValue::decode)?.collect()
     }
 }
 
@@ -187,7 +180,14 @@ impl<'a> TimeValueList<'a> {
     }
 
     pub fn encode(&self, writer: &mut Writer) {
-        encode_day(writer, self.time_values.iter());
+        if self.time_values.is_empty() {
+            // decoded time values are written back out as is
+            encode_opening_tag(writer, 0);
+            writer.extend_from_slice(self.buf);
+            encode_closing_tag(writer, 0);
+        } else {
+            encode_day(writer, self.time_values.iter());
+        }
     }
 
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
@@ -0,0 +1,169 @@
+use super::{
+    error::Error,
+    helper::{
+        decode_context_object_id, decode_context_property_id, decode_unsigned,
+        encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
+    },
+    io::{Reader, Writer},
+    object_id::ObjectId,
+    property_id::PropertyId,
+    tag::{Tag, TagNumber},
+};
+
+// BACnetDeviceObjectPropertyReference
+// used by event enrollment, trend log and loop objects to point at a property (possibly on another device)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub device_id: Option<ObjectId>, // None means the local device
+}
+
+impl DeviceObjectPropertyReference {
+    const OBJECT_ID_TAG: u8 = 0;
+    const PROPERTY_ID_TAG: u8 = 1;
+    const ARRAY_INDEX_TAG: u8 = 2;
+    const DEVICE_ID_TAG: u8 = 3;
+
+    pub fn new(object_id: ObjectId, property_id: PropertyId) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index: None,
+            device_id: None,
+        }
+    }
+
+    pub fn with_device_id(mut self, device_id: ObjectId) -> Self {
+        self.device_id = Some(device_id);
+        self
+    }
+
+    pub fn with_array_index(mut self, array_index: u32) -> Self {
+        self.array_index = Some(array_index);
+        self
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::OBJECT_ID_TAG, &self.object_id);
+        encode_context_enumerated(writer, Self::PROPERTY_ID_TAG, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::ARRAY_INDEX_TAG, array_index);
+        }
+        if let Some(device_id) = &self.device_id {
+            encode_context_object_id(writer, Self::DEVICE_ID_TAG, device_id);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::OBJECT_ID_TAG,
+            "DeviceObjectPropertyReference decode object_id",
+        )?;
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::PROPERTY_ID_TAG,
+            "DeviceObjectPropertyReference decode property_id",
+        )?;
+
+        // array_index and device_id (both optional)
+        let mut array_index = None;
+        let mut device_id = None;
+        while !reader.eof() {
+            let index = reader.index;
+            let tag = Tag::decode(reader, buf)?;
+            match tag.number {
+                TagNumber::ContextSpecific(Self::ARRAY_INDEX_TAG)
+                    if array_index.is_none() && device_id.is_none() =>
+                {
+                    array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+                }
+                TagNumber::ContextSpecific(Self::DEVICE_ID_TAG) if device_id.is_none() => {
+                    device_id = Some(ObjectId::decode(tag.value, reader, buf)?);
+                }
+                _ => {
+                    // not ours, leave it for the caller
+                    reader.index = index;
+                    break;
+                }
+            }
+        }
+
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+            device_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+    };
+
+    use super::DeviceObjectPropertyReference;
+
+    #[test]
+    fn remote_reference_round_trip() {
+        let reference = DeviceObjectPropertyReference::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, 3),
+            PropertyId::PropPriorityArray,
+        )
+        .with_array_index(8)
+        .with_device_id(ObjectId::new(ObjectType::ObjectDevice, 1234));
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        reference.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(
+            encoded,
+            &[
+                0x0C, 0x00, 0x00, 0x00, 0x03, // object id
+                0x19, 0x57, // property id
+                0x29, 0x08, // array index
+                0x3C, 0x02, 0x00, 0x04, 0xD2, // device id
+            ]
+        );
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = DeviceObjectPropertyReference::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.object_id, reference.object_id);
+        assert_eq!(decoded.property_id, PropertyId::PropPriorityArray);
+        assert_eq!(decoded.array_index, Some(8));
+        assert_eq!(decoded.device_id, reference.device_id);
+        assert!(reader.eof());
+    }
+
+    #[test]
+    fn local_reference_leaves_following_tags() {
+        let reference = DeviceObjectPropertyReference::new(
+            ObjectId::new(ObjectType::ObjectBinaryInput, 1),
+            PropertyId::PropPresentValue,
+        );
+
+        // followed by an unrelated context tag 4 that belongs to the caller
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        reference.encode(&mut writer);
+        writer.extend_from_slice(&[0x49, 0x01]);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = DeviceObjectPropertyReference::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.array_index, None);
+        assert_eq!(decoded.device_id, None);
+        assert_eq!(&encoded[reader.index..], &[0x49, 0x01]);
+    }
+}
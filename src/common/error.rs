@@ -1,9 +1,14 @@
 use crate::application_protocol::{
-    application_pdu::ApduType, confirmed::ConfirmedServiceChoice,
-    services::read_range::ReadRangeValueType, unconfirmed::UnconfirmedServiceChoice,
+    application_pdu::ApduType,
+    confirmed::{ConfirmedBacnetError, ConfirmedServiceChoice},
+    services::read_range::ReadRangeValueType,
+    unconfirmed::UnconfirmedServiceChoice,
 };
 
-use super::tag::{ApplicationTagNumber, Tag, TagNumber};
+use super::{
+    spec::{AbortReason, RejectReason},
+    tag::{ApplicationTagNumber, Tag, TagNumber},
+};
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -21,6 +26,22 @@ pub enum Error {
     TagValueInvalid((&'static str, Tag, u32)),
     ReaderEof(usize),
     ConvertDataLink(&'static str),
+    // the device responded to our request with an Error, Reject or Abort pdu instead of an ack
+    RequestFailed(ConfirmedBacnetError),
+    Rejected(RejectReason),
+    Aborted(AbortReason),
+    // a constructed value is nested deeper than we are willing to decode (see MAX_NESTING_DEPTH)
+    NestingTooDeep,
+}
+
+impl Error {
+    // true if the request was aborted because the response would have to be segmented
+    pub fn is_segmentation_not_supported(&self) -> bool {
+        matches!(
+            self,
+            Self::SegmentationNotSupported | Self::Aborted(AbortReason::SegmentationNotSupported)
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
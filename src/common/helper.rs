@@ -6,12 +6,34 @@ use super::{
     tag::{ApplicationTagNumber, Tag, TagNumber},
 };
 
+// the default limit on how deeply constructed values (opening and closing tags) can be nested
+// stops a malicious or corrupt packet from overflowing the stack (or spinning) on a small device
+// the _with_max_depth decoders take a different limit (e.g. a lower one on a device with a small stack)
+pub const MAX_NESTING_DEPTH: usize = 32;
+
 // reads and checks the opening tag number passed in
 pub fn get_tagged_body_for_tag<'a>(
     reader: &mut Reader,
     buf: &'a [u8],
     expected_tag_number: u8,
     context: &'static str,
+) -> Result<&'a [u8], Error> {
+    get_tagged_body_for_tag_with_max_depth(
+        reader,
+        buf,
+        expected_tag_number,
+        context,
+        MAX_NESTING_DEPTH,
+    )
+}
+
+// returns Error::NestingTooDeep if the expected tag is nested inside itself more than max_depth times
+pub fn get_tagged_body_for_tag_with_max_depth<'a>(
+    reader: &mut Reader,
+    buf: &'a [u8],
+    expected_tag_number: u8,
+    context: &'static str,
+    max_depth: usize,
 ) -> Result<&'a [u8], Error> {
     Tag::decode_expected(
         reader,
@@ -20,18 +42,50 @@ pub fn get_tagged_body_for_tag<'a>(
         context,
     )?;
 
-    get_tagged_body_internal(reader, buf, expected_tag_number)
+    get_tagged_body_internal(reader, buf, expected_tag_number, max_depth)
+}
+
+// decodes a value wrapped in the opening and closing context tag passed in (e.g. the value of a WriteProperty)
+// decode gets a reader over the bytes between the tags so it can't run past the closing tag
+// and the reader is left after the closing tag no matter how much of the value was decoded
+pub fn decode_constructed_value<'a, T>(
+    reader: &mut Reader,
+    buf: &'a [u8],
+    tag_number: u8,
+    context: &'static str,
+    decode: impl FnOnce(&mut Reader, &'a [u8]) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let buf = get_tagged_body_for_tag(reader, buf, tag_number, context)?;
+    let mut reader = Reader::new_with_len(buf.len());
+    decode(&mut reader, buf)
+}
+
+// checks the length of a fixed size value (e.g. a date) read from a tag
+pub fn check_len(len: u32, expected: u32, context: &'static str) -> Result<(), Error> {
+    if len != expected {
+        return Err(Error::Length((context, len)));
+    }
+    Ok(())
 }
 
 // This gives you the bytes that begin after the opening tag and end before the closing tag
 pub fn get_tagged_body<'a>(reader: &mut Reader, buf: &'a [u8]) -> Result<(&'a [u8], u8), Error> {
+    get_tagged_body_with_max_depth(reader, buf, MAX_NESTING_DEPTH)
+}
+
+// returns Error::NestingTooDeep if the opening tag is nested inside itself more than max_depth times
+pub fn get_tagged_body_with_max_depth<'a>(
+    reader: &mut Reader,
+    buf: &'a [u8],
+    max_depth: usize,
+) -> Result<(&'a [u8], u8), Error> {
     let tag = Tag::decode(reader, buf)?;
     let tag_number = match tag.number {
         TagNumber::ContextSpecificOpening(x) => x,
         x => return Err(Error::ExpectedOpeningTag(x)),
     };
 
-    let buf = get_tagged_body_internal(reader, buf, tag_number)?;
+    let buf = get_tagged_body_internal(reader, buf, tag_number, max_depth)?;
     Ok((buf, tag_number))
 }
 
@@ -39,6 +93,7 @@ fn get_tagged_body_internal<'a>(
     reader: &mut Reader,
     buf: &'a [u8],
     opening_tag_number: u8,
+    max_depth: usize,
 ) -> Result<&'a [u8], Error> {
     let index = reader.index;
     let mut counter = 0;
@@ -47,7 +102,12 @@ fn get_tagged_body_internal<'a>(
 
         // keep track of nested tags and when we reach our last closing tag then we are done
         match tag.number {
-            TagNumber::ContextSpecificOpening(x) if x == opening_tag_number => counter += 1,
+            TagNumber::ContextSpecificOpening(x) if x == opening_tag_number => {
+                counter += 1;
+                if counter > max_depth {
+                    return Err(Error::NestingTooDeep);
+                }
+            }
             TagNumber::ContextSpecificClosing(x) if x == opening_tag_number => {
                 if counter == 0 {
                     //  let len = reader.index - index - 1;
@@ -61,8 +121,8 @@ fn get_tagged_body_internal<'a>(
                 // tag value is not a length for bool
             }
             _ => {
-                // skip past value and read next tag
-                reader.index += tag.value as usize;
+                // skip past value and read next tag (reading that tag fails if the value is too long)
+                reader.index = reader.index.saturating_add(tag.value as usize);
             }
         }
     }
@@ -146,11 +206,16 @@ pub fn encode_closing_tag(writer: &mut Writer, tag_number: u8) {
 }
 
 pub fn encode_context_unsigned(writer: &mut Writer, tag_number: u8, value: u32) {
-    let len = get_len_u64(value as u64);
+    encode_context_unsigned_u64(writer, tag_number, value as u64);
+}
+
+// for counts that can exceed a u32 (e.g. the present value of an accumulator)
+pub fn encode_context_unsigned_u64(writer: &mut Writer, tag_number: u8, value: u64) {
+    let len = get_len_u64(value);
 
     let tag = Tag::new(TagNumber::ContextSpecific(tag_number), len);
     tag.encode(writer);
-    encode_unsigned(writer, len, value as u64);
+    encode_unsigned(writer, len, value);
 }
 
 pub fn decode_context_property_id(
@@ -208,6 +273,75 @@ pub fn encode_application_object_id(writer: &mut Writer, object_id: &ObjectId) {
     object_id.encode(writer);
 }
 
+pub fn encode_application_octet_string(writer: &mut Writer, value: &[u8]) {
+    Tag::new(
+        TagNumber::Application(ApplicationTagNumber::OctetString),
+        value.len() as u32,
+    )
+    .encode(writer);
+    writer.extend_from_slice(value);
+}
+
+pub fn decode_application_unsigned(
+    reader: &mut Reader,
+    buf: &[u8],
+    context: &'static str,
+) -> Result<u64, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+        context,
+    )?;
+    decode_unsigned(tag.value, reader, buf)
+}
+
+pub fn decode_application_octet_string<'a>(
+    reader: &mut Reader,
+    buf: &'a [u8],
+    context: &'static str,
+) -> Result<&'a [u8], Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::OctetString),
+        context,
+    )?;
+    reader.read_slice(tag.value as usize, buf)
+}
+
+// for bit strings that fit into a single byte (e.g. status flags, days of week)
+pub fn encode_application_bit_string_byte(writer: &mut Writer, unused_bits: u8, value: u8) {
+    Tag::new(TagNumber::Application(ApplicationTagNumber::BitString), 2).encode(writer);
+    writer.push(unused_bits);
+    writer.push(value);
+}
+
+pub fn decode_application_bit_string_byte(
+    reader: &mut Reader,
+    buf: &[u8],
+    context: &'static str,
+) -> Result<u8, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::BitString),
+        context,
+    )?;
+    if tag.value != 2 {
+        return Err(Error::Length((context, tag.value)));
+    }
+    let _unused_bits = reader.read_byte(buf)?;
+    reader.read_byte(buf)
+}
+
+pub fn encode_context_signed(writer: &mut Writer, tag_number: u8, value: i32) {
+    let mut len = get_len_i32(value);
+    len = if len == 3 { 4 } else { len }; // we don't bother with 3 byte integers (just save it as a 4 byte integer)
+    Tag::new(TagNumber::ContextSpecific(tag_number), len).encode(writer);
+    encode_signed(writer, len, value);
+}
+
 pub fn encode_application_signed(writer: &mut Writer, value: i32) {
     let mut len = get_len_i32(value);
     len = if len == 3 { 4 } else { len }; // we don't bother with 3 byte integers (just save it as a 4 byte integer)
@@ -215,6 +349,18 @@ pub fn encode_application_signed(writer: &mut Writer, value: i32) {
     encode_signed(writer, len, value);
 }
 
+pub fn encode_application_signed_i64(writer: &mut Writer, value: i64) {
+    // the number of bytes needed to keep the sign bit (at least 1)
+    let redundant_bits = if value < 0 {
+        value.leading_ones()
+    } else {
+        value.leading_zeros()
+    };
+    let len = 8 - ((redundant_bits - 1) / 8).min(7);
+    Tag::new(TagNumber::Application(ApplicationTagNumber::SignedInt), len).encode(writer);
+    writer.extend_from_slice(&value.to_be_bytes()[8 - len as usize..]);
+}
+
 pub fn get_len_u32(value: u32) -> u32 {
     if value < 0x100 {
         1
@@ -228,17 +374,8 @@ pub fn get_len_u32(value: u32) -> u32 {
 }
 
 fn get_len_u64(value: u64) -> u32 {
-    if value < 0x100 {
-        1
-    } else if value < 0x10000 {
-        2
-    } else if value < 0x1000000 {
-        3
-    } else if value < 0x100000000 {
-        4
-    } else {
-        8
-    }
+    // the number of bytes without leading zeros (at least 1)
+    8 - (value.leading_zeros() / 8).min(7)
 }
 
 fn get_len_i32(value: i32) -> u32 {
@@ -254,21 +391,12 @@ fn get_len_i32(value: i32) -> u32 {
 }
 
 pub fn decode_unsigned(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<u64, Error> {
-    let value = match len {
-        1 => reader.read_byte(buf)? as u64,
-        2 => u16::from_be_bytes(reader.read_bytes(buf)?) as u64,
-        3 => {
-            let bytes: [u8; 3] = reader.read_bytes(buf)?;
-            let mut tmp: [u8; 4] = [0; 4];
-            tmp[1..].copy_from_slice(&bytes);
-            u32::from_be_bytes(tmp) as u64
-        }
-        4 => u32::from_be_bytes(reader.read_bytes(buf)?) as u64,
-        8 => u64::from_be_bytes(reader.read_bytes(buf)?),
-        x => return Err(Error::Length(("unsigned len must be between 1 and 8", x))),
-    };
+    if !(1..=8).contains(&len) {
+        return Err(Error::Length(("unsigned len must be between 1 and 8", len)));
+    }
 
-    Ok(value)
+    let bytes = reader.read_slice(len as usize, buf)?;
+    Ok(bytes.iter().fold(0, |acc, x| (acc << 8) | *x as u64))
 }
 
 pub fn _decode_u32(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<u32, Error> {
@@ -288,15 +416,16 @@ pub fn _decode_u32(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<u32, Err
     Ok(value)
 }
 
+// two's complement so the sign bit of the first byte is extended for shorter encodings
 pub fn decode_signed(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<i32, Error> {
     let value = match len {
-        1 => reader.read_byte(buf)? as i32,
-        2 => u16::from_be_bytes(reader.read_bytes(buf)?) as i32,
+        1 => reader.read_byte(buf)? as i8 as i32,
+        2 => i16::from_be_bytes(reader.read_bytes(buf)?) as i32,
         3 => {
             let bytes: [u8; 3] = reader.read_bytes(buf)?;
             let mut tmp: [u8; 4] = [0; 4];
             tmp[1..].copy_from_slice(&bytes);
-            i32::from_be_bytes(tmp)
+            i32::from_be_bytes(tmp) << 8 >> 8
         }
         4 => i32::from_be_bytes(reader.read_bytes(buf)?),
         x => return Err(Error::Length(("signed len must be between 1 and 4", x))),
@@ -305,12 +434,20 @@ pub fn decode_signed(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<i32, E
     Ok(value)
 }
 
+// the same as decode_signed but for values up to 8 bytes long
+pub fn decode_signed_i64(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<i64, Error> {
+    let value = decode_unsigned(len, reader, buf)?;
+    let shift = 64 - len * 8;
+    Ok(((value << shift) as i64) >> shift)
+}
+
 pub fn encode_unsigned(writer: &mut Writer, len: u32, value: u64) {
     match len {
         1 => writer.push(value as u8),
         2 => encode_u16(writer, value as u16),
         3 => encode_u24(writer, value as u32),
         4 => encode_u32(writer, value as u32),
+        5..=7 => writer.extend_from_slice(&value.to_be_bytes()[8 - len as usize..]),
         8 => encode_u64(writer, value),
         _ => unreachable!(),
     }
@@ -324,3 +461,103 @@ pub fn encode_signed(writer: &mut Writer, len: u32, value: i32) {
         _ => unreachable!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{application_pdu::ApplicationPdu, confirmed::ComplexAckService},
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+        },
+        network_protocol::data_link::DataLink,
+    };
+
+    use super::{
+        encode_closing_tag, encode_opening_tag, get_tagged_body, get_tagged_body_with_max_depth,
+        MAX_NESTING_DEPTH,
+    };
+
+    fn nested(writer: &mut Writer, tag_number: u8, depth: usize, value: &[u8]) {
+        for _ in 0..depth {
+            encode_opening_tag(writer, tag_number);
+        }
+        writer.extend_from_slice(value);
+        for _ in 0..depth {
+            encode_closing_tag(writer, tag_number);
+        }
+    }
+
+    #[test]
+    fn nesting_depth_limit() {
+        // the outer tag plus MAX_NESTING_DEPTH nested tags is fine
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        nested(&mut writer, 3, MAX_NESTING_DEPTH + 1, &[0x21, 0x05]);
+        let encoded = writer.to_bytes();
+        let mut reader = Reader::new_with_len(encoded.len());
+        let (body, tag_number) = get_tagged_body(&mut reader, encoded).unwrap();
+        assert_eq!(tag_number, 3);
+        assert_eq!(body.len(), encoded.len() - 2);
+
+        // one more is too deep
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        nested(&mut writer, 3, MAX_NESTING_DEPTH + 2, &[0x21, 0x05]);
+        let encoded = writer.to_bytes();
+        let mut reader = Reader::new_with_len(encoded.len());
+        assert!(matches!(
+            get_tagged_body(&mut reader, encoded),
+            Err(Error::NestingTooDeep)
+        ));
+    }
+
+    #[test]
+    fn configurable_nesting_depth_limit() {
+        let mut buf = [0; 1024];
+        let mut writer = Writer::new(&mut buf);
+        nested(&mut writer, 3, 201, &[0x21, 0x05]);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let (body, _) = get_tagged_body_with_max_depth(&mut reader, encoded, 200).unwrap();
+        assert_eq!(body.len(), encoded.len() - 2);
+        assert!(reader.eof());
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        assert!(matches!(
+            get_tagged_body_with_max_depth(&mut reader, encoded, 4),
+            Err(Error::NestingTooDeep)
+        ));
+    }
+
+    #[test]
+    fn deeply_nested_frame() {
+        // a ReadPropertyMultiple ack with the present value wrapped in 100 opening tags
+        let mut buf = [0; 512];
+        let mut writer = Writer::new(&mut buf);
+        writer.extend_from_slice(&[0x81, 0x0A, 0x00, 0x00]); // bvlc (length set below)
+        writer.extend_from_slice(&[0x01, 0x00]); // npdu
+        writer.extend_from_slice(&[0x30, 0x05, 0x0E]); // complex ack, read property multiple
+        writer.extend_from_slice(&[0x0C, 0x00, 0x00, 0x00, 0x01]); // analog-input 1
+        encode_opening_tag(&mut writer, 1);
+        writer.extend_from_slice(&[0x29, 0x55]); // present-value
+        nested(&mut writer, 4, 100, &[0x44, 0x41, 0xAC, 0x00, 0x00]);
+        encode_closing_tag(&mut writer, 1);
+        let len = writer.index as u16;
+        writer.buf[2..4].copy_from_slice(&len.to_be_bytes());
+        let frame = writer.to_bytes();
+
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, frame).unwrap();
+        let Some(ApplicationPdu::ComplexAck(ack)) = message.apdu() else {
+            panic!("expected a complex ack: {:?}", message);
+        };
+        let ComplexAckService::ReadPropertyMultiple(ack) = &ack.service else {
+            panic!("expected a read property multiple ack: {:?}", ack);
+        };
+        let object = ack.into_iter().next().unwrap().unwrap();
+        let result = (&object.property_results).into_iter().next().unwrap();
+        assert!(matches!(result, Err(Error::NestingTooDeep)));
+    }
+}
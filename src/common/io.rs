@@ -1,29 +1,139 @@
-use super::error::Error;
+use super::{
+    error::Error,
+    helper::{
+        encode_closing_tag, encode_context_bool, encode_context_enumerated,
+        encode_context_object_id, encode_context_unsigned, encode_opening_tag,
+        get_tagged_body_for_tag,
+    },
+    object_id::ObjectId,
+    property_id::PropertyId,
+    tag::{Tag, TagNumber},
+};
+
+// a tag whose length is written by Writer::backpatch_len once the contents have been written
+#[derive(Debug, Clone)]
+pub struct LenHandle {
+    number: TagNumber,
+    start: usize,
+}
 
 pub struct Writer<'a> {
     pub buf: &'a mut [u8],
     pub index: usize,
+    measure_only: bool,
 }
 
 impl<'a> Writer<'a> {
     pub fn new(buf: &'a mut [u8]) -> Self {
-        Self { buf, index: 0 }
+        Self {
+            buf,
+            index: 0,
+            measure_only: false,
+        }
+    }
+
+    // a writer that only counts bytes (nothing is written) so you can find out how big an encoded pdu will be
+    pub fn new_measure() -> Writer<'static> {
+        Writer {
+            buf: &mut [],
+            index: 0,
+            measure_only: true,
+        }
+    }
+
+    // returns the number of bytes the encode function passed in would write
+    pub fn measure(encode: impl FnOnce(&mut Writer)) -> usize {
+        let mut writer = Writer::new_measure();
+        encode(&mut writer);
+        writer.index
+    }
+
+    pub fn is_measure_only(&self) -> bool {
+        self.measure_only
     }
 
     pub fn push(&mut self, item: u8) {
-        self.buf[self.index] = item;
+        if !self.measure_only {
+            self.buf[self.index] = item;
+        }
         self.index += 1;
     }
 
     pub fn extend_from_slice(&mut self, src: &[u8]) {
-        assert!(src.len() <= self.buf.len() - self.index);
-        self.buf[self.index..self.index + src.len()].copy_from_slice(src);
+        if !self.measure_only {
+            assert!(src.len() <= self.buf.len() - self.index);
+            self.buf[self.index..self.index + src.len()].copy_from_slice(src);
+        }
         self.index += src.len();
     }
 
+    // a measure only writer has nothing to return (use `index` for the length)
     pub fn to_bytes(&self) -> &[u8] {
+        if self.measure_only {
+            return &[];
+        }
         &self.buf[..self.index]
     }
+
+    // start writing from the beginning of the buffer again so one buffer can be reused for every encode
+    // the old bytes are left in place (they are overwritten by the next encode)
+    pub fn reset(&mut self) {
+        self.index = 0;
+    }
+
+    // starts a tag (e.g. an octet string) whose length isn't known until its contents have been written
+    // one byte is reserved for the tag and backpatch_len moves the contents along if the length needs more
+    pub fn reserve_len(&mut self, number: TagNumber) -> LenHandle {
+        let start = self.index;
+        self.push(0);
+        LenHandle { number, start }
+    }
+
+    // writes the tag started by reserve_len with the length of everything written since
+    pub fn backpatch_len(&mut self, handle: LenHandle) {
+        let contents_start = handle.start + 1;
+        let len = self.index - contents_start;
+
+        let mut tag_buf = [0; 10];
+        let mut tag_writer = Writer::new(&mut tag_buf);
+        Tag::new(handle.number, len as u32).encode(&mut tag_writer);
+        let tag_len = tag_writer.index;
+        let shift = tag_len - 1;
+
+        if !self.measure_only {
+            assert!(self.index + shift <= self.buf.len());
+            self.buf
+                .copy_within(contents_start..self.index, contents_start + shift);
+            self.buf[handle.start..handle.start + tag_len].copy_from_slice(&tag_buf[..tag_len]);
+        }
+        self.index += shift;
+    }
+
+    // convenience methods for context tagged values so that service encoders don't have to hand roll tag bytes
+
+    pub fn push_opening_tag(&mut self, tag_number: u8) {
+        encode_opening_tag(self, tag_number);
+    }
+
+    pub fn push_closing_tag(&mut self, tag_number: u8) {
+        encode_closing_tag(self, tag_number);
+    }
+
+    pub fn push_context_unsigned(&mut self, tag_number: u8, value: u32) {
+        encode_context_unsigned(self, tag_number, value);
+    }
+
+    pub fn push_context_bool(&mut self, tag_number: u8, value: bool) {
+        encode_context_bool(self, tag_number, value);
+    }
+
+    pub fn push_context_object_id(&mut self, tag_number: u8, object_id: &ObjectId) {
+        encode_context_object_id(self, tag_number, object_id);
+    }
+
+    pub fn push_context_property_id(&mut self, tag_number: u8, property_id: &PropertyId) {
+        encode_context_enumerated(self, tag_number, property_id);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -46,8 +156,10 @@ impl Reader {
         self.end = len;
     }
 
+    // the read functions below check against the end of buf as well as self.end
+    // so that a truncated buffer (e.g. when using Reader::default) is an error rather than a panic
     pub fn read_byte(&mut self, buf: &[u8]) -> Result<u8, Error> {
-        if self.eof() {
+        if self.eof() || self.index >= buf.len() {
             Err(Error::ReaderEof(self.end))
         } else {
             let byte = buf[self.index];
@@ -57,8 +169,8 @@ impl Reader {
     }
 
     pub fn read_bytes<const COUNT: usize>(&mut self, buf: &[u8]) -> Result<[u8; COUNT], Error> {
-        if self.index + COUNT > self.end {
-            Err(Error::ReaderEof(self.index + COUNT))
+        if self.index.saturating_add(COUNT) > self.end.min(buf.len()) {
+            Err(Error::ReaderEof(self.index.saturating_add(COUNT)))
         } else {
             let mut tmp: [u8; COUNT] = [0; COUNT];
             tmp.copy_from_slice(&buf[self.index..self.index + COUNT]);
@@ -68,14 +180,81 @@ impl Reader {
     }
 
     pub fn read_slice<'a>(&mut self, len: usize, buf: &'a [u8]) -> Result<&'a [u8], Error> {
-        if self.index + len > self.end {
-            Err(Error::ReaderEof(self.index + len))
+        // saturating because len comes off the wire and can be anything up to u32::MAX (e.g. on 32 bit targets)
+        if self.index.saturating_add(len) > self.end.min(buf.len()) {
+            Err(Error::ReaderEof(self.index.saturating_add(len)))
         } else {
             let slice = &buf[self.index..self.index + len];
             self.index += len;
             Ok(slice)
         }
     }
+
+    // the rest of buf up to the end of the reader without moving the reader
+    // empty rather than a panic when the reader is already past the end of buf
+    pub fn remaining<'a>(&self, buf: &'a [u8]) -> &'a [u8] {
+        let end = self.end.min(buf.len());
+        buf.get(self.index..end).unwrap_or(&[])
+    }
+
+    // reads the tag of a CHOICE and returns its context tag number so the caller can match on it
+    // the tag is returned too: a primitive choice still has its value to read and an opening tag choice its body
+    pub fn decode_choice(&mut self, buf: &[u8]) -> Result<(u8, Tag), Error> {
+        let tag = Tag::decode(self, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(x) | TagNumber::ContextSpecificOpening(x) => Ok((x, tag)),
+            number => Err(Error::TagNotSupported((
+                "Reader decode_choice expected context tag",
+                number,
+            ))),
+        }
+    }
+
+    // reads a LIST OF / SEQUENCE OF wrapped in the opening and closing context tag passed in
+    // the reader is moved past the closing tag and each item is decoded lazily by the iterator
+    // nested opening and closing tags with the same tag number are skipped over
+    pub fn list_of<'a, T, F>(
+        &mut self,
+        tag_number: u8,
+        buf: &'a [u8],
+        decode: F,
+    ) -> Result<ListOfIter<'a, F>, Error>
+    where
+        F: FnMut(&mut Reader, &'a [u8]) -> Result<T, Error>,
+    {
+        let buf = get_tagged_body_for_tag(self, buf, tag_number, "Reader list_of")?;
+        Ok(ListOfIter {
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+            decode,
+        })
+    }
+}
+
+pub struct ListOfIter<'a, F> {
+    reader: Reader,
+    buf: &'a [u8],
+    decode: F,
+}
+
+impl<'a, T, F> Iterator for ListOfIter<'a, F>
+where
+    F: FnMut(&mut Reader, &'a [u8]) -> Result<T, Error>,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        let item = (self.decode)(&mut self.reader, self.buf);
+        if item.is_err() {
+            // stop rather than trying to decode the rest of a corrupt list
+            self.reader.index = self.reader.end;
+        }
+        Some(item)
+    }
 }
 
 impl Default for Reader {
@@ -86,3 +265,168 @@ impl Default for Reader {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        error::Error,
+        helper::{
+            decode_application_unsigned, encode_application_unsigned, encode_closing_tag,
+            encode_context_unsigned, encode_opening_tag, get_tagged_body_for_tag,
+        },
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    };
+
+    use super::{Reader, Writer};
+
+    #[test]
+    fn backpatch_len_at_length_boundaries() {
+        let mut buf = [0; 70_000];
+        let mut expected = [0; 70_000];
+        for len in [0, 4, 5, 253, 254, 65535, 65536] {
+            let contents: [u8; 3] = [0xAA, 0xBB, 0xCC];
+            let number = TagNumber::Application(ApplicationTagNumber::OctetString);
+
+            let mut writer = Writer::new(&mut buf);
+            writer.push(0x01); // something before the tag
+            let handle = writer.reserve_len(number.clone());
+            for i in 0..len {
+                writer.push(contents[i % 3]);
+            }
+            writer.backpatch_len(handle);
+            let patched_len = writer.index;
+
+            let mut writer = Writer::new(&mut expected);
+            writer.push(0x01);
+            Tag::new(number.clone(), len as u32).encode(&mut writer);
+            for i in 0..len {
+                writer.push(contents[i % 3]);
+            }
+            let expected_len = writer.index;
+
+            assert_eq!(patched_len, expected_len, "length {}", len);
+            assert_eq!(
+                buf[..patched_len],
+                expected[..expected_len],
+                "length {}",
+                len
+            );
+
+            let measured = Writer::measure(|writer| {
+                let handle = writer.reserve_len(number);
+                writer.index += len;
+                writer.backpatch_len(handle);
+            });
+            assert_eq!(measured, expected_len - 1, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn measure_only_writer() {
+        let mut writer = Writer::new_measure();
+        encode_application_unsigned(&mut writer, 300);
+        assert!(writer.is_measure_only());
+        assert_eq!(writer.index, 3);
+        assert!(writer.to_bytes().is_empty());
+    }
+
+    #[test]
+    fn read_past_the_end() {
+        let buf = [0x01, 0x02, 0x03];
+        let mut reader = Reader {
+            index: 1,
+            ..Default::default()
+        };
+        assert_eq!(reader.remaining(&buf), &[0x02, 0x03]);
+
+        // lengths off the wire can be anything
+        assert!(reader.read_slice(usize::MAX, &buf).is_err());
+        assert_eq!(reader.index, 1);
+
+        reader.index = 5;
+        assert!(reader.remaining(&buf).is_empty());
+        assert!(reader.read_byte(&buf).is_err());
+    }
+
+    #[test]
+    fn reset_reuses_buffer() {
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        encode_application_unsigned(&mut writer, 300);
+        assert_eq!(writer.to_bytes(), &[0x22, 0x01, 0x2C]);
+
+        writer.reset();
+        assert!(writer.to_bytes().is_empty());
+        encode_application_unsigned(&mut writer, 5);
+        assert_eq!(writer.to_bytes(), &[0x21, 0x05]);
+
+        // only the cursor is reset
+        assert_eq!(buf[2], 0x2C);
+    }
+
+    #[test]
+    fn decode_choice() {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        encode_context_unsigned(&mut writer, 1, 300);
+        encode_opening_tag(&mut writer, 2);
+        encode_closing_tag(&mut writer, 2);
+        encode_application_unsigned(&mut writer, 5);
+        let encoded = writer.to_bytes();
+        let mut reader = Reader::new_with_len(encoded.len());
+
+        // primitive choice
+        let (choice, tag) = reader.decode_choice(encoded).unwrap();
+        assert_eq!(choice, 1);
+        assert_eq!(tag.value, 2);
+        reader.index += tag.value as usize;
+
+        // constructed choice
+        let (choice, tag) = reader.decode_choice(encoded).unwrap();
+        assert_eq!(choice, 2);
+        assert_eq!(tag.number, TagNumber::ContextSpecificOpening(2));
+
+        // a closing or application tag can't start a choice
+        assert!(matches!(
+            reader.decode_choice(encoded),
+            Err(Error::TagNotSupported(_))
+        ));
+        assert!(matches!(
+            reader.decode_choice(encoded),
+            Err(Error::TagNotSupported(_))
+        ));
+    }
+
+    #[test]
+    fn list_of_with_nested_tags() {
+        // each item is itself wrapped in the same tag number as the list
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        encode_opening_tag(&mut writer, 1);
+        for value in [5, 300, 7] {
+            encode_opening_tag(&mut writer, 1);
+            encode_application_unsigned(&mut writer, value);
+            encode_closing_tag(&mut writer, 1);
+        }
+        encode_closing_tag(&mut writer, 1);
+        encode_application_unsigned(&mut writer, 99);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let values = reader
+            .list_of(1, encoded, |reader, buf| {
+                let body = get_tagged_body_for_tag(reader, buf, 1, "test item")?;
+                decode_application_unsigned(&mut Reader::new_with_len(body.len()), body, "test")
+            })
+            .unwrap();
+        let mut values = values.map(|x| x.unwrap());
+        assert_eq!(values.next(), Some(5));
+        assert_eq!(values.next(), Some(300));
+        assert_eq!(values.next(), Some(7));
+        assert_eq!(values.next(), None);
+
+        // the reader is left just after the closing tag of the list
+        let trailing = decode_application_unsigned(&mut reader, encoded, "test").unwrap();
+        assert_eq!(trailing, 99);
+    }
+}
@@ -1,9 +1,19 @@
+pub mod action_list;
+pub mod calendar;
+pub mod cov_subscription;
 pub mod daily_schedule;
+pub mod device_object_property_reference;
 pub mod error;
 pub(crate) mod helper;
 pub mod io;
+pub mod notification_parameters;
 pub mod object_id;
+pub mod object_property_reference;
 pub mod property_id;
+pub mod property_states;
+pub mod recipient;
+pub mod scale;
 pub mod spec;
 pub mod tag;
+pub mod time_stamp;
 pub mod time_value;
@@ -0,0 +1,308 @@
+use crate::application_protocol::primitives::data_value::CustomBitStream;
+
+use super::{
+    error::Error,
+    helper::{check_len, encode_closing_tag, encode_opening_tag, get_tagged_body},
+    io::{Reader, Writer},
+    spec::Status,
+    tag::{Tag, TagNumber},
+};
+
+// BACnetNotificationParameters - the event values of an event notification (why the event fired)
+// the choice tag number is the event type, event types that are not modeled yet keep the bytes between the tags
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NotificationParameters<'a> {
+    ChangeOfValue {
+        new_value: ChangedValue<'a>,
+        status_flags: Status,
+    },
+    OutOfRange {
+        exceeding_value: f32,
+        status_flags: Status,
+        deadband: f32,
+        exceeded_limit: f32,
+    },
+    Unknown {
+        event_type: u8,
+        body: &'a [u8],
+    },
+}
+
+// the new value of a change of value event
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChangedValue<'a> {
+    ChangedBits(CustomBitStream<'a>),
+    ChangedValue(f32),
+}
+
+impl<'a> NotificationParameters<'a> {
+    const CHANGE_OF_VALUE_TAG: u8 = 2;
+    const OUT_OF_RANGE_TAG: u8 = 5;
+
+    // change of value
+    const NEW_VALUE_TAG: u8 = 0;
+    const CHANGED_BITS_TAG: u8 = 0;
+    const CHANGED_VALUE_TAG: u8 = 1;
+    const COV_STATUS_FLAGS_TAG: u8 = 1;
+
+    // out of range
+    const EXCEEDING_VALUE_TAG: u8 = 0;
+    const STATUS_FLAGS_TAG: u8 = 1;
+    const DEADBAND_TAG: u8 = 2;
+    const EXCEEDED_LIMIT_TAG: u8 = 3;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::ChangeOfValue {
+                new_value,
+                status_flags,
+            } => {
+                encode_opening_tag(writer, Self::CHANGE_OF_VALUE_TAG);
+                encode_opening_tag(writer, Self::NEW_VALUE_TAG);
+                match new_value {
+                    ChangedValue::ChangedBits(bits) => {
+                        Tag::new(
+                            TagNumber::ContextSpecific(Self::CHANGED_BITS_TAG),
+                            bits.bits.len() as u32 + 1,
+                        )
+                        .encode(writer);
+                        writer.push(bits.unused_bits);
+                        writer.extend_from_slice(bits.bits);
+                    }
+                    ChangedValue::ChangedValue(x) => {
+                        encode_context_real(writer, Self::CHANGED_VALUE_TAG, *x)
+                    }
+                }
+                encode_closing_tag(writer, Self::NEW_VALUE_TAG);
+                encode_context_status(writer, Self::COV_STATUS_FLAGS_TAG, status_flags);
+                encode_closing_tag(writer, Self::CHANGE_OF_VALUE_TAG);
+            }
+            Self::OutOfRange {
+                exceeding_value,
+                status_flags,
+                deadband,
+                exceeded_limit,
+            } => {
+                encode_opening_tag(writer, Self::OUT_OF_RANGE_TAG);
+                encode_context_real(writer, Self::EXCEEDING_VALUE_TAG, *exceeding_value);
+                encode_context_status(writer, Self::STATUS_FLAGS_TAG, status_flags);
+                encode_context_real(writer, Self::DEADBAND_TAG, *deadband);
+                encode_context_real(writer, Self::EXCEEDED_LIMIT_TAG, *exceeded_limit);
+                encode_closing_tag(writer, Self::OUT_OF_RANGE_TAG);
+            }
+            Self::Unknown { event_type, body } => {
+                encode_opening_tag(writer, *event_type);
+                writer.extend_from_slice(body);
+                encode_closing_tag(writer, *event_type);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (body, event_type) = get_tagged_body(reader, buf)?;
+        let mut reader = Reader::new_with_len(body.len());
+        let reader = &mut reader;
+
+        match event_type {
+            Self::CHANGE_OF_VALUE_TAG => {
+                Tag::decode_expected(
+                    reader,
+                    body,
+                    TagNumber::ContextSpecificOpening(Self::NEW_VALUE_TAG),
+                    "NotificationParameters decode new_value",
+                )?;
+                let tag = Tag::decode(reader, body)?;
+                let new_value = match tag.number {
+                    TagNumber::ContextSpecific(Self::CHANGED_BITS_TAG) => {
+                        if tag.value == 0 {
+                            return Err(Error::Length((
+                                "NotificationParameters decode changed_bits",
+                                tag.value,
+                            )));
+                        }
+                        let unused_bits = reader.read_byte(body)?;
+                        let bits = reader.read_slice(tag.value as usize - 1, body)?;
+                        ChangedValue::ChangedBits(CustomBitStream::new(unused_bits, bits))
+                    }
+                    TagNumber::ContextSpecific(Self::CHANGED_VALUE_TAG) => {
+                        check_len(tag.value, 4, "NotificationParameters decode changed_value")?;
+                        ChangedValue::ChangedValue(f32::from_be_bytes(reader.read_bytes(body)?))
+                    }
+                    number => {
+                        return Err(Error::TagNotSupported((
+                            "NotificationParameters decode new_value",
+                            number,
+                        )))
+                    }
+                };
+                Tag::decode_expected(
+                    reader,
+                    body,
+                    TagNumber::ContextSpecificClosing(Self::NEW_VALUE_TAG),
+                    "NotificationParameters decode new_value",
+                )?;
+                let status_flags = decode_context_status(
+                    reader,
+                    body,
+                    Self::COV_STATUS_FLAGS_TAG,
+                    "NotificationParameters decode change of value status_flags",
+                )?;
+                Ok(Self::ChangeOfValue {
+                    new_value,
+                    status_flags,
+                })
+            }
+            Self::OUT_OF_RANGE_TAG => {
+                let exceeding_value = decode_context_real(
+                    reader,
+                    body,
+                    Self::EXCEEDING_VALUE_TAG,
+                    "NotificationParameters decode exceeding_value",
+                )?;
+                let status_flags = decode_context_status(
+                    reader,
+                    body,
+                    Self::STATUS_FLAGS_TAG,
+                    "NotificationParameters decode out of range status_flags",
+                )?;
+                let deadband = decode_context_real(
+                    reader,
+                    body,
+                    Self::DEADBAND_TAG,
+                    "NotificationParameters decode deadband",
+                )?;
+                let exceeded_limit = decode_context_real(
+                    reader,
+                    body,
+                    Self::EXCEEDED_LIMIT_TAG,
+                    "NotificationParameters decode exceeded_limit",
+                )?;
+                Ok(Self::OutOfRange {
+                    exceeding_value,
+                    status_flags,
+                    deadband,
+                    exceeded_limit,
+                })
+            }
+            event_type => Ok(Self::Unknown { event_type, body }),
+        }
+    }
+}
+
+fn encode_context_real(writer: &mut Writer, tag_number: u8, value: f32) {
+    Tag::new(TagNumber::ContextSpecific(tag_number), 4).encode(writer);
+    writer.extend_from_slice(&value.to_be_bytes());
+}
+
+fn decode_context_real(
+    reader: &mut Reader,
+    buf: &[u8],
+    tag_number: u8,
+    context: &'static str,
+) -> Result<f32, Error> {
+    let tag = Tag::decode_expected(reader, buf, TagNumber::ContextSpecific(tag_number), context)?;
+    check_len(tag.value, 4, context)?;
+    Ok(f32::from_be_bytes(reader.read_bytes(buf)?))
+}
+
+fn encode_context_status(writer: &mut Writer, tag_number: u8, status: &Status) {
+    Tag::new(TagNumber::ContextSpecific(tag_number), 2).encode(writer);
+    writer.push(0); // no unused bits
+    writer.push(status.inner);
+}
+
+fn decode_context_status(
+    reader: &mut Reader,
+    buf: &[u8],
+    tag_number: u8,
+    context: &'static str,
+) -> Result<Status, Error> {
+    let tag = Tag::decode_expected(reader, buf, TagNumber::ContextSpecific(tag_number), context)?;
+    check_len(tag.value, 2, context)?;
+    let _unused_bits = reader.read_byte(buf)?;
+    Ok(Status::new(reader.read_byte(buf)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        spec::Status,
+    };
+
+    use super::{ChangedValue, NotificationParameters};
+
+    fn decode(input: &[u8]) -> NotificationParameters<'_> {
+        let mut reader = Reader::new_with_len(input.len());
+        NotificationParameters::decode(&mut reader, input).unwrap()
+    }
+
+    #[test]
+    fn out_of_range_high_limit() {
+        // 31.5 exceeded the high limit of 30.0 with a deadband of 1.0 and the in-alarm flag set
+        let input = [
+            0x5E, 0x0C, 0x41, 0xFC, 0x00, 0x00, 0x1A, 0x04, 0x80, 0x2C, 0x3F, 0x80, 0x00, 0x00,
+            0x3C, 0x41, 0xF0, 0x00, 0x00, 0x5F,
+        ];
+        let NotificationParameters::OutOfRange {
+            exceeding_value,
+            status_flags,
+            deadband,
+            exceeded_limit,
+        } = decode(&input)
+        else {
+            panic!("expected out of range");
+        };
+        assert_eq!(exceeding_value, 31.5);
+        assert!(status_flags.in_alarm());
+        assert!(!status_flags.fault());
+        assert_eq!(deadband, 1.0);
+        assert_eq!(exceeded_limit, 30.0);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        NotificationParameters::OutOfRange {
+            exceeding_value,
+            status_flags,
+            deadband,
+            exceeded_limit,
+        }
+        .encode(&mut writer);
+        // the only difference is the unused bits of the status flags
+        let mut expected = input;
+        expected[7] = 0;
+        assert_eq!(writer.to_bytes(), expected);
+    }
+
+    #[test]
+    fn change_of_value() {
+        let parameters = NotificationParameters::ChangeOfValue {
+            new_value: ChangedValue::ChangedValue(18.25),
+            status_flags: Status::new(0),
+        };
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        parameters.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        assert!(matches!(
+            decode(encoded),
+            NotificationParameters::ChangeOfValue {
+                new_value: ChangedValue::ChangedValue(x),
+                status_flags,
+            } if x == 18.25 && !status_flags.in_alarm()
+        ));
+
+        // event types that are not modeled yet are kept as is
+        let input = [0x0E, 0x09, 0x01, 0x0F];
+        assert!(matches!(
+            decode(&input),
+            NotificationParameters::Unknown {
+                event_type: 0,
+                body: [0x09, 0x01]
+            }
+        ));
+    }
+}
@@ -1,6 +1,8 @@
+use core::fmt::Display;
+
 use super::{
     error::Error,
-    helper::decode_unsigned,
+    helper::check_len,
     io::{Reader, Writer},
     spec::{BACNET_INSTANCE_BITS, BACNET_MAX_INSTANCE, BACNET_MAX_OBJECT},
 };
@@ -17,21 +19,38 @@ pub struct ObjectId {
 impl ObjectId {
     pub const LEN: u32 = 4; // 4 bytes
 
+    // the unspecified instance number (e.g. used by WhoHas or when reading the device object of an unknown device)
+    pub const WILDCARD_INSTANCE: u32 = BACNET_MAX_INSTANCE;
+
     pub fn new(object_type: ObjectType, id: u32) -> Self {
         Self { object_type, id }
     }
 
+    pub fn new_wildcard(object_type: ObjectType) -> Self {
+        Self {
+            object_type,
+            id: Self::WILDCARD_INSTANCE,
+        }
+    }
+
+    pub fn is_wildcard(&self) -> bool {
+        self.id == Self::WILDCARD_INSTANCE
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
-        let value = ((self.object_type as u32 & BACNET_MAX_OBJECT) << BACNET_INSTANCE_BITS)
+        let object_type: u32 = self.object_type.into();
+        let value = ((object_type & BACNET_MAX_OBJECT) << BACNET_INSTANCE_BITS)
             | (self.id & BACNET_MAX_INSTANCE);
         writer.extend_from_slice(&value.to_be_bytes());
     }
 
+    // unknown (e.g. vendor proprietary) object types do not fail decoding and keep their raw type number
+    // size is the length from the tag and anything other than 4 bytes is an error
     pub fn decode(size: u32, reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
-        let value = decode_unsigned(size, reader, buf)? as u32;
+        check_len(size, Self::LEN, "ObjectId decode")?;
+        let value = u32::from_be_bytes(reader.read_bytes(buf)?);
         let object_type = value >> BACNET_INSTANCE_BITS & BACNET_MAX_OBJECT;
-        let object_type = ObjectType::try_from(object_type)
-            .map_err(|x| Error::InvalidVariant(("ObjectId decode ObjectType", x)))?;
+        let object_type = ObjectType::from_raw(object_type as u16);
         let id = value & BACNET_MAX_INSTANCE;
         let object_id = ObjectId { object_type, id };
         Ok(object_id)
@@ -104,12 +123,166 @@ pub enum ObjectType {
     // Enumerated values 0-127 are reserved for definition by ASHRAE.
     // Enumerated values 128-1023 may be used by others subject to
     // the procedures and constraints described in Clause 23.
-    // do the max range inside of enum so that
-    // compilers will allocate adequate sized datatype for enum
-    // which is used to store decoding
-    Reserved = 57,
-    Proprietary = 128,
     Invalid = 1024,
+    // reserved or vendor proprietary object types (the raw type number is preserved)
+    Unknown(u16),
+}
+
+impl ObjectType {
+    // never fails because any type number not known to this library is kept as ObjectType::Unknown
+    pub fn from_raw(value: u16) -> Self {
+        match Self::try_from(value as u32) {
+            Ok(object_type) => object_type,
+            Err(_) => Self::Unknown(value),
+        }
+    }
+
+    // the canonical hyphenated name used by the standard (e.g. "analog-input")
+    // None for reserved and vendor proprietary types
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Self::ObjectAnalogInput => Some("analog-input"),
+            Self::ObjectAnalogOutput => Some("analog-output"),
+            Self::ObjectAnalogValue => Some("analog-value"),
+            Self::ObjectBinaryInput => Some("binary-input"),
+            Self::ObjectBinaryOutput => Some("binary-output"),
+            Self::ObjectBinaryValue => Some("binary-value"),
+            Self::ObjectCalendar => Some("calendar"),
+            Self::ObjectCommand => Some("command"),
+            Self::ObjectDevice => Some("device"),
+            Self::ObjectEventEnrollment => Some("event-enrollment"),
+            Self::ObjectFile => Some("file"),
+            Self::ObjectGroup => Some("group"),
+            Self::ObjectLoop => Some("loop"),
+            Self::ObjectMultiStateInput => Some("multi-state-input"),
+            Self::ObjectMultiStateOutput => Some("multi-state-output"),
+            Self::ObjectNotificationClass => Some("notification-class"),
+            Self::ObjectProgram => Some("program"),
+            Self::ObjectSchedule => Some("schedule"),
+            Self::ObjectAveraging => Some("averaging"),
+            Self::ObjectMultiStateValue => Some("multi-state-value"),
+            Self::ObjectTrendlog => Some("trend-log"),
+            Self::ObjectLifeSafetyPoint => Some("life-safety-point"),
+            Self::ObjectLifeSafetyZone => Some("life-safety-zone"),
+            Self::ObjectAccumulator => Some("accumulator"),
+            Self::ObjectPulseConverter => Some("pulse-converter"),
+            Self::ObjectEventLog => Some("event-log"),
+            Self::ObjectGlobalGroup => Some("global-group"),
+            Self::ObjectTrendLogMultiple => Some("trend-log-multiple"),
+            Self::ObjectLoadControl => Some("load-control"),
+            Self::ObjectStructuredView => Some("structured-view"),
+            Self::ObjectAccessDoor => Some("access-door"),
+            Self::ObjectTimer => Some("timer"),
+            Self::ObjectAccessCredential => Some("access-credential"),
+            Self::ObjectAccessPoint => Some("access-point"),
+            Self::ObjectAccessRights => Some("access-rights"),
+            Self::ObjectAccessUser => Some("access-user"),
+            Self::ObjectAccessZone => Some("access-zone"),
+            Self::ObjectCredentialDataInput => Some("credential-data-input"),
+            Self::ObjectNetworkSecurity => Some("network-security"),
+            Self::ObjectBitstringValue => Some("bitstring-value"),
+            Self::ObjectCharacterstringValue => Some("characterstring-value"),
+            Self::ObjectDatePatternValue => Some("datepattern-value"),
+            Self::ObjectDateValue => Some("date-value"),
+            Self::ObjectDatetimePatternValue => Some("datetimepattern-value"),
+            Self::ObjectDatetimeValue => Some("datetime-value"),
+            Self::ObjectIntegerValue => Some("integer-value"),
+            Self::ObjectLargeAnalogValue => Some("large-analog-value"),
+            Self::ObjectOctetstringValue => Some("octetstring-value"),
+            Self::ObjectPositiveIntegerValue => Some("positive-integer-value"),
+            Self::ObjectTimePatternValue => Some("timepattern-value"),
+            Self::ObjectTimeValue => Some("time-value"),
+            Self::ObjectNotificationForwarder => Some("notification-forwarder"),
+            Self::ObjectAlertEnrollment => Some("alert-enrollment"),
+            Self::ObjectChannel => Some("channel"),
+            Self::ObjectLightingOutput => Some("lighting-output"),
+            Self::ObjectBinaryLightingOutput => Some("binary-lighting-output"),
+            Self::ObjectNetworkPort => Some("network-port"),
+            Self::Invalid | Self::Unknown(_) => None,
+        }
+    }
+}
+
+// e.g. "analog-input:3" or "130:5" for a vendor proprietary type
+impl Display for ObjectId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}", self.object_type, self.id)
+    }
+}
+
+impl Display for ObjectType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "{}", u32::from(*self)),
+        }
+    }
+}
+
+impl From<ObjectType> for u32 {
+    fn from(value: ObjectType) -> Self {
+        match value {
+            ObjectType::ObjectAnalogInput => 0,
+            ObjectType::ObjectAnalogOutput => 1,
+            ObjectType::ObjectAnalogValue => 2,
+            ObjectType::ObjectBinaryInput => 3,
+            ObjectType::ObjectBinaryOutput => 4,
+            ObjectType::ObjectBinaryValue => 5,
+            ObjectType::ObjectCalendar => 6,
+            ObjectType::ObjectCommand => 7,
+            ObjectType::ObjectDevice => 8,
+            ObjectType::ObjectEventEnrollment => 9,
+            ObjectType::ObjectFile => 10,
+            ObjectType::ObjectGroup => 11,
+            ObjectType::ObjectLoop => 12,
+            ObjectType::ObjectMultiStateInput => 13,
+            ObjectType::ObjectMultiStateOutput => 14,
+            ObjectType::ObjectNotificationClass => 15,
+            ObjectType::ObjectProgram => 16,
+            ObjectType::ObjectSchedule => 17,
+            ObjectType::ObjectAveraging => 18,
+            ObjectType::ObjectMultiStateValue => 19,
+            ObjectType::ObjectTrendlog => 20,
+            ObjectType::ObjectLifeSafetyPoint => 21,
+            ObjectType::ObjectLifeSafetyZone => 22,
+            ObjectType::ObjectAccumulator => 23,
+            ObjectType::ObjectPulseConverter => 24,
+            ObjectType::ObjectEventLog => 25,
+            ObjectType::ObjectGlobalGroup => 26,
+            ObjectType::ObjectTrendLogMultiple => 27,
+            ObjectType::ObjectLoadControl => 28,
+            ObjectType::ObjectStructuredView => 29,
+            ObjectType::ObjectAccessDoor => 30,
+            ObjectType::ObjectTimer => 31,
+            ObjectType::ObjectAccessCredential => 32,
+            ObjectType::ObjectAccessPoint => 33,
+            ObjectType::ObjectAccessRights => 34,
+            ObjectType::ObjectAccessUser => 35,
+            ObjectType::ObjectAccessZone => 36,
+            ObjectType::ObjectCredentialDataInput => 37,
+            ObjectType::ObjectNetworkSecurity => 38,
+            ObjectType::ObjectBitstringValue => 39,
+            ObjectType::ObjectCharacterstringValue => 40,
+            ObjectType::ObjectDatePatternValue => 41,
+            ObjectType::ObjectDateValue => 42,
+            ObjectType::ObjectDatetimePatternValue => 43,
+            ObjectType::ObjectDatetimeValue => 44,
+            ObjectType::ObjectIntegerValue => 45,
+            ObjectType::ObjectLargeAnalogValue => 46,
+            ObjectType::ObjectOctetstringValue => 47,
+            ObjectType::ObjectPositiveIntegerValue => 48,
+            ObjectType::ObjectTimePatternValue => 49,
+            ObjectType::ObjectTimeValue => 50,
+            ObjectType::ObjectNotificationForwarder => 51,
+            ObjectType::ObjectAlertEnrollment => 52,
+            ObjectType::ObjectChannel => 53,
+            ObjectType::ObjectLightingOutput => 54,
+            ObjectType::ObjectBinaryLightingOutput => 55,
+            ObjectType::ObjectNetworkPort => 56,
+            ObjectType::Invalid => 1024,
+            ObjectType::Unknown(x) => x as u32,
+        }
+    }
 }
 
 impl TryFrom<u32> for ObjectType {
@@ -174,9 +347,100 @@ impl TryFrom<u32> for ObjectType {
             54 => Ok(Self::ObjectLightingOutput),
             55 => Ok(Self::ObjectBinaryLightingOutput),
             56 => Ok(Self::ObjectNetworkPort),
-            57..=127 => Ok(Self::Reserved),
-            128..=1023 => Ok(Self::Proprietary),
+            57..=1023 => Ok(Self::Unknown(value as u16)),
             x => Err(x),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+
+    use crate::common::{
+        error::Error,
+        io::{Reader, Writer},
+        tag::Tag,
+    };
+
+    use super::{ObjectId, ObjectType};
+
+    #[test]
+    fn wildcard_round_trip() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogValue, 0x3FFFFF);
+        assert!(object_id.is_wildcard());
+
+        let mut buf = [0; 4];
+        let mut writer = Writer::new(&mut buf);
+        object_id.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &[0x00, 0xBF, 0xFF, 0xFF]);
+
+        let mut reader = Reader::new_with_len(buf.len());
+        let decoded = ObjectId::decode(ObjectId::LEN, &mut reader, &buf).unwrap();
+        assert_eq!(
+            decoded,
+            ObjectId::new_wildcard(ObjectType::ObjectAnalogValue)
+        );
+        assert!(decoded.is_wildcard());
+    }
+
+    #[test]
+    fn proprietary_type_round_trip() {
+        // type 130 is in the vendor proprietary range
+        let buf = [0x20, 0x80, 0x00, 0x05];
+        let mut reader = Reader::new_with_len(buf.len());
+        let decoded = ObjectId::decode(ObjectId::LEN, &mut reader, &buf).unwrap();
+        assert_eq!(decoded.object_type, ObjectType::Unknown(130));
+        assert_eq!(decoded.id, 5);
+
+        let mut output = [0; 4];
+        let mut writer = Writer::new(&mut output);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &buf);
+    }
+
+    #[test]
+    fn display() {
+        let name = |object_type, id| format!("{}", ObjectId::new(object_type, id));
+        assert_eq!(name(ObjectType::ObjectAnalogInput, 3), "analog-input:3");
+        assert_eq!(
+            name(ObjectType::ObjectMultiStateValue, 12),
+            "multi-state-value:12"
+        );
+        assert_eq!(name(ObjectType::ObjectDevice, 389001), "device:389001");
+        assert_eq!(
+            name(ObjectType::ObjectTrendLogMultiple, 1),
+            "trend-log-multiple:1"
+        );
+        assert_eq!(name(ObjectType::ObjectNetworkPort, 0), "network-port:0");
+        assert_eq!(name(ObjectType::Unknown(130), 5), "130:5");
+    }
+
+    #[test]
+    fn truncated_object_id() {
+        // application tagged object id for device 1234
+        let input = [0xC4, 0x02, 0x00, 0x04, 0xD2];
+        let decode = |buf: &[u8]| {
+            // the default reader has no end so this checks that the end of buf is respected
+            let mut reader = Reader::default();
+            let tag = Tag::decode(&mut reader, buf)?;
+            ObjectId::decode(tag.value, &mut reader, buf)
+        };
+
+        for len in 0..input.len() {
+            assert!(decode(&input[..len]).is_err());
+        }
+        assert_eq!(
+            decode(&input).unwrap(),
+            ObjectId::new(ObjectType::ObjectDevice, 1234)
+        );
+
+        // a tag claiming fewer than 4 bytes
+        assert!(matches!(
+            decode(&[0xC3, 0x02, 0x00, 0x04]),
+            Err(Error::Length(_))
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use super::{
+    error::Error,
+    helper::{
+        decode_context_object_id, decode_context_property_id, decode_unsigned,
+        encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
+    },
+    io::{Reader, Writer},
+    object_id::ObjectId,
+    property_id::PropertyId,
+    tag::{Tag, TagNumber},
+};
+
+// BACnetObjectPropertyReference - a property of an object on the device being talked to
+// (e.g. the property monitored by an event enrollment), see DeviceObjectPropertyReference
+// for a reference that can point at an object on another device
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl ObjectPropertyReference {
+    const OBJECT_ID_TAG: u8 = 0;
+    const PROPERTY_ID_TAG: u8 = 1;
+    const ARRAY_INDEX_TAG: u8 = 2;
+
+    pub fn new(object_id: ObjectId, property_id: PropertyId, array_index: Option<u32>) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::OBJECT_ID_TAG, &self.object_id);
+        encode_context_enumerated(writer, Self::PROPERTY_ID_TAG, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::ARRAY_INDEX_TAG, array_index);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::OBJECT_ID_TAG,
+            "ObjectPropertyReference decode object_id",
+        )?;
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::PROPERTY_ID_TAG,
+            "ObjectPropertyReference decode property_id",
+        )?;
+
+        // array_index (optional)
+        let mut array_index = None;
+        if !reader.eof() {
+            let index = reader.index;
+            let tag = Tag::decode(reader, buf)?;
+            if let TagNumber::ContextSpecific(Self::ARRAY_INDEX_TAG) = tag.number {
+                array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+            } else {
+                // not ours, leave it for the caller
+                reader.index = index;
+            }
+        }
+
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        helper::encode_context_unsigned,
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+    };
+
+    use super::ObjectPropertyReference;
+
+    #[test]
+    fn round_trip() {
+        let with_index = ObjectPropertyReference::new(
+            ObjectId::new(ObjectType::ObjectMultiStateValue, 3),
+            PropertyId::PropStateText,
+            Some(2),
+        );
+        let without_index = ObjectPropertyReference::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            PropertyId::PropPresentValue,
+            None,
+        );
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        with_index.encode(&mut writer);
+        without_index.encode(&mut writer);
+        encode_context_unsigned(&mut writer, 3, 1); // the next field of the enclosing type
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = ObjectPropertyReference::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.object_id, with_index.object_id);
+        assert_eq!(decoded.property_id, PropertyId::PropStateText);
+        assert_eq!(decoded.array_index, Some(2));
+
+        // the tag after a reference without an array index is left for the caller
+        let decoded = ObjectPropertyReference::decode(&mut reader, encoded).unwrap();
+        assert_eq!(decoded.object_id, without_index.object_id);
+        assert_eq!(decoded.property_id, PropertyId::PropPresentValue);
+        assert_eq!(decoded.array_index, None);
+        assert_eq!(reader.index, encoded.len() - 2);
+    }
+}
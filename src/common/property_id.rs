@@ -1,3 +1,5 @@
+use super::object_id::ObjectType;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -948,3 +950,241 @@ impl From<u32> for PropertyId {
         }
     }
 }
+
+// The commonly supported standard properties of an object type. This is useful for building a
+// ReadPropertyMultiple request without using PropAll which some devices reject.
+// Optional properties (e.g. Description, Reliability, CovIncrement) are included where most devices
+// implement them so expect the odd property error in the response.
+// Object types without a curated list only return the properties that every object has.
+pub fn standard_properties(object_type: ObjectType) -> &'static [PropertyId] {
+    match object_type {
+        ObjectType::ObjectAnalogInput => ANALOG_INPUT_PROPERTIES,
+        ObjectType::ObjectAnalogOutput => ANALOG_OUTPUT_PROPERTIES,
+        ObjectType::ObjectAnalogValue => ANALOG_VALUE_PROPERTIES,
+        ObjectType::ObjectBinaryInput => BINARY_INPUT_PROPERTIES,
+        ObjectType::ObjectBinaryOutput => BINARY_OUTPUT_PROPERTIES,
+        ObjectType::ObjectBinaryValue => BINARY_VALUE_PROPERTIES,
+        ObjectType::ObjectMultiStateInput => MULTI_STATE_INPUT_PROPERTIES,
+        ObjectType::ObjectMultiStateOutput => MULTI_STATE_OUTPUT_PROPERTIES,
+        ObjectType::ObjectMultiStateValue => MULTI_STATE_VALUE_PROPERTIES,
+        ObjectType::ObjectDevice => DEVICE_PROPERTIES,
+        _ => BASIC_PROPERTIES,
+    }
+}
+
+const ANALOG_INPUT_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropPresentValue,
+    PropertyId::PropDescription,
+    PropertyId::PropStatusFlags,
+    PropertyId::PropEventState,
+    PropertyId::PropReliability,
+    PropertyId::PropOutOfService,
+    PropertyId::PropUnits,
+    PropertyId::PropCovIncrement,
+];
+
+const ANALOG_OUTPUT_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropPresentValue,
+    PropertyId::PropDescription,
+    PropertyId::PropStatusFlags,
+    PropertyId::PropEventState,
+    PropertyId::PropReliability,
+    PropertyId::PropOutOfService,
+    PropertyId::PropUnits,
+    PropertyId::PropPriorityArray,
+    PropertyId::PropRelinquishDefault,
+    PropertyId::PropCovIncrement,
+];
+
+const ANALOG_VALUE_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropPresentValue,
+    PropertyId::PropDescription,
+    PropertyId::PropStatusFlags,
+    PropertyId::PropEventState,
+    PropertyId::PropReliability,
+    PropertyId::PropOutOfService,
+    PropertyId::PropUnits,
+    PropertyId::PropCovIncrement,
+];
+
+const BINARY_INPUT_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropPresentValue,
+    PropertyId::PropDescription,
+    PropertyId::PropStatusFlags,
+    PropertyId::PropEventState,
+    PropertyId::PropReliability,
+    PropertyId::PropOutOfService,
+    PropertyId::PropPolarity,
+    PropertyId::PropActiveText,
+    PropertyId::PropInactiveText,
+];
+
+const BINARY_OUTPUT_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropPresentValue,
+    PropertyId::PropDescription,
+    PropertyId::PropStatusFlags,
+    PropertyId::PropEventState,
+    PropertyId::PropReliability,
+    PropertyId::PropOutOfService,
+    PropertyId::PropPolarity,
+    PropertyId::PropPriorityArray,
+    PropertyId::PropRelinquishDefault,
+    PropertyId::PropActiveText,
+    PropertyId::PropInactiveText,
+];
+
+const BINARY_VALUE_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropPresentValue,
+    PropertyId::PropDescription,
+    PropertyId::PropStatusFlags,
+    PropertyId::PropEventState,
+    PropertyId::PropReliability,
+    PropertyId::PropOutOfService,
+    PropertyId::PropActiveText,
+    PropertyId::PropInactiveText,
+];
+
+const MULTI_STATE_INPUT_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropPresentValue,
+    PropertyId::PropDescription,
+    PropertyId::PropStatusFlags,
+    PropertyId::PropEventState,
+    PropertyId::PropReliability,
+    PropertyId::PropOutOfService,
+    PropertyId::PropNumberOfStates,
+    PropertyId::PropStateText,
+];
+
+const MULTI_STATE_OUTPUT_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropPresentValue,
+    PropertyId::PropDescription,
+    PropertyId::PropStatusFlags,
+    PropertyId::PropEventState,
+    PropertyId::PropReliability,
+    PropertyId::PropOutOfService,
+    PropertyId::PropNumberOfStates,
+    PropertyId::PropStateText,
+    PropertyId::PropPriorityArray,
+    PropertyId::PropRelinquishDefault,
+];
+
+const MULTI_STATE_VALUE_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropPresentValue,
+    PropertyId::PropDescription,
+    PropertyId::PropStatusFlags,
+    PropertyId::PropEventState,
+    PropertyId::PropReliability,
+    PropertyId::PropOutOfService,
+    PropertyId::PropNumberOfStates,
+    PropertyId::PropStateText,
+];
+
+// the object list is left out because it can be too big for a single response
+const DEVICE_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+    PropertyId::PropDescription,
+    PropertyId::PropSystemStatus,
+    PropertyId::PropVendorName,
+    PropertyId::PropVendorIdentifier,
+    PropertyId::PropModelName,
+    PropertyId::PropFirmwareRevision,
+    PropertyId::PropApplicationSoftwareVersion,
+    PropertyId::PropLocation,
+    PropertyId::PropProtocolVersion,
+    PropertyId::PropProtocolRevision,
+    PropertyId::PropMaxApduLengthAccepted,
+    PropertyId::PropSegmentationSupported,
+    PropertyId::PropApduTimeout,
+    PropertyId::PropNumberOfApduRetries,
+    PropertyId::PropDatabaseRevision,
+];
+
+// every object type has these
+const BASIC_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PropObjectIdentifier,
+    PropertyId::PropObjectName,
+    PropertyId::PropObjectType,
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::common::object_id::ObjectType;
+
+    use super::{standard_properties, PropertyId};
+
+    #[test]
+    fn standard_properties_start_with_required() {
+        let object_types = [
+            ObjectType::ObjectAnalogInput,
+            ObjectType::ObjectBinaryOutput,
+            ObjectType::ObjectMultiStateValue,
+            ObjectType::ObjectDevice,
+            ObjectType::ObjectSchedule,
+            ObjectType::Unknown(600),
+        ];
+        for object_type in object_types {
+            let properties = standard_properties(object_type);
+            assert_eq!(
+                &properties[..3],
+                &[
+                    PropertyId::PropObjectIdentifier,
+                    PropertyId::PropObjectName,
+                    PropertyId::PropObjectType,
+                ]
+            );
+
+            // no duplicates and never the special ids that some devices reject
+            for (i, property) in properties.iter().enumerate() {
+                assert!(!properties[i + 1..].contains(property));
+                assert!(!matches!(
+                    property,
+                    PropertyId::PropAll | PropertyId::PropRequired | PropertyId::PropOptional
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn standard_properties_commandable() {
+        let properties = standard_properties(ObjectType::ObjectAnalogOutput);
+        assert!(properties.contains(&PropertyId::PropPriorityArray));
+        assert!(properties.contains(&PropertyId::PropRelinquishDefault));
+        assert!(properties.contains(&PropertyId::PropUnits));
+
+        let properties = standard_properties(ObjectType::ObjectBinaryInput);
+        assert!(!properties.contains(&PropertyId::PropPriorityArray));
+        assert!(properties.contains(&PropertyId::PropActiveText));
+
+        let properties = standard_properties(ObjectType::ObjectDevice);
+        assert!(!properties.contains(&PropertyId::PropObjectList));
+    }
+}
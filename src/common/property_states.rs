@@ -0,0 +1,203 @@
+use super::{
+    error::Error,
+    helper::{decode_unsigned, encode_context_bool, encode_context_unsigned},
+    io::{Reader, Writer},
+    spec::{Binary, DeviceStatus, EngineeringUnits, EventState, Polarity, Reliability},
+    tag::TagNumber,
+};
+
+// BACnetPropertyStates - the value that triggered a change-of-state event (and the alarm values it is compared against)
+// choices that are not modeled yet are kept as their raw context tag number and value
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PropertyStates {
+    Boolean(bool),
+    BinaryValue(Binary),
+    EventType(u32),
+    Polarity(Polarity),
+    ProgramChange(u32),
+    ProgramState(u32),
+    ReasonForHalt(u32),
+    Reliability(Reliability),
+    State(EventState),
+    SystemStatus(DeviceStatus),
+    Units(EngineeringUnits),
+    UnsignedValue(u32),
+    LifeSafetyMode(u32),
+    LifeSafetyState(u32),
+    Unknown { tag_number: u8, value: u32 },
+}
+
+impl PropertyStates {
+    const BOOLEAN_TAG: u8 = 0;
+    const BINARY_VALUE_TAG: u8 = 1;
+    const EVENT_TYPE_TAG: u8 = 2;
+    const POLARITY_TAG: u8 = 3;
+    const PROGRAM_CHANGE_TAG: u8 = 4;
+    const PROGRAM_STATE_TAG: u8 = 5;
+    const REASON_FOR_HALT_TAG: u8 = 6;
+    const RELIABILITY_TAG: u8 = 7;
+    const STATE_TAG: u8 = 8;
+    const SYSTEM_STATUS_TAG: u8 = 9;
+    const UNITS_TAG: u8 = 10;
+    const UNSIGNED_VALUE_TAG: u8 = 11;
+    const LIFE_SAFETY_MODE_TAG: u8 = 12;
+    const LIFE_SAFETY_STATE_TAG: u8 = 13;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        let (tag_number, value) = match self {
+            Self::Boolean(x) => {
+                encode_context_bool(writer, Self::BOOLEAN_TAG, *x);
+                return;
+            }
+            Self::BinaryValue(x) => (Self::BINARY_VALUE_TAG, x.clone() as u32),
+            Self::EventType(x) => (Self::EVENT_TYPE_TAG, *x),
+            Self::Polarity(x) => (Self::POLARITY_TAG, (*x).into()),
+            Self::ProgramChange(x) => (Self::PROGRAM_CHANGE_TAG, *x),
+            Self::ProgramState(x) => (Self::PROGRAM_STATE_TAG, *x),
+            Self::ReasonForHalt(x) => (Self::REASON_FOR_HALT_TAG, *x),
+            Self::Reliability(x) => (Self::RELIABILITY_TAG, (*x).into()),
+            Self::State(x) => (Self::STATE_TAG, x.clone() as u32),
+            Self::SystemStatus(x) => (Self::SYSTEM_STATUS_TAG, (*x).into()),
+            Self::Units(x) => (Self::UNITS_TAG, x.clone() as u32),
+            Self::UnsignedValue(x) => (Self::UNSIGNED_VALUE_TAG, *x),
+            Self::LifeSafetyMode(x) => (Self::LIFE_SAFETY_MODE_TAG, *x),
+            Self::LifeSafetyState(x) => (Self::LIFE_SAFETY_STATE_TAG, *x),
+            Self::Unknown { tag_number, value } => (*tag_number, *value),
+        };
+
+        // enumerations and unsigned values are encoded the same way
+        encode_context_unsigned(writer, tag_number, value);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let (tag_number, tag) = reader.decode_choice(buf)?;
+        if let TagNumber::ContextSpecificOpening(_) = tag.number {
+            return Err(Error::TagNotSupported((
+                "PropertyStates decode expected primitive context tag",
+                tag.number,
+            )));
+        }
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let unknown = Self::Unknown { tag_number, value };
+
+        let property_states = match tag_number {
+            Self::BOOLEAN_TAG => Self::Boolean(value > 0),
+            Self::BINARY_VALUE_TAG => match Binary::try_from(value) {
+                Ok(x) => Self::BinaryValue(x),
+                Err(_) => unknown,
+            },
+            Self::EVENT_TYPE_TAG => Self::EventType(value),
+            Self::POLARITY_TAG => match Polarity::try_from(value) {
+                Ok(x) => Self::Polarity(x),
+                Err(_) => unknown,
+            },
+            Self::PROGRAM_CHANGE_TAG => Self::ProgramChange(value),
+            Self::PROGRAM_STATE_TAG => Self::ProgramState(value),
+            Self::REASON_FOR_HALT_TAG => Self::ReasonForHalt(value),
+            Self::RELIABILITY_TAG => match Reliability::try_from(value) {
+                Ok(x) => Self::Reliability(x),
+                Err(_) => unknown,
+            },
+            Self::STATE_TAG => match EventState::try_from(value) {
+                Ok(x) => Self::State(x),
+                Err(_) => unknown,
+            },
+            Self::SYSTEM_STATUS_TAG => match DeviceStatus::try_from(value) {
+                Ok(x) => Self::SystemStatus(x),
+                Err(_) => unknown,
+            },
+            Self::UNITS_TAG => match EngineeringUnits::try_from(value) {
+                Ok(x) => Self::Units(x),
+                Err(_) => unknown,
+            },
+            Self::UNSIGNED_VALUE_TAG => Self::UnsignedValue(value),
+            Self::LIFE_SAFETY_MODE_TAG => Self::LifeSafetyMode(value),
+            Self::LIFE_SAFETY_STATE_TAG => Self::LifeSafetyState(value),
+            _ => unknown,
+        };
+
+        Ok(property_states)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{
+        io::{Reader, Writer},
+        spec::{Binary, DeviceStatus, Polarity, Reliability},
+    };
+
+    use super::PropertyStates;
+
+    fn decode(input: &[u8]) -> PropertyStates {
+        let mut reader = Reader::new_with_len(input.len());
+        PropertyStates::decode(&mut reader, input).unwrap()
+    }
+
+    #[test]
+    fn decode_choices() {
+        // binary-value [1] active
+        assert!(matches!(
+            decode(&[0x19, 0x01]),
+            PropertyStates::BinaryValue(Binary::On)
+        ));
+
+        // boolean-value [0] true
+        assert!(matches!(
+            decode(&[0x09, 0x01]),
+            PropertyStates::Boolean(true)
+        ));
+
+        // reliability [7] over-range
+        assert!(matches!(
+            decode(&[0x79, 0x02]),
+            PropertyStates::Reliability(Reliability::OverRange)
+        ));
+
+        // polarity [3] reverse
+        assert!(matches!(
+            decode(&[0x39, 0x01]),
+            PropertyStates::Polarity(Polarity::Reverse)
+        ));
+
+        // system-status [9] non-operational
+        assert!(matches!(
+            decode(&[0x99, 0x04]),
+            PropertyStates::SystemStatus(DeviceStatus::NonOperational)
+        ));
+
+        // door-alarm-state [15] is not modeled
+        assert!(matches!(
+            decode(&[0xF9, 0x0F, 0x02]),
+            PropertyStates::Unknown {
+                tag_number: 15,
+                value: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn unknown_round_trip() {
+        let value = PropertyStates::Unknown {
+            tag_number: 15,
+            value: 2,
+        };
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        value.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &[0xF9, 0x0F, 0x02]);
+    }
+
+    #[test]
+    fn typed_round_trip() {
+        for input in [[0x79, 0x02], [0x39, 0x01], [0x99, 0x04]] {
+            let value = decode(&input);
+            let mut buf = [0; 8];
+            let mut writer = Writer::new(&mut buf);
+            value.encode(&mut writer);
+            assert_eq!(writer.to_bytes(), &input);
+        }
+    }
+}
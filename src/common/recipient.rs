@@ -0,0 +1,575 @@
+use crate::{
+    application_protocol::primitives::data_value::Time,
+    network_protocol::network_pdu::{Addr, MacAddress},
+};
+
+use super::{
+    error::Error,
+    helper::{
+        decode_application_bit_string_byte, decode_unsigned, encode_application_bit_string_byte,
+        encode_application_object_id, encode_application_octet_string, encode_application_unsigned,
+        encode_closing_tag, encode_context_object_id, encode_context_unsigned, encode_opening_tag,
+    },
+    io::{Reader, Writer},
+    object_id::ObjectId,
+    spec::{DaysOfWeek, EventTransitionBits, BACNET_BROADCAST_NETWORK},
+    tag::{ApplicationTagNumber, Tag, TagNumber},
+};
+
+// BACnetRecipient - used in notification class recipient lists and event enrollment
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Recipient<'a> {
+    Device(ObjectId),
+    Address { network: u16, mac: &'a [u8] }, // an empty mac is a broadcast on that network
+}
+
+impl<'a> Recipient<'a> {
+    const DEVICE_TAG: u8 = 0;
+    const ADDRESS_TAG: u8 = 1;
+
+    // global broadcast (all networks)
+    pub fn broadcast() -> Self {
+        Self::Address {
+            network: BACNET_BROADCAST_NETWORK,
+            mac: &[],
+        }
+    }
+
+    pub fn is_broadcast(&self) -> bool {
+        match self {
+            Self::Address { network, mac } => {
+                *network == BACNET_BROADCAST_NETWORK || mac.is_empty()
+            }
+            Self::Device(_) => false,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Device(object_id) => {
+                encode_context_object_id(writer, Self::DEVICE_TAG, object_id);
+            }
+            Self::Address { network, mac } => {
+                encode_opening_tag(writer, Self::ADDRESS_TAG);
+                encode_address(writer, *network, mac);
+                encode_closing_tag(writer, Self::ADDRESS_TAG);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (choice, tag) = reader.decode_choice(buf)?;
+        match (choice, &tag.number) {
+            (Self::DEVICE_TAG, TagNumber::ContextSpecific(_)) => {
+                let object_id = ObjectId::decode(tag.value, reader, buf)?;
+                Ok(Self::Device(object_id))
+            }
+            (Self::ADDRESS_TAG, TagNumber::ContextSpecificOpening(_)) => {
+                let (network, mac) = decode_address(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::ADDRESS_TAG),
+                    "Recipient decode address closing tag",
+                )?;
+
+                Ok(Self::Address { network, mac })
+            }
+            _ => Err(Error::TagNotSupported((
+                "Recipient decode choice",
+                tag.number,
+            ))),
+        }
+    }
+}
+
+// BACnetAddress (application tagged network number and mac address)
+fn encode_address(writer: &mut Writer, network: u16, mac: &[u8]) {
+    encode_application_unsigned(writer, network as u64);
+    encode_application_octet_string(writer, mac);
+}
+
+fn decode_address<'a>(reader: &mut Reader, buf: &'a [u8]) -> Result<(u16, &'a [u8]), Error> {
+    // network number
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+        "Address decode network number",
+    )?;
+    let network = decode_unsigned(tag.value, reader, buf)?;
+    if network > u16::MAX as u64 {
+        return Err(Error::InvalidValue("Address network number out of range"));
+    }
+    let network = network as u16;
+
+    // mac address
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::OctetString),
+        "Address decode mac address",
+    )?;
+    let mac = reader.read_slice(tag.value as usize, buf)?;
+
+    Ok((network, mac))
+}
+
+// BACnetAddressBinding - an entry in the PropDeviceAddressBinding list of a device
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AddressBinding<'a> {
+    pub device_id: ObjectId,
+    pub network: u16,
+    pub mac: &'a [u8],
+}
+
+impl<'a> AddressBinding<'a> {
+    pub fn new(device_id: ObjectId, network: u16, mac: &'a [u8]) -> Self {
+        Self {
+            device_id,
+            network,
+            mac,
+        }
+    }
+
+    // the B/IP address of the device if it has one
+    pub fn addr(&self) -> Option<Addr> {
+        MacAddress::new(self.mac).ok()?.addr()
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.device_id);
+        encode_address(writer, self.network, self.mac);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "AddressBinding decode device_id",
+        )?;
+        let device_id = ObjectId::decode(tag.value, reader, buf)?;
+        let (network, mac) = decode_address(reader, buf)?;
+        Ok(Self::new(device_id, network, mac))
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AddressBindingList<'a> {
+    pub address_bindings: &'a [AddressBinding<'a>],
+    buf: &'a [u8],
+}
+
+impl<'a> AddressBindingList<'a> {
+    pub fn new(address_bindings: &'a [AddressBinding<'a>]) -> Self {
+        Self {
+            address_bindings,
+            buf: &[],
+        }
+    }
+
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self {
+            address_bindings: &[],
+            buf,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        for address_binding in self.address_bindings {
+            address_binding.encode(writer);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'_ AddressBindingList<'a> {
+    type Item = Result<AddressBinding<'a>, Error>;
+    type IntoIter = AddressBindingIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        AddressBindingIter {
+            reader: Reader::new_with_len(self.buf.len()),
+            buf: self.buf,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AddressBindingIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> Iterator for AddressBindingIter<'a> {
+    type Item = Result<AddressBinding<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(AddressBinding::decode(&mut self.reader, self.buf))
+    }
+}
+
+// BACnetRecipientProcess - used in cov subscriptions and event notification recipients
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecipientProcess<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub recipient: Recipient<'a>,
+    pub process_id: u32,
+}
+
+impl<'a> RecipientProcess<'a> {
+    const RECIPIENT_TAG: u8 = 0;
+    const PROCESS_ID_TAG: u8 = 1;
+
+    pub fn new(recipient: Recipient<'a>, process_id: u32) -> Self {
+        Self {
+            recipient,
+            process_id,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::RECIPIENT_TAG);
+        self.recipient.encode(writer);
+        encode_closing_tag(writer, Self::RECIPIENT_TAG);
+        encode_context_unsigned(writer, Self::PROCESS_ID_TAG, self.process_id);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificOpening(Self::RECIPIENT_TAG),
+            "RecipientProcess decode recipient opening tag",
+        )?;
+        let recipient = Recipient::decode(reader, buf)?;
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(Self::RECIPIENT_TAG),
+            "RecipientProcess decode recipient closing tag",
+        )?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::PROCESS_ID_TAG),
+            "RecipientProcess decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        Ok(Self {
+            recipient,
+            process_id,
+        })
+    }
+}
+
+// BACnetDestination - an entry in the PropRecipientList of a notification class
+// NOTE: all fields are application tagged except for the recipient which is a context tagged choice
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Destination<'a> {
+    pub valid_days: DaysOfWeek,
+    pub from_time: Time,
+    pub to_time: Time,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub recipient: Recipient<'a>,
+    pub process_id: u32,
+    pub issue_confirmed_notifications: bool,
+    pub transitions: EventTransitionBits,
+}
+
+impl<'a> Destination<'a> {
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_bit_string_byte(writer, DaysOfWeek::UNUSED_BITS, self.valid_days.inner);
+        Self::encode_time(writer, &self.from_time);
+        Self::encode_time(writer, &self.to_time);
+        self.recipient.encode(writer);
+        encode_application_unsigned(writer, self.process_id as u64);
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Boolean),
+            self.issue_confirmed_notifications as u32,
+        )
+        .encode(writer);
+        encode_application_bit_string_byte(
+            writer,
+            EventTransitionBits::UNUSED_BITS,
+            self.transitions.inner,
+        );
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let valid_days =
+            decode_application_bit_string_byte(reader, buf, "Destination decode valid_days")?;
+        let valid_days = DaysOfWeek::new(valid_days);
+        let from_time = Self::decode_time(reader, buf, "Destination decode from_time")?;
+        let to_time = Self::decode_time(reader, buf, "Destination decode to_time")?;
+        let recipient = Recipient::decode(reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+            "Destination decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Boolean),
+            "Destination decode issue_confirmed_notifications",
+        )?;
+        let issue_confirmed_notifications = tag.value > 0;
+
+        let transitions =
+            decode_application_bit_string_byte(reader, buf, "Destination decode transitions")?;
+        let transitions = EventTransitionBits::new(transitions);
+
+        Ok(Self {
+            valid_days,
+            from_time,
+            to_time,
+            recipient,
+            process_id,
+            issue_confirmed_notifications,
+            transitions,
+        })
+    }
+
+    fn encode_time(writer: &mut Writer, time: &Time) {
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Time),
+            Time::LEN,
+        )
+        .encode(writer);
+        time.encode(writer);
+    }
+
+    fn decode_time(reader: &mut Reader, buf: &[u8], context: &'static str) -> Result<Time, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Time),
+            context,
+        )?;
+        if tag.value != Time::LEN {
+            return Err(Error::Length((context, tag.value)));
+        }
+        Time::decode(reader, buf)
+    }
+}
+
+// the value of PropRecipientList
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecipientList<'a> {
+    pub destinations: &'a [Destination<'a>],
+    buf: &'a [u8],
+}
+
+impl<'a> RecipientList<'a> {
+    pub fn new(destinations: &'a [Destination<'a>]) -> Self {
+        Self {
+            destinations,
+            buf: &[],
+        }
+    }
+
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self {
+            destinations: &[],
+            buf,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        for destination in self.destinations {
+            destination.encode(writer);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'_ RecipientList<'a> {
+    type Item = Result<Destination<'a>, Error>;
+    type IntoIter = DestinationIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DestinationIter {
+            reader: Reader::new_with_len(self.buf.len()),
+            buf: self.buf,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DestinationIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> Iterator for DestinationIter<'a> {
+    type Item = Result<Destination<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(Destination::decode(&mut self.reader, self.buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::primitives::data_value::Time,
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::{DaysOfWeek, EventTransitionBits},
+        },
+    };
+
+    use super::{AddressBinding, AddressBindingList, Destination, Recipient};
+
+    #[test]
+    fn destination_round_trip() {
+        let destination = Destination {
+            valid_days: DaysOfWeek::new(DaysOfWeek::WEEKDAYS),
+            from_time: Time {
+                hour: 8,
+                minute: 0,
+                second: 0,
+                hundredths: 0,
+            },
+            to_time: Time {
+                hour: 17,
+                minute: 30,
+                second: 0,
+                hundredths: 0,
+            },
+            recipient: Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 1234)),
+            process_id: 7,
+            issue_confirmed_notifications: true,
+            transitions: EventTransitionBits::new(EventTransitionBits::ALL),
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        destination.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = Destination::decode(&mut reader, encoded).unwrap();
+        assert!(reader.eof());
+        assert!(decoded.valid_days.monday());
+        assert!(decoded.valid_days.friday());
+        assert!(!decoded.valid_days.saturday());
+        assert!(!decoded.valid_days.sunday());
+        assert_eq!(decoded.to_time.hour, 17);
+        assert_eq!(decoded.to_time.minute, 30);
+        assert_eq!(decoded.process_id, 7);
+        assert!(decoded.issue_confirmed_notifications);
+        assert!(decoded.transitions.to_fault());
+        match decoded.recipient {
+            Recipient::Device(x) => assert_eq!(x, ObjectId::new(ObjectType::ObjectDevice, 1234)),
+            x => panic!("unexpected recipient {:?}", x),
+        }
+
+        let mut buf2 = [0; 64];
+        let mut writer = Writer::new(&mut buf2);
+        decoded.encode(&mut writer);
+        assert_eq!(encoded, writer.to_bytes());
+    }
+
+    fn recipient_round_trip(recipient: &Recipient, expected: &[u8]) {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        recipient.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(encoded, expected);
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = Recipient::decode(&mut reader, encoded).unwrap();
+        assert!(reader.eof());
+        let mut buf2 = [0; 16];
+        let mut writer = Writer::new(&mut buf2);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), expected);
+    }
+
+    #[test]
+    fn recipient_address() {
+        // device 5 on network 5 behind a router
+        let routed = Recipient::Address {
+            network: 5,
+            mac: &[0x05],
+        };
+        assert!(!routed.is_broadcast());
+        recipient_round_trip(&routed, &[0x1E, 0x21, 0x05, 0x61, 0x05, 0x1F]);
+
+        // global broadcast
+        let broadcast = Recipient::broadcast();
+        assert!(broadcast.is_broadcast());
+        recipient_round_trip(&broadcast, &[0x1E, 0x22, 0xFF, 0xFF, 0x60, 0x1F]);
+
+        let mut reader = Reader::new_with_len(6);
+        let decoded =
+            Recipient::decode(&mut reader, &[0x1E, 0x22, 0xFF, 0xFF, 0x60, 0x1F]).unwrap();
+        assert!(matches!(
+            decoded,
+            Recipient::Address {
+                network: 0xFFFF,
+                mac: []
+            }
+        ));
+    }
+
+    #[test]
+    fn address_binding_list_round_trip() {
+        let mac = [192, 168, 1, 20, 0xBA, 0xC0];
+        let bindings = [
+            AddressBinding::new(ObjectId::new(ObjectType::ObjectDevice, 1001), 0, &mac),
+            AddressBinding::new(ObjectId::new(ObjectType::ObjectDevice, 2002), 5, &[0x07]),
+        ];
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        AddressBindingList::new(&bindings).encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        let decoded = AddressBindingList::new_from_buf(encoded);
+        let mut iter = decoded.into_iter();
+
+        let first = iter.next().unwrap().unwrap();
+        assert_eq!(first.device_id.id, 1001);
+        let addr = first.addr().unwrap();
+        assert_eq!(addr.ipv4, [192, 168, 1, 20]);
+        assert_eq!(addr.port, 0xBAC0);
+
+        // an MS/TP device behind a router has no B/IP address
+        let second = iter.next().unwrap().unwrap();
+        assert_eq!(second.device_id.id, 2002);
+        assert_eq!(second.network, 5);
+        assert_eq!(second.mac, [0x07]);
+        assert!(second.addr().is_none());
+
+        assert!(iter.next().is_none());
+    }
+}
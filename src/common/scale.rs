@@ -0,0 +1,124 @@
+use super::{
+    error::Error,
+    helper::{check_len, decode_signed, encode_context_signed},
+    io::{Reader, Writer},
+    tag::{Tag, TagNumber},
+};
+
+// BACnetScale - the conversion factor of an Accumulator object (PropScale)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scale {
+    FloatScale(f32),
+    IntegerScale(i32),
+}
+
+impl Scale {
+    const FLOAT_SCALE_TAG: u8 = 0;
+    const INTEGER_SCALE_TAG: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::FloatScale(x) => {
+                Tag::new(TagNumber::ContextSpecific(Self::FLOAT_SCALE_TAG), 4).encode(writer);
+                writer.extend_from_slice(&x.to_be_bytes());
+            }
+            Self::IntegerScale(x) => {
+                encode_context_signed(writer, Self::INTEGER_SCALE_TAG, *x);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::FLOAT_SCALE_TAG) => {
+                check_len(tag.value, 4, "Scale decode float scale")?;
+                Ok(Self::FloatScale(f32::from_be_bytes(
+                    reader.read_bytes(buf)?,
+                )))
+            }
+            TagNumber::ContextSpecific(Self::INTEGER_SCALE_TAG) => {
+                let value = decode_signed(tag.value, reader, buf)?;
+                Ok(Self::IntegerScale(value))
+            }
+            number => Err(Error::TagNotSupported(("Scale decode", number))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::ApplicationDataValue,
+            services::read_property::{ReadPropertyAck, ReadPropertyValue},
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::Scale;
+
+    fn round_trip<'a>(ack: &ReadPropertyAck, buf: &'a mut [u8]) -> ReadPropertyAck<'a> {
+        let mut writer = Writer::new(buf);
+        ack.encode(&mut writer);
+        let len = writer.index;
+        let encoded = &buf[..len];
+
+        // skip the service choice
+        let mut reader = Reader::new_with_len(encoded.len());
+        reader.index = 1;
+        ReadPropertyAck::decode(&mut reader, encoded).unwrap()
+    }
+
+    #[test]
+    fn scale_ack() {
+        let object_id = ObjectId::new(ObjectType::ObjectAccumulator, 1);
+        for (scale, expected) in [
+            (Scale::FloatScale(0.25), 0.25),
+            (Scale::IntegerScale(-3), -3.0),
+        ] {
+            let ack = ReadPropertyAck {
+                object_id,
+                property_id: PropertyId::PropScale,
+                property_value: ReadPropertyValue::Scale(scale),
+            };
+            let mut buf = [0; 32];
+            let decoded = round_trip(&ack, &mut buf);
+            match decoded.property_value {
+                ReadPropertyValue::Scale(Scale::FloatScale(x)) => assert_eq!(x, expected),
+                ReadPropertyValue::Scale(Scale::IntegerScale(x)) => assert_eq!(x as f32, expected),
+                x => panic!("expected Scale: {:?}", x),
+            }
+        }
+    }
+
+    #[test]
+    fn loop_constants() {
+        let object_id = ObjectId::new(ObjectType::ObjectLoop, 2);
+        for (property_id, value) in [
+            (PropertyId::PropProportionalConstant, 1.5),
+            (PropertyId::PropIntegralConstant, 0.02),
+            (PropertyId::PropDerivativeConstant, 0.0),
+        ] {
+            let ack = ReadPropertyAck {
+                object_id,
+                property_id,
+                property_value: ReadPropertyValue::ApplicationDataValue(
+                    ApplicationDataValue::Real(value),
+                ),
+            };
+            let mut buf = [0; 32];
+            let decoded = round_trip(&ack, &mut buf);
+            assert!(matches!(
+                decoded.property_value,
+                ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Real(x)) if x == value
+            ));
+        }
+    }
+}
@@ -8,6 +8,7 @@ pub const BACNET_ARRAY_ALL: u32 = 0xFFFFFFFF;
 pub const BACNET_NO_PRIORITY: u32 = 0;
 pub const BACNET_MIN_PRIORITY: u32 = 1;
 pub const BACNET_MAX_PRIORITY: u32 = 16;
+pub const BACNET_BROADCAST_NETWORK: u16 = 0xFFFF;
 
 /*
 TODO: use derive_more when it reaches 1.0 (to automatically impl TryFrom for all enums)
@@ -110,6 +111,143 @@ impl TryFrom<u32> for ErrorClass {
     }
 }
 
+impl From<ErrorClass> for u32 {
+    fn from(value: ErrorClass) -> Self {
+        match value {
+            ErrorClass::Device => 0,
+            ErrorClass::Object => 1,
+            ErrorClass::Property => 2,
+            ErrorClass::Resources => 3,
+            ErrorClass::Security => 4,
+            ErrorClass::Services => 5,
+            ErrorClass::Vt => 6,
+            ErrorClass::Communication => 7,
+            ErrorClass::Proprietary(x) => x as u32,
+        }
+    }
+}
+
+// reason given in a Reject pdu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum RejectReason {
+    // 0 (other) and the unassigned codes up to 63 that are reserved by the standard
+    Other(u8),
+    BufferOverflow = 1,
+    InconsistentParameters = 2,
+    InvalidParameterDataType = 3,
+    InvalidTag = 4,
+    MissingRequiredParameter = 5,
+    ParameterOutOfRange = 6,
+    TooManyArguments = 7,
+    UndefinedEnumeration = 8,
+    UnrecognizedService = 9,
+    InvalidDataEncoding = 10,
+    // codes 64 and above
+    Proprietary(u8),
+}
+
+impl From<u8> for RejectReason {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::BufferOverflow,
+            2 => Self::InconsistentParameters,
+            3 => Self::InvalidParameterDataType,
+            4 => Self::InvalidTag,
+            5 => Self::MissingRequiredParameter,
+            6 => Self::ParameterOutOfRange,
+            7 => Self::TooManyArguments,
+            8 => Self::UndefinedEnumeration,
+            9 => Self::UnrecognizedService,
+            10 => Self::InvalidDataEncoding,
+            x if x > 63 => Self::Proprietary(x),
+            x => Self::Other(x),
+        }
+    }
+}
+
+impl From<RejectReason> for u8 {
+    fn from(value: RejectReason) -> Self {
+        match value {
+            RejectReason::BufferOverflow => 1,
+            RejectReason::InconsistentParameters => 2,
+            RejectReason::InvalidParameterDataType => 3,
+            RejectReason::InvalidTag => 4,
+            RejectReason::MissingRequiredParameter => 5,
+            RejectReason::ParameterOutOfRange => 6,
+            RejectReason::TooManyArguments => 7,
+            RejectReason::UndefinedEnumeration => 8,
+            RejectReason::UnrecognizedService => 9,
+            RejectReason::InvalidDataEncoding => 10,
+            RejectReason::Other(x) | RejectReason::Proprietary(x) => x,
+        }
+    }
+}
+
+// reason given in an Abort pdu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum AbortReason {
+    // 0 (other) and the unassigned codes up to 63 that are reserved by the standard
+    Other(u8),
+    BufferOverflow = 1,
+    InvalidApduInThisState = 2,
+    PreemptedByHigherPriorityTask = 3,
+    SegmentationNotSupported = 4,
+    SecurityError = 5,
+    InsufficientSecurity = 6,
+    WindowSizeOutOfRange = 7,
+    ApplicationExceededReplyTime = 8,
+    OutOfResources = 9,
+    TsmTimeout = 10,
+    ApduTooLong = 11,
+    // codes 64 and above
+    Proprietary(u8),
+}
+
+impl From<u8> for AbortReason {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::BufferOverflow,
+            2 => Self::InvalidApduInThisState,
+            3 => Self::PreemptedByHigherPriorityTask,
+            4 => Self::SegmentationNotSupported,
+            5 => Self::SecurityError,
+            6 => Self::InsufficientSecurity,
+            7 => Self::WindowSizeOutOfRange,
+            8 => Self::ApplicationExceededReplyTime,
+            9 => Self::OutOfResources,
+            10 => Self::TsmTimeout,
+            11 => Self::ApduTooLong,
+            x if x > 63 => Self::Proprietary(x),
+            x => Self::Other(x),
+        }
+    }
+}
+
+impl From<AbortReason> for u8 {
+    fn from(value: AbortReason) -> Self {
+        match value {
+            AbortReason::BufferOverflow => 1,
+            AbortReason::InvalidApduInThisState => 2,
+            AbortReason::PreemptedByHigherPriorityTask => 3,
+            AbortReason::SegmentationNotSupported => 4,
+            AbortReason::SecurityError => 5,
+            AbortReason::InsufficientSecurity => 6,
+            AbortReason::WindowSizeOutOfRange => 7,
+            AbortReason::ApplicationExceededReplyTime => 8,
+            AbortReason::OutOfResources => 9,
+            AbortReason::TsmTimeout => 10,
+            AbortReason::ApduTooLong => 11,
+            AbortReason::Other(x) | AbortReason::Proprietary(x) => x,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -564,6 +702,220 @@ impl TryFrom<u32> for ErrorCode {
     }
 }
 
+impl From<ErrorCode> for u32 {
+    fn from(value: ErrorCode) -> Self {
+        match value {
+            ErrorCode::Other => 0,
+            ErrorCode::DeviceBusy => 3,
+            ErrorCode::ConfigurationInProgress => 2,
+            ErrorCode::OperationalProblem => 25,
+            ErrorCode::DynamicCreationNotSupported => 4,
+            ErrorCode::NoObjectsOfSpecifiedType => 17,
+            ErrorCode::ObjectDeletionNotPermitted => 23,
+            ErrorCode::ObjectIdentifierAlreadyExists => 24,
+            ErrorCode::ReadAccessDenied => 27,
+            ErrorCode::UnknownObject => 31,
+            ErrorCode::UnsupportedObjectType => 36,
+            ErrorCode::CharacterSetNotSupported => 41,
+            ErrorCode::DatatypeNotSupported => 47,
+            ErrorCode::InconsistentSelectionCriterion => 8,
+            ErrorCode::InvalidArrayIndex => 42,
+            ErrorCode::InvalidDataType => 9,
+            ErrorCode::NotCovProperty => 44,
+            ErrorCode::OptionalFunctionalityNotSupported => 45,
+            ErrorCode::PropertyIsNotAnArray => 50,
+            ErrorCode::UnknownProperty => 32,
+            ErrorCode::ValueOutOfRange => 37,
+            ErrorCode::WriteAccessDenied => 40,
+            ErrorCode::NoSpaceForObject => 18,
+            ErrorCode::NoSpaceToAddListElement => 19,
+            ErrorCode::NoSpaceToWriteProperty => 20,
+            ErrorCode::AuthenticationFailed => 1,
+            ErrorCode::IncompatibleSecurityLevels => 6,
+            ErrorCode::InvalidOperatorName => 12,
+            ErrorCode::KeyGenerationError => 15,
+            ErrorCode::PasswordFailure => 26,
+            ErrorCode::SecurityNotSupported => 28,
+            ErrorCode::Timeout => 30,
+            ErrorCode::CovSubscriptionFailed => 43,
+            ErrorCode::DuplicateName => 48,
+            ErrorCode::DuplicateObjectId => 49,
+            ErrorCode::FileAccessDenied => 5,
+            ErrorCode::InconsistentParameters => 7,
+            ErrorCode::InvalidConfigurationData => 46,
+            ErrorCode::InvalidFileAccessMethod => 10,
+            ErrorCode::InvalidFileStartPosition => 11,
+            ErrorCode::InvalidParameterDataType => 13,
+            ErrorCode::InvalidTimeStamp => 14,
+            ErrorCode::MissingRequiredParameter => 16,
+            ErrorCode::PropertyIsNotAList => 22,
+            ErrorCode::ServiceRequestDenied => 29,
+            ErrorCode::UnknownVtClass => 34,
+            ErrorCode::UnknownVtSession => 35,
+            ErrorCode::NoVtSessionsAvailable => 21,
+            ErrorCode::VtSessionAlreadyClosed => 38,
+            ErrorCode::VtSessionTerminationFailure => 39,
+            ErrorCode::Reserved1 => 33,
+            ErrorCode::AbortBufferOverflow => 51,
+            ErrorCode::AbortInvalidApduInThisState => 52,
+            ErrorCode::AbortPreemptedByHigherPriorityTask => 53,
+            ErrorCode::AbortSegmentationNotSupported => 54,
+            ErrorCode::AbortProprietary => 55,
+            ErrorCode::AbortOther => 56,
+            ErrorCode::InvalidTag => 57,
+            ErrorCode::NetworkDown => 58,
+            ErrorCode::RejectBufferOverflow => 59,
+            ErrorCode::RejectInconsistentParameters => 60,
+            ErrorCode::RejectInvalidParameterDataType => 61,
+            ErrorCode::RejectInvalidTag => 62,
+            ErrorCode::RejectMissingRequiredParameter => 63,
+            ErrorCode::RejectParameterOutOfRange => 64,
+            ErrorCode::RejectTooManyArguments => 65,
+            ErrorCode::RejectUndefinedEnumeration => 66,
+            ErrorCode::RejectUnrecognizedService => 67,
+            ErrorCode::RejectProprietary => 68,
+            ErrorCode::RejectOther => 69,
+            ErrorCode::UnknownDevice => 70,
+            ErrorCode::UnknownRoute => 71,
+            ErrorCode::ValueNotInitialized => 72,
+            ErrorCode::InvalidEventState => 73,
+            ErrorCode::NoAlarmConfigured => 74,
+            ErrorCode::LogBufferFull => 75,
+            ErrorCode::LoggedValuePurged => 76,
+            ErrorCode::NoPropertySpecified => 77,
+            ErrorCode::NotConfiguredForTriggeredLogging => 78,
+            ErrorCode::UnknownSubscription => 79,
+            ErrorCode::ParameterOutOfRange => 80,
+            ErrorCode::ListElementNotFound => 81,
+            ErrorCode::Busy => 82,
+            ErrorCode::CommunicationDisabled => 83,
+            ErrorCode::Success => 84,
+            ErrorCode::AccessDenied => 85,
+            ErrorCode::BadDestinationAddress => 86,
+            ErrorCode::BadDestinationDeviceId => 87,
+            ErrorCode::BadSignature => 88,
+            ErrorCode::BadSourceAddress => 89,
+            ErrorCode::BadTimestamp => 90,
+            ErrorCode::CannotUseKey => 91,
+            ErrorCode::CannotVerifyMessageId => 92,
+            ErrorCode::CorrectKeyRevision => 93,
+            ErrorCode::DestinationDeviceIdRequired => 94,
+            ErrorCode::DuplicateMessage => 95,
+            ErrorCode::EncryptionNotConfigured => 96,
+            ErrorCode::EncryptionRequired => 97,
+            ErrorCode::IncorrectKey => 98,
+            ErrorCode::InvalidKeyData => 99,
+            ErrorCode::KeyUpdateInProgress => 100,
+            ErrorCode::MalformedMessage => 101,
+            ErrorCode::NotKeyServer => 102,
+            ErrorCode::SecurityNotConfigured => 103,
+            ErrorCode::SourceSecurityRequired => 104,
+            ErrorCode::TooManyKeys => 105,
+            ErrorCode::UnknownAuthenticationType => 106,
+            ErrorCode::UnknownKey => 107,
+            ErrorCode::UnknownKeyRevision => 108,
+            ErrorCode::UnknownSourceMessage => 109,
+            ErrorCode::NotRouterToDnet => 110,
+            ErrorCode::RouterBusy => 111,
+            ErrorCode::UnknownNetworkMessage => 112,
+            ErrorCode::MessageTooLong => 113,
+            ErrorCode::SecurityError => 114,
+            ErrorCode::AddressingError => 115,
+            ErrorCode::WriteBdtFailed => 116,
+            ErrorCode::ReadBdtFailed => 117,
+            ErrorCode::RegisterForeignDeviceFailed => 118,
+            ErrorCode::ReadFdtFailed => 119,
+            ErrorCode::DeleteFdtEntryFailed => 120,
+            ErrorCode::DistributeBroadcastFailed => 121,
+            ErrorCode::UnknownFileSize => 122,
+            ErrorCode::AbortApduTooLong => 123,
+            ErrorCode::AbortApplicationExceededReplyTime => 124,
+            ErrorCode::AbortOutOfResources => 125,
+            ErrorCode::AbortTsmTimeout => 126,
+            ErrorCode::AbortWindowSizeOutOfRange => 127,
+            ErrorCode::FileFull => 128,
+            ErrorCode::InconsistentConfiguration => 129,
+            ErrorCode::InconsistentObjectType => 130,
+            ErrorCode::InternalError => 131,
+            ErrorCode::NotConfigured => 132,
+            ErrorCode::OutOfMemory => 133,
+            ErrorCode::ValueTooLong => 134,
+            ErrorCode::AbortInsufficientSecurity => 135,
+            ErrorCode::AbortSecurityError => 136,
+            ErrorCode::DuplicateEntry => 137,
+            ErrorCode::InvalidValueInThisState => 138,
+            ErrorCode::InvalidOperationInThisState => 139,
+            ErrorCode::ListItemNotNumbered => 140,
+            ErrorCode::ListItemNotTimestamped => 141,
+            ErrorCode::InvalidDataEncoding => 142,
+            ErrorCode::BvlcFunctionUnknown => 143,
+            ErrorCode::BvlcProprietaryFunctionUnknown => 144,
+            ErrorCode::HeaderEncodingError => 145,
+            ErrorCode::HeaderNotUnderstood => 146,
+            ErrorCode::MessageIncomplete => 147,
+            ErrorCode::NotABacnetScHub => 148,
+            ErrorCode::PayloadExpected => 149,
+            ErrorCode::UnexpectedData => 150,
+            ErrorCode::NodeDuplicateVmac => 151,
+            ErrorCode::HttpUnexpectedResponseCode => 152,
+            ErrorCode::HttpNoUpgrade => 153,
+            ErrorCode::HttpResourceNotLocal => 154,
+            ErrorCode::HttpProxyAuthenticationFailed => 155,
+            ErrorCode::HttpResponseTimeout => 156,
+            ErrorCode::HttpResponseSyntaxError => 157,
+            ErrorCode::HttpResponseValueError => 158,
+            ErrorCode::HttpResponseMissingHeader => 159,
+            ErrorCode::HttpWebsocketHeaderError => 160,
+            ErrorCode::HttpUpgradeRequired => 161,
+            ErrorCode::HttpUpgradeError => 162,
+            ErrorCode::HttpTemporaryUnavailable => 163,
+            ErrorCode::HttpNotAServer => 164,
+            ErrorCode::HttpError => 165,
+            ErrorCode::WebsocketSchemeNotSupported => 166,
+            ErrorCode::WebsocketUnknownControlMessage => 167,
+            ErrorCode::WebsocketCloseError => 168,
+            ErrorCode::WebsocketClosedByPeer => 169,
+            ErrorCode::WebsocketEndpointLeaves => 170,
+            ErrorCode::WebsocketProtocolError => 171,
+            ErrorCode::WebsocketDataNotAccepted => 172,
+            ErrorCode::WebsocketClosedAbnormally => 173,
+            ErrorCode::WebsocketDataInconsistent => 174,
+            ErrorCode::WebsocketDataAgainstPolicy => 175,
+            ErrorCode::WebsocketFrameTooLong => 176,
+            ErrorCode::WebsocketExtensionMissing => 177,
+            ErrorCode::WebsocketRequestUnavailable => 178,
+            ErrorCode::WebsocketError => 179,
+            ErrorCode::TlsClientCertificateError => 180,
+            ErrorCode::TlsServerCertificateError => 181,
+            ErrorCode::TlsClientAuthenticationFailed => 182,
+            ErrorCode::TlsServerAuthenticationFailed => 183,
+            ErrorCode::TlsClientCertificateExpired => 184,
+            ErrorCode::TlsServerCertificateExpired => 185,
+            ErrorCode::TlsClientCertificateRevoked => 186,
+            ErrorCode::TlsServerCertificateRevoked => 187,
+            ErrorCode::TlsError => 188,
+            ErrorCode::DnsUnavailable => 189,
+            ErrorCode::DnsNameResolutionFailed => 190,
+            ErrorCode::DnsResolverFailure => 191,
+            ErrorCode::DnsError => 192,
+            ErrorCode::TcpConnectTimeout => 193,
+            ErrorCode::TcpConnectionRefused => 194,
+            ErrorCode::TcpClosedByLocal => 195,
+            ErrorCode::TcpClosedOther => 196,
+            ErrorCode::TcpError => 197,
+            ErrorCode::IpAddressNotReachable => 198,
+            ErrorCode::IpError => 199,
+            ErrorCode::CertificateExpired => 200,
+            ErrorCode::CertificateInvalid => 201,
+            ErrorCode::CertificateMalformed => 202,
+            ErrorCode::CertificateRevoked => 203,
+            ErrorCode::UnknownSecurityKey => 204,
+            ErrorCode::ReferencedPortInError => 205,
+            ErrorCode::Proprietary(x) => x as u32,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1219,6 +1571,132 @@ impl LogBufferResult {
     }
 }
 
+#[repr(u8)]
+pub enum LimitEnableFlags {
+    LowLimitEnable = 0b1000_0000,
+    HighLimitEnable = 0b0100_0000,
+}
+
+// a 2 bit bitstring
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitEnable {
+    pub inner: u8,
+}
+
+impl LimitEnable {
+    pub const UNUSED_BITS: u8 = 6;
+
+    pub fn new(inner: u8) -> Self {
+        Self { inner }
+    }
+
+    pub const fn low_limit_enable(&self) -> bool {
+        self.inner & LimitEnableFlags::LowLimitEnable as u8
+            == LimitEnableFlags::LowLimitEnable as u8
+    }
+
+    pub const fn high_limit_enable(&self) -> bool {
+        self.inner & LimitEnableFlags::HighLimitEnable as u8
+            == LimitEnableFlags::HighLimitEnable as u8
+    }
+}
+
+#[repr(u8)]
+pub enum DaysOfWeekFlags {
+    Monday = 0b1000_0000,
+    Tuesday = 0b0100_0000,
+    Wednesday = 0b0010_0000,
+    Thursday = 0b0001_0000,
+    Friday = 0b0000_1000,
+    Saturday = 0b0000_0100,
+    Sunday = 0b0000_0010,
+}
+
+// a 7 bit bitstring (monday is the most significant bit)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DaysOfWeek {
+    pub inner: u8,
+}
+
+impl DaysOfWeek {
+    pub const UNUSED_BITS: u8 = 1;
+    pub const ALL: u8 = 0b1111_1110;
+    pub const WEEKDAYS: u8 = 0b1111_1000;
+
+    pub fn new(inner: u8) -> Self {
+        Self { inner }
+    }
+
+    pub const fn monday(&self) -> bool {
+        self.inner & DaysOfWeekFlags::Monday as u8 == DaysOfWeekFlags::Monday as u8
+    }
+
+    pub const fn tuesday(&self) -> bool {
+        self.inner & DaysOfWeekFlags::Tuesday as u8 == DaysOfWeekFlags::Tuesday as u8
+    }
+
+    pub const fn wednesday(&self) -> bool {
+        self.inner & DaysOfWeekFlags::Wednesday as u8 == DaysOfWeekFlags::Wednesday as u8
+    }
+
+    pub const fn thursday(&self) -> bool {
+        self.inner & DaysOfWeekFlags::Thursday as u8 == DaysOfWeekFlags::Thursday as u8
+    }
+
+    pub const fn friday(&self) -> bool {
+        self.inner & DaysOfWeekFlags::Friday as u8 == DaysOfWeekFlags::Friday as u8
+    }
+
+    pub const fn saturday(&self) -> bool {
+        self.inner & DaysOfWeekFlags::Saturday as u8 == DaysOfWeekFlags::Saturday as u8
+    }
+
+    pub const fn sunday(&self) -> bool {
+        self.inner & DaysOfWeekFlags::Sunday as u8 == DaysOfWeekFlags::Sunday as u8
+    }
+}
+
+#[repr(u8)]
+pub enum EventTransitionFlags {
+    ToOffNormal = 0b1000_0000,
+    ToFault = 0b0100_0000,
+    ToNormal = 0b0010_0000,
+}
+
+// a 3 bit bitstring
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventTransitionBits {
+    pub inner: u8,
+}
+
+impl EventTransitionBits {
+    pub const UNUSED_BITS: u8 = 5;
+    pub const ALL: u8 = 0b1110_0000;
+
+    pub fn new(inner: u8) -> Self {
+        Self { inner }
+    }
+
+    pub const fn to_offnormal(&self) -> bool {
+        self.inner & EventTransitionFlags::ToOffNormal as u8
+            == EventTransitionFlags::ToOffNormal as u8
+    }
+
+    pub const fn to_fault(&self) -> bool {
+        self.inner & EventTransitionFlags::ToFault as u8 == EventTransitionFlags::ToFault as u8
+    }
+
+    pub const fn to_normal(&self) -> bool {
+        self.inner & EventTransitionFlags::ToNormal as u8 == EventTransitionFlags::ToNormal as u8
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1228,6 +1706,39 @@ pub enum LogStatus {
     LogInterrupted = 2,
 }
 
+// a 3 bit bitstring with the bits numbered by LogStatus
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogStatusBits {
+    pub inner: u8,
+}
+
+impl LogStatusBits {
+    pub const UNUSED_BITS: u8 = 5;
+
+    pub fn new(inner: u8) -> Self {
+        Self { inner }
+    }
+
+    pub const fn log_disabled(&self) -> bool {
+        self.bit(LogStatus::LogDisabled)
+    }
+
+    pub const fn buffer_purged(&self) -> bool {
+        self.bit(LogStatus::BufferPurged)
+    }
+
+    pub const fn log_interrupted(&self) -> bool {
+        self.bit(LogStatus::LogInterrupted)
+    }
+
+    const fn bit(&self, bit: LogStatus) -> bool {
+        let mask = 0x80 >> bit as u8;
+        self.inner & mask == mask
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1310,6 +1821,185 @@ impl TryFrom<u32> for LoggingType {
     }
 }
 
+// BACnetReliability - the reason an object is in fault
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Reliability {
+    NoFaultDetected,
+    NoSensor,
+    OverRange,
+    UnderRange,
+    OpenLoop,
+    ShortedLoop,
+    NoOutput,
+    UnreliableOther,
+    ProcessError,
+    MultiStateFault,
+    ConfigurationError,
+    CommunicationFailure,
+    MemberFault,
+    MonitoredObjectFault,
+    Tripped,
+    LampFailure,
+    ActivationFailure,
+    RenewDhcpFailure,
+    RenewFdRegistrationFailure,
+    RestartAutoNegotiationFailure,
+    RestartFailure,
+    ProprietaryCommandFailure,
+    FaultsListed,
+    ReferencedObjectFault,
+    // reserved (e.g. 11 which was removed from the standard) or vendor proprietary (64 to 65535)
+    Unknown(u32),
+}
+
+impl From<Reliability> for u32 {
+    fn from(value: Reliability) -> Self {
+        match value {
+            Reliability::NoFaultDetected => 0,
+            Reliability::NoSensor => 1,
+            Reliability::OverRange => 2,
+            Reliability::UnderRange => 3,
+            Reliability::OpenLoop => 4,
+            Reliability::ShortedLoop => 5,
+            Reliability::NoOutput => 6,
+            Reliability::UnreliableOther => 7,
+            Reliability::ProcessError => 8,
+            Reliability::MultiStateFault => 9,
+            Reliability::ConfigurationError => 10,
+            Reliability::CommunicationFailure => 12,
+            Reliability::MemberFault => 13,
+            Reliability::MonitoredObjectFault => 14,
+            Reliability::Tripped => 15,
+            Reliability::LampFailure => 16,
+            Reliability::ActivationFailure => 17,
+            Reliability::RenewDhcpFailure => 18,
+            Reliability::RenewFdRegistrationFailure => 19,
+            Reliability::RestartAutoNegotiationFailure => 20,
+            Reliability::RestartFailure => 21,
+            Reliability::ProprietaryCommandFailure => 22,
+            Reliability::FaultsListed => 23,
+            Reliability::ReferencedObjectFault => 24,
+            Reliability::Unknown(x) => x,
+        }
+    }
+}
+
+impl TryFrom<u32> for Reliability {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::NoFaultDetected),
+            1 => Ok(Self::NoSensor),
+            2 => Ok(Self::OverRange),
+            3 => Ok(Self::UnderRange),
+            4 => Ok(Self::OpenLoop),
+            5 => Ok(Self::ShortedLoop),
+            6 => Ok(Self::NoOutput),
+            7 => Ok(Self::UnreliableOther),
+            8 => Ok(Self::ProcessError),
+            9 => Ok(Self::MultiStateFault),
+            10 => Ok(Self::ConfigurationError),
+            12 => Ok(Self::CommunicationFailure),
+            13 => Ok(Self::MemberFault),
+            14 => Ok(Self::MonitoredObjectFault),
+            15 => Ok(Self::Tripped),
+            16 => Ok(Self::LampFailure),
+            17 => Ok(Self::ActivationFailure),
+            18 => Ok(Self::RenewDhcpFailure),
+            19 => Ok(Self::RenewFdRegistrationFailure),
+            20 => Ok(Self::RestartAutoNegotiationFailure),
+            21 => Ok(Self::RestartFailure),
+            22 => Ok(Self::ProprietaryCommandFailure),
+            23 => Ok(Self::FaultsListed),
+            24 => Ok(Self::ReferencedObjectFault),
+            11 | 25..=65535 => Ok(Self::Unknown(value)),
+            x => Err(x),
+        }
+    }
+}
+
+// BACnetPolarity - whether a binary object inverts its physical input or output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Polarity {
+    Normal,
+    Reverse,
+    Unknown(u32),
+}
+
+impl From<Polarity> for u32 {
+    fn from(value: Polarity) -> Self {
+        match value {
+            Polarity::Normal => 0,
+            Polarity::Reverse => 1,
+            Polarity::Unknown(x) => x,
+        }
+    }
+}
+
+impl TryFrom<u32> for Polarity {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Reverse),
+            2..=65535 => Ok(Self::Unknown(value)),
+            x => Err(x),
+        }
+    }
+}
+
+// BACnetDeviceStatus - the system status of a device object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviceStatus {
+    Operational,
+    OperationalReadOnly,
+    DownloadRequired,
+    DownloadInProgress,
+    NonOperational,
+    BackupInProgress,
+    // reserved or vendor proprietary (64 to 65535)
+    Unknown(u32),
+}
+
+impl From<DeviceStatus> for u32 {
+    fn from(value: DeviceStatus) -> Self {
+        match value {
+            DeviceStatus::Operational => 0,
+            DeviceStatus::OperationalReadOnly => 1,
+            DeviceStatus::DownloadRequired => 2,
+            DeviceStatus::DownloadInProgress => 3,
+            DeviceStatus::NonOperational => 4,
+            DeviceStatus::BackupInProgress => 5,
+            DeviceStatus::Unknown(x) => x,
+        }
+    }
+}
+
+impl TryFrom<u32> for DeviceStatus {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Operational),
+            1 => Ok(Self::OperationalReadOnly),
+            2 => Ok(Self::DownloadRequired),
+            3 => Ok(Self::DownloadInProgress),
+            4 => Ok(Self::NonOperational),
+            5 => Ok(Self::BackupInProgress),
+            6..=65535 => Ok(Self::Unknown(value)),
+            x => Err(x),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -140,14 +140,14 @@ impl Tag {
             if self.value <= 253 {
                 buf[len] = self.value as u8;
                 len += 1;
-            } else if self.value < u16::MAX as u32 {
-                buf[len] = self.value as u8;
+            } else if self.value <= u16::MAX as u32 {
+                buf[len] = 254; // tagged as u16
                 len += 1;
                 let tmp = u16::to_be_bytes(self.value as u16);
                 buf[len..len + tmp.len()].copy_from_slice(&tmp);
                 len += tmp.len();
             } else {
-                buf[len] = self.value as u8;
+                buf[len] = 255; // tagged as u32
                 len += 1;
                 let tmp = u32::to_be_bytes(self.value);
                 buf[len..len + tmp.len()].copy_from_slice(&tmp);
@@ -237,18 +237,18 @@ fn decode_tag_number(reader: &mut Reader, buf: &[u8]) -> Result<(TagNumber, u8),
 
     let value = if is_context_specific(byte0) {
         // context specific tag num
-        if is_extended_tag_number(byte0) {
-            let num = reader.read_byte(buf)?;
-            (TagNumber::ContextSpecific(num), byte0)
+        let num = if is_extended_tag_number(byte0) {
+            reader.read_byte(buf)?
         } else {
-            let num = byte0 >> 4;
-            if is_opening_tag(byte0) {
-                (TagNumber::ContextSpecificOpening(num), 0)
-            } else if is_closing_tag(byte0) {
-                (TagNumber::ContextSpecificClosing(num), 0)
-            } else {
-                (TagNumber::ContextSpecific(num), byte0)
-            }
+            byte0 >> 4
+        };
+
+        if is_opening_tag(byte0) {
+            (TagNumber::ContextSpecificOpening(num), 0)
+        } else if is_closing_tag(byte0) {
+            (TagNumber::ContextSpecificClosing(num), 0)
+        } else {
+            (TagNumber::ContextSpecific(num), byte0)
         }
     } else {
         // application tag num
@@ -278,3 +278,79 @@ fn is_opening_tag(byte0: u8) -> bool {
 fn is_closing_tag(byte0: u8) -> bool {
     byte0 & 0x07 == 0x07
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::io::{Reader, Writer};
+
+    use super::{ApplicationTagNumber, Tag, TagNumber};
+
+    fn round_trip(tag: Tag, buf: &mut [u8]) -> &[u8] {
+        let mut writer = Writer::new(buf);
+        tag.encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        let decoded = Tag::decode(&mut reader, buf).unwrap();
+        assert_eq!(decoded.number, tag.number);
+        assert_eq!(decoded.value, tag.value);
+        assert_eq!(reader.index, len);
+        &buf[..len]
+    }
+
+    #[test]
+    fn context_tag_numbers_and_lengths() {
+        let mut buf = [0; 10];
+        for number in 0..=20 {
+            for value in 0..=1000 {
+                round_trip(
+                    Tag::new(TagNumber::ContextSpecific(number), value),
+                    &mut buf,
+                );
+            }
+            round_trip(
+                Tag::new(TagNumber::ContextSpecificOpening(number), 0),
+                &mut buf,
+            );
+            round_trip(
+                Tag::new(TagNumber::ContextSpecificClosing(number), 0),
+                &mut buf,
+            );
+        }
+    }
+
+    #[test]
+    fn extended_encoding() {
+        let mut buf = [0; 10];
+
+        // context tag 3 with length 4 fits in one byte
+        let tag = Tag::new(TagNumber::ContextSpecific(3), 4);
+        assert_eq!(round_trip(tag, &mut buf), &[0x3C]);
+
+        // extended tag number
+        let tag = Tag::new(TagNumber::ContextSpecific(20), 2);
+        assert_eq!(round_trip(tag, &mut buf), &[0xFA, 20]);
+
+        // extended length
+        let tag = Tag::new(TagNumber::ContextSpecific(1), 253);
+        assert_eq!(round_trip(tag, &mut buf), &[0x1D, 253]);
+
+        // extended length tagged as u16
+        let tag = Tag::new(TagNumber::ContextSpecific(1), 1000);
+        assert_eq!(round_trip(tag, &mut buf), &[0x1D, 254, 0x03, 0xE8]);
+
+        // extended tag number and length tagged as u32
+        let tag = Tag::new(TagNumber::ContextSpecific(20), 70000);
+        assert_eq!(
+            round_trip(tag, &mut buf),
+            &[0xFD, 20, 255, 0x00, 0x01, 0x11, 0x70]
+        );
+
+        // a long character string
+        let tag = Tag::new(
+            TagNumber::Application(ApplicationTagNumber::CharacterString),
+            300,
+        );
+        assert_eq!(round_trip(tag, &mut buf), &[0x75, 254, 0x01, 0x2C]);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::application_protocol::primitives::data_value::{ApplicationDataValue, Date, Time};
+
+use super::{
+    error::Error,
+    helper::{
+        check_len, decode_unsigned, encode_closing_tag, encode_context_unsigned, encode_opening_tag,
+    },
+    io::{Reader, Writer},
+    tag::{ApplicationTagNumber, Tag, TagNumber},
+};
+
+// BACnetTimeStamp
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeStamp {
+    Time(Time),
+    SequenceNumber(u32),
+    DateTime { date: Date, time: Time },
+}
+
+impl TimeStamp {
+    const TIME_TAG: u8 = 0;
+    const SEQUENCE_NUMBER_TAG: u8 = 1;
+    const DATE_TIME_TAG: u8 = 2;
+
+    // devices use an all wildcard date and time for a transition that has never happened
+    pub fn unspecified() -> Self {
+        Self::DateTime {
+            date: Date::unspecified(),
+            time: Time::unspecified(),
+        }
+    }
+
+    pub fn is_unspecified(&self) -> bool {
+        match self {
+            Self::Time(time) => time.is_unspecified(),
+            Self::SequenceNumber(_) => false,
+            Self::DateTime { date, time } => date.is_unspecified() && time.is_unspecified(),
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Time(time) => {
+                Tag::new(TagNumber::ContextSpecific(Self::TIME_TAG), Time::LEN).encode(writer);
+                time.encode(writer);
+            }
+            Self::SequenceNumber(sequence_number) => {
+                encode_context_unsigned(writer, Self::SEQUENCE_NUMBER_TAG, *sequence_number);
+            }
+            Self::DateTime { date, time } => {
+                encode_opening_tag(writer, Self::DATE_TIME_TAG);
+                ApplicationDataValue::Date(date.clone()).encode(writer);
+                ApplicationDataValue::Time(time.clone()).encode(writer);
+                encode_closing_tag(writer, Self::DATE_TIME_TAG);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::TIME_TAG) => {
+                check_len(tag.value, Time::LEN, "TimeStamp decode time")?;
+                Ok(Self::Time(Time::decode(reader, buf)?))
+            }
+            TagNumber::ContextSpecific(Self::SEQUENCE_NUMBER_TAG) => {
+                let sequence_number = decode_unsigned(tag.value, reader, buf)? as u32;
+                Ok(Self::SequenceNumber(sequence_number))
+            }
+            TagNumber::ContextSpecificOpening(Self::DATE_TIME_TAG) => {
+                let tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::Application(ApplicationTagNumber::Date),
+                    "TimeStamp decode date",
+                )?;
+                check_len(tag.value, Date::LEN, "TimeStamp decode date")?;
+                let date = Date::decode(reader, buf)?;
+
+                let tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::Application(ApplicationTagNumber::Time),
+                    "TimeStamp decode time",
+                )?;
+                check_len(tag.value, Time::LEN, "TimeStamp decode time")?;
+                let time = Time::decode(reader, buf)?;
+
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::DATE_TIME_TAG),
+                    "TimeStamp decode date time",
+                )?;
+                Ok(Self::DateTime { date, time })
+            }
+            number => Err(Error::TagNotSupported(("TimeStamp decode", number))),
+        }
+    }
+}
+
+// the value of PropEventTimeStamps
+// the time of the last transition to each event state (an unspecified timestamp if it never happened)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventTimeStamps {
+    pub to_offnormal: TimeStamp,
+    pub to_fault: TimeStamp,
+    pub to_normal: TimeStamp,
+}
+
+impl EventTimeStamps {
+    pub fn new(to_offnormal: TimeStamp, to_fault: TimeStamp, to_normal: TimeStamp) -> Self {
+        Self {
+            to_offnormal,
+            to_fault,
+            to_normal,
+        }
+    }
+
+    // the array elements in order (without the surrounding property value tags)
+    pub fn encode(&self, writer: &mut Writer) {
+        self.to_offnormal.encode(writer);
+        self.to_fault.encode(writer);
+        self.to_normal.encode(writer);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let to_offnormal = TimeStamp::decode(reader, buf)?;
+        let to_fault = TimeStamp::decode(reader, buf)?;
+        let to_normal = TimeStamp::decode(reader, buf)?;
+        Ok(Self {
+            to_offnormal,
+            to_fault,
+            to_normal,
+        })
+    }
+
+    // the following return None if the transition has never happened
+    pub fn to_offnormal_opt(&self) -> Option<&TimeStamp> {
+        Self::specified(&self.to_offnormal)
+    }
+
+    pub fn to_fault_opt(&self) -> Option<&TimeStamp> {
+        Self::specified(&self.to_fault)
+    }
+
+    pub fn to_normal_opt(&self) -> Option<&TimeStamp> {
+        Self::specified(&self.to_normal)
+    }
+
+    fn specified(time_stamp: &TimeStamp) -> Option<&TimeStamp> {
+        if time_stamp.is_unspecified() {
+            None
+        } else {
+            Some(time_stamp)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::{Date, Time},
+            services::read_property::{ReadPropertyAck, ReadPropertyValue},
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{EventTimeStamps, TimeStamp};
+
+    #[test]
+    fn event_time_stamps_ack() {
+        let ack = ReadPropertyAck {
+            object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            property_id: PropertyId::PropEventTimeStamps,
+            property_value: ReadPropertyValue::EventTimeStamps(EventTimeStamps::new(
+                TimeStamp::DateTime {
+                    date: Date::new(2024, 3, 14, 4),
+                    time: Time::new(9, 26, 53, 0),
+                },
+                TimeStamp::unspecified(),
+                TimeStamp::SequenceNumber(42),
+            )),
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        // skip the service choice
+        let mut reader = Reader::new_with_len(encoded.len());
+        reader.index = 1;
+        let decoded = ReadPropertyAck::decode(&mut reader, encoded).unwrap();
+        let time_stamps = match decoded.property_value {
+            ReadPropertyValue::EventTimeStamps(x) => x,
+            x => panic!("expected EventTimeStamps: {:?}", x),
+        };
+
+        match time_stamps.to_offnormal_opt() {
+            Some(TimeStamp::DateTime { date, time }) => {
+                assert_eq!((date.year, date.month, date.day), (2024, 3, 14));
+                assert_eq!((time.hour, time.minute, time.second), (9, 26, 53));
+            }
+            x => panic!("expected a date time: {:?}", x),
+        }
+        assert!(time_stamps.to_fault.is_unspecified());
+        assert!(time_stamps.to_fault_opt().is_none());
+        assert!(matches!(
+            time_stamps.to_normal_opt(),
+            Some(TimeStamp::SequenceNumber(42))
+        ));
+    }
+}
@@ -1,12 +1,17 @@
 use crate::{
-    application_protocol::{application_pdu::ApplicationPdu, confirmed::ConfirmedRequest},
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedRequest},
+        primitives::data_value::{ApplicationDataValue, Enumerated},
+        services::read_property::{ReadPropertyAck, ReadPropertyValue},
+    },
     common::{
         error::Error,
         io::{Reader, Writer},
     },
 };
 
-use super::network_pdu::{MessagePriority, NetworkMessage, NetworkPdu};
+use super::network_pdu::{MessagePriority, NetworkPdu};
 
 // Bacnet Virtual Link Control
 #[derive(Debug, Clone)]
@@ -68,11 +73,62 @@ impl<'a> DataLink<'a> {
 
     pub fn new_confirmed_req(req: ConfirmedRequest<'a>) -> Self {
         let apdu = ApplicationPdu::ConfirmedRequest(req);
-        let message = NetworkMessage::Apdu(apdu);
-        let npdu = NetworkPdu::new(None, None, true, MessagePriority::Normal, message);
+        let npdu = NetworkPdu::new_apdu(None, None, MessagePriority::Normal, apdu);
         DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu))
     }
 
+    // None if there is no npdu or it holds a network layer message
+    pub fn apdu(&self) -> Option<&ApplicationPdu<'a>> {
+        self.npdu.as_ref().and_then(|npdu| npdu.apdu())
+    }
+
+    // the value of a ReadProperty ack
+    // None if this is not a ReadProperty ack or the property is a list or an array
+    pub fn get_read_property_value(&self) -> Option<&ApplicationDataValue<'a>> {
+        match self.apdu() {
+            Some(ApplicationPdu::ComplexAck(ComplexAck {
+                service:
+                    ComplexAckService::ReadProperty(ReadPropertyAck {
+                        property_value: ReadPropertyValue::ApplicationDataValue(value),
+                        ..
+                    }),
+                ..
+            })) => Some(value),
+            _ => None,
+        }
+    }
+
+    // the typed accessors below return None if the value is a different type
+
+    pub fn get_read_property_real(&self) -> Option<f32> {
+        match self.get_read_property_value()? {
+            ApplicationDataValue::Real(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn get_read_property_unsigned(&self) -> Option<u64> {
+        match self.get_read_property_value()? {
+            ApplicationDataValue::UnsignedInt(x) => Some(*x as u64),
+            ApplicationDataValue::BigUnsignedInt(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn get_read_property_bool(&self) -> Option<bool> {
+        match self.get_read_property_value()? {
+            ApplicationDataValue::Boolean(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn get_read_property_enumerated(&self) -> Option<&Enumerated> {
+        match self.get_read_property_value()? {
+            ApplicationDataValue::Enumerated(x) => Some(x),
+            _ => None,
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(BVLL_TYPE_BACNET_IP);
         writer.push(self.function.clone() as u8);
@@ -84,15 +140,32 @@ impl<'a> DataLink<'a> {
             }
             _ => todo!(),
         }
+
+        if !writer.is_measure_only() {
+            trace_frame("encoded", writer.to_bytes());
+        }
+    }
+
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
     }
 
     fn update_len(writer: &mut Writer) {
+        if writer.is_measure_only() {
+            return;
+        }
         let len = writer.index as u16;
         let src = len.to_be_bytes();
         writer.buf[2..4].copy_from_slice(&src);
     }
 
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        Self::decode_with_len(reader, buf).map(|(data_link, _)| data_link)
+    }
+
+    // also returns the number of bytes that make up the frame (the bvlc length)
+    // anything in buf after that is trailing data (e.g. padding or another frame)
+    pub fn decode_with_len(reader: &mut Reader, buf: &'a [u8]) -> Result<(Self, usize), Error> {
         let bvll_type = reader.read_byte(buf)?;
         if bvll_type != BVLL_TYPE_BACNET_IP {
             return Err(Error::InvalidValue("only BACNET_IP supported"));
@@ -110,7 +183,14 @@ impl<'a> DataLink<'a> {
                 len as u32,
             )));
         }
+        if (len as usize) < reader.index {
+            return Err(Error::Length((
+                "bvlc length is shorter than the bvlc header",
+                len as u32,
+            )));
+        }
         reader.set_len(len as usize);
+        trace_frame("decoded", &buf[..len as usize]);
 
         let npdu = match function {
             // see h_bbmd.c for all the types (only 2 are supported here)
@@ -120,6 +200,256 @@ impl<'a> DataLink<'a> {
             _ => None,
         };
 
-        Ok(Self { function, npdu })
+        Ok((Self { function, npdu }, len as usize))
+    }
+
+    // decodes every frame in buf for gateways that (non-standard) pack several frames into one datagram
+    // the iterator stops after the first error (e.g. an incomplete trailing frame)
+    pub fn decode_all(buf: &'a [u8]) -> DataLinkIter<'a> {
+        DataLinkIter { buf, index: 0 }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DataLinkIter<'a> {
+    buf: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Iterator for DataLinkIter<'a> {
+    type Item = Result<DataLink<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.buf.len() {
+            return None;
+        }
+
+        let buf = &self.buf[self.index..];
+        let mut reader = Reader::new_with_len(buf.len());
+        match DataLink::decode_with_len(&mut reader, buf) {
+            Ok((data_link, len)) => {
+                self.index += len;
+                Some(Ok(data_link))
+            }
+            Err(e) => {
+                self.index = self.buf.len();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+// logs the raw bytes of a frame when the log or defmt feature is enabled (does nothing otherwise)
+#[allow(unused_variables)]
+fn trace_frame(direction: &str, bytes: &[u8]) {
+    #[cfg(feature = "log")]
+    log::trace!("{} frame: {:02x?}", direction, bytes);
+
+    #[cfg(feature = "defmt")]
+    defmt::trace!("{=str} frame: {=[u8]:02x}", direction, bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{ComplexAck, ComplexAckService, ConfirmedRequest, ConfirmedRequestService},
+            primitives::data_value::{ApplicationDataValue, Enumerated},
+            services::{
+                read_property::{ReadPropertyAck, ReadPropertyValue},
+                read_property_multiple::{ReadAccessSpecification, ReadPropertyMultiple},
+            },
+        },
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::Binary,
+        },
+        network_protocol::network_pdu::{MessagePriority, MessageType, NetworkMessage, NetworkPdu},
+    };
+
+    use super::{DataLink, DataLinkFunction};
+
+    fn encode_read_property_multiple(invoke_id: u8, buf: &mut [u8]) -> &[u8] {
+        let property_ids = [PropertyId::PropPresentValue];
+        let objects = [ReadAccessSpecification::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, invoke_id as u32),
+            &property_ids,
+        )];
+        let service =
+            ConfirmedRequestService::ReadPropertyMultiple(ReadPropertyMultiple::new(&objects));
+        let data_link = DataLink::new_confirmed_req(ConfirmedRequest::new(invoke_id, service));
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+        let len = writer.index;
+        &buf[..len]
+    }
+
+    #[test]
+    fn encoded_len_matches_encode() {
+        let property_ids = [
+            PropertyId::PropObjectName,
+            PropertyId::PropPresentValue,
+            PropertyId::PropStatusFlags,
+        ];
+        let objects = [
+            ReadAccessSpecification::new(
+                ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                &property_ids,
+            ),
+            ReadAccessSpecification::new(
+                ObjectId::new(ObjectType::ObjectAnalogValue, 300000),
+                &property_ids,
+            ),
+        ];
+        let service =
+            ConfirmedRequestService::ReadPropertyMultiple(ReadPropertyMultiple::new(&objects));
+        let data_link = DataLink::new_confirmed_req(ConfirmedRequest::new(3, service));
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+
+        assert_eq!(data_link.encoded_len(), writer.to_bytes().len());
+    }
+
+    #[test]
+    fn decode_with_len_ignores_trailing_bytes() {
+        let property_ids = [PropertyId::PropPresentValue];
+        let objects = [ReadAccessSpecification::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            &property_ids,
+        )];
+        let service =
+            ConfirmedRequestService::ReadPropertyMultiple(ReadPropertyMultiple::new(&objects));
+        let data_link = DataLink::new_confirmed_req(ConfirmedRequest::new(1, service));
+
+        let mut buf = [0xAA; 64];
+        let mut writer = Writer::new(&mut buf);
+        data_link.encode(&mut writer);
+        let len = writer.index;
+
+        // frame followed by some garbage
+        let input = &buf[..len + 5];
+        let mut reader = Reader::default();
+        let (_, consumed) = DataLink::decode_with_len(&mut reader, input).unwrap();
+        assert_eq!(consumed, len);
+        assert_eq!(&input[consumed..], &[0xAA; 5]);
+    }
+
+    #[test]
+    fn decode_all_batched_frames() {
+        let mut datagram = [0; 128];
+        let mut len = 0;
+        for invoke_id in [1, 2] {
+            let mut buf = [0; 64];
+            let frame = encode_read_property_multiple(invoke_id, &mut buf);
+            datagram[len..len + frame.len()].copy_from_slice(frame);
+            len += frame.len();
+        }
+
+        let invoke_ids = |buf| {
+            DataLink::decode_all(buf).map(|x| {
+                x.map(|data_link| match data_link.npdu.unwrap().network_message {
+                    NetworkMessage::Apdu(apdu) => apdu.invoke_id().unwrap(),
+                    x => panic!("expected apdu: {:?}", x),
+                })
+            })
+        };
+
+        let mut iter = invoke_ids(&datagram[..len]);
+        assert!(matches!(iter.next(), Some(Ok(1))));
+        assert!(matches!(iter.next(), Some(Ok(2))));
+        assert!(iter.next().is_none());
+
+        // an incomplete trailing frame is an error and then the iterator stops
+        let mut iter = invoke_ids(&datagram[..len - 3]);
+        assert!(matches!(iter.next(), Some(Ok(1))));
+        assert!(matches!(iter.next(), Some(Err(Error::Length(_)))));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn apdu_accessor() {
+        let mut buf = [0; 64];
+        let frame = encode_read_property_multiple(7, &mut buf);
+        let mut reader = Reader::default();
+        let data_link = DataLink::decode(&mut reader, frame).unwrap();
+        assert_eq!(data_link.apdu().unwrap().invoke_id(), Some(7));
+
+        // network layer message (who-is-router-to-network)
+        let data_link = DataLink::new(
+            DataLinkFunction::OriginalBroadcastNpdu,
+            Some(NetworkPdu::new(
+                None,
+                None,
+                false,
+                MessagePriority::Normal,
+                NetworkMessage::MessageType(MessageType::WhoIsRouterToNetwork),
+            )),
+        );
+        assert!(data_link.npdu.as_ref().unwrap().apdu().is_none());
+        assert!(data_link.apdu().is_none());
+        assert!(DataLink::new(DataLinkFunction::OriginalUnicastNpdu, None)
+            .apdu()
+            .is_none());
+    }
+
+    #[test]
+    fn typed_read_property_values() {
+        fn encode_ack(buf: &mut [u8], value: ApplicationDataValue) -> usize {
+            let ack = ComplexAck {
+                invoke_id: 1,
+                service: ComplexAckService::ReadProperty(ReadPropertyAck {
+                    object_id: ObjectId::new(ObjectType::ObjectBinaryValue, 1),
+                    property_id: PropertyId::PropPresentValue,
+                    property_value: ReadPropertyValue::ApplicationDataValue(value),
+                }),
+            };
+            let message = NetworkMessage::Apdu(ApplicationPdu::ComplexAck(ack));
+            let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+            let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+            let mut writer = Writer::new(buf);
+            data_link.encode(&mut writer);
+            writer.index
+        }
+
+        fn decode(input: &[u8]) -> DataLink<'_> {
+            let mut reader = Reader::default();
+            DataLink::decode(&mut reader, input).unwrap()
+        }
+
+        let mut buf = [0; 64];
+        let len = encode_ack(&mut buf, ApplicationDataValue::Real(21.5));
+        let message = decode(&buf[..len]);
+        assert_eq!(message.get_read_property_real(), Some(21.5));
+        assert_eq!(message.get_read_property_unsigned(), None);
+
+        let len = encode_ack(&mut buf, ApplicationDataValue::UnsignedInt(300));
+        let message = decode(&buf[..len]);
+        assert_eq!(message.get_read_property_unsigned(), Some(300));
+        assert_eq!(message.get_read_property_real(), None);
+
+        let len = encode_ack(&mut buf, ApplicationDataValue::Boolean(true));
+        let message = decode(&buf[..len]);
+        assert_eq!(message.get_read_property_bool(), Some(true));
+
+        let value = ApplicationDataValue::Enumerated(Enumerated::Binary(Binary::On));
+        let len = encode_ack(&mut buf, value);
+        let message = decode(&buf[..len]);
+        assert!(matches!(
+            message.get_read_property_enumerated(),
+            Some(Enumerated::Binary(Binary::On))
+        ));
+        assert_eq!(message.get_read_property_bool(), None);
+
+        // not a read property ack
+        let len = encode_read_property_multiple(1, &mut buf).len();
+        let message = decode(&buf[..len]);
+        assert!(message.get_read_property_value().is_none());
     }
 }
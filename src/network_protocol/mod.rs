@@ -1,2 +1,3 @@
 pub mod data_link;
 pub mod network_pdu;
+pub mod routing_table;
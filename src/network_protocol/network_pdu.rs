@@ -6,6 +6,8 @@ use crate::{
     },
 };
 
+use super::routing_table::RoutingTable;
+
 // Network Layer Protocol Data Unit
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -59,6 +61,11 @@ pub enum NetworkMessage<'a> {
     Apdu(ApplicationPdu<'a>),
     MessageType(MessageType),
     CustomMessageType(u8),
+    // an empty routing table queries the router for its routing table
+    InitRtTable(RoutingTable<'a>),
+    InitRtTableAck(RoutingTable<'a>),
+    // configured is false when the network number was learned from another router
+    NetworkNumberIs { network: u16, configured: bool },
 }
 
 // Network Layer Message Type
@@ -138,7 +145,64 @@ impl<'a> NetworkPdu<'a> {
         }
     }
 
+    // wraps an apdu with the expecting-reply bit set to match the apdu type
+    pub fn new_apdu(
+        src: Option<SourceAddress>,
+        dst: Option<DestinationAddress>,
+        message_priority: MessagePriority,
+        apdu: ApplicationPdu<'a>,
+    ) -> Self {
+        let expect_reply = apdu.expects_reply();
+        Self::new(
+            src,
+            dst,
+            expect_reply,
+            message_priority,
+            NetworkMessage::Apdu(apdu),
+        )
+    }
+
+    // the hop count is only sent when routing so this has no effect without a destination network
+    pub fn with_hop_count(mut self, hop_count: u8) -> Self {
+        if let Some(dst) = self.dst.take() {
+            self.dst = Some(dst.with_hop_count(hop_count));
+        }
+        self
+    }
+
+    pub fn with_priority(mut self, message_priority: MessagePriority) -> Self {
+        self.message_priority = message_priority;
+        self
+    }
+
+    // the control byte is calculated from these fields when encoding so a pdu can be reused
+    pub fn set_expecting_reply(&mut self, expect_reply: bool) {
+        self.expect_reply = expect_reply;
+    }
+
+    pub fn set_priority(&mut self, message_priority: MessagePriority) {
+        self.message_priority = message_priority;
+    }
+
+    // None for network layer messages
+    pub fn apdu(&self) -> Option<&ApplicationPdu<'a>> {
+        match &self.network_message {
+            NetworkMessage::Apdu(apdu) => Some(apdu),
+            _ => None,
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
+        // a confirmed request without the expecting-reply bit may never be answered
+        debug_assert!(
+            self.expect_reply
+                || !matches!(
+                    self.network_message,
+                    NetworkMessage::Apdu(ApplicationPdu::ConfirmedRequest(_))
+                ),
+            "confirmed request sent without the expecting-reply bit"
+        );
+
         writer.push(Self::VERSION);
         writer.push(self.calculate_control());
 
@@ -163,36 +227,43 @@ impl<'a> NetworkPdu<'a> {
             NetworkMessage::CustomMessageType(message_type) => {
                 writer.push(*message_type);
             }
+            NetworkMessage::InitRtTable(routing_table) => {
+                writer.push(MessageType::InitRtTable as u8);
+                routing_table.encode(writer);
+            }
+            NetworkMessage::InitRtTableAck(routing_table) => {
+                writer.push(MessageType::InitRtTableAck as u8);
+                routing_table.encode(writer);
+            }
+            NetworkMessage::NetworkNumberIs {
+                network,
+                configured,
+            } => {
+                writer.push(MessageType::NetworkNumberIs as u8);
+                writer.extend_from_slice(&network.to_be_bytes());
+                writer.push(*configured as u8);
+            }
         };
     }
 
+    pub fn encoded_len(&self) -> usize {
+        Writer::measure(|writer| self.encode(writer))
+    }
+
     fn calculate_control(&self) -> u8 {
         let is_network_layer_message = match &self.network_message {
             NetworkMessage::Apdu(_) => 0,
-            NetworkMessage::MessageType(_) => ControlFlags::NetworkLayerMessage as u8,
-            NetworkMessage::CustomMessageType(_) => ControlFlags::NetworkLayerMessage as u8,
+            _ => ControlFlags::NetworkLayerMessage as u8,
         };
 
         let has_destination = match self.dst.as_ref() {
-            Some(dst) => {
-                if dst.network_address.net > 0 {
-                    ControlFlags::HasDestination as u8
-                } else {
-                    0
-                }
-            }
-            None => 0,
+            Some(dst) if dst.network_address.net > 0 => ControlFlags::HasDestination as u8,
+            _ => 0,
         };
 
         let has_source = match self.src.as_ref() {
-            Some(src) => {
-                if src.net > 0 && src.net != 0xFFFF {
-                    ControlFlags::HasSource as u8
-                } else {
-                    0
-                }
-            }
-            None => 0,
+            Some(src) if src.net > 0 && src.net != 0xFFFF => ControlFlags::HasSource as u8,
+            _ => 0,
         };
         let expecting_reply = if self.expect_reply {
             ControlFlags::ExpectingReply as u8
@@ -242,6 +313,20 @@ impl<'a> NetworkPdu<'a> {
         let network_message = if is_network_message {
             let message_type = reader.read_byte(buf)?;
             match message_type.try_into() {
+                Ok(MessageType::InitRtTable) => {
+                    NetworkMessage::InitRtTable(RoutingTable::decode(reader, buf)?)
+                }
+                Ok(MessageType::InitRtTableAck) => {
+                    NetworkMessage::InitRtTableAck(RoutingTable::decode(reader, buf)?)
+                }
+                Ok(MessageType::NetworkNumberIs) => {
+                    let network = u16::from_be_bytes(reader.read_bytes(buf)?);
+                    let configured = reader.read_byte(buf)? == 1;
+                    NetworkMessage::NetworkNumberIs {
+                        network,
+                        configured,
+                    }
+                }
                 Ok(message_type) => NetworkMessage::MessageType(message_type),
                 Err(custom_message_type) => NetworkMessage::CustomMessageType(custom_message_type),
             }
@@ -269,13 +354,177 @@ pub struct Addr {
 
 const IPV4_ADDR_LEN: u8 = 6;
 
+// a B/IP address (the 6 byte mac of B/IP is the ipv4 address followed by the udp port)
+// this is the address in a Forwarded-NPDU and the mac of B/IP devices in address bindings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BipAddress {
+    pub ip: [u8; 4],
+    pub port: u16,
+}
+
+impl BipAddress {
+    pub const LEN: usize = IPV4_ADDR_LEN as usize;
+
+    pub fn new(ip: [u8; 4], port: u16) -> Self {
+        Self { ip, port }
+    }
+
+    pub fn from_mac(mac: &[u8]) -> Result<Self, Error> {
+        if mac.len() != Self::LEN {
+            return Err(Error::Length((
+                "BipAddress mac must be 6 bytes",
+                mac.len() as u32,
+            )));
+        }
+
+        let mut ip = [0; 4];
+        ip.copy_from_slice(&mac[..4]);
+        let port = u16::from_be_bytes([mac[4], mac[5]]);
+        Ok(Self { ip, port })
+    }
+
+    pub fn to_mac(&self) -> [u8; Self::LEN] {
+        let mut mac = [0; Self::LEN];
+        mac[..4].copy_from_slice(&self.ip);
+        mac[4..].copy_from_slice(&self.port.to_be_bytes());
+        mac
+    }
+
+    // the socket address is only available with std (which the tokio feature brings in)
+    #[cfg(feature = "tokio")]
+    pub fn to_socket_addr(&self) -> std::net::SocketAddr {
+        std::net::SocketAddr::from((self.ip, self.port))
+    }
+
+    // encodes the 6 bytes without a length prefix (as in a Forwarded-NPDU)
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.extend_from_slice(&self.to_mac());
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let mac: [u8; Self::LEN] = reader.read_bytes(buf)?;
+        Self::from_mac(&mac)
+    }
+}
+
+impl From<BipAddress> for MacAddress {
+    fn from(value: BipAddress) -> Self {
+        let mut bytes = [0; MAX_MAC_LEN];
+        bytes[..BipAddress::LEN].copy_from_slice(&value.to_mac());
+        Self {
+            len: IPV4_ADDR_LEN,
+            bytes,
+        }
+    }
+}
+
+impl From<Addr> for BipAddress {
+    fn from(value: Addr) -> Self {
+        Self::new(value.ipv4, value.port)
+    }
+}
+
+impl From<BipAddress> for Addr {
+    fn from(value: BipAddress) -> Self {
+        Self {
+            ipv4: value.ip,
+            port: value.port,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl From<std::net::SocketAddrV4> for BipAddress {
+    fn from(value: std::net::SocketAddrV4) -> Self {
+        Self::new(value.ip().octets(), value.port())
+    }
+}
+
+// the largest DLEN / SLEN we support (ASHRAE 135 clause 6.2.2), the same limit as MAX_MAC_LEN in the
+// bacnet-stack reference implementation (B/IP and Ethernet are 6 bytes, MS/TP and ARCNET are 1 byte)
+pub const MAX_MAC_LEN: usize = 7;
+
+// a data link agnostic mac address (DADR / SADR in the npdu)
+// an empty mac address means broadcast
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MacAddress {
+    len: u8,
+    bytes: [u8; MAX_MAC_LEN],
+}
+
+impl MacAddress {
+    pub fn new(mac: &[u8]) -> Result<Self, Error> {
+        if mac.len() > MAX_MAC_LEN {
+            return Err(Error::Length((
+                "MacAddress len cannot be greater than MAX_MAC_LEN",
+                mac.len() as u32,
+            )));
+        }
+
+        let mut bytes = [0; MAX_MAC_LEN];
+        bytes[..mac.len()].copy_from_slice(mac);
+        Ok(Self {
+            len: mac.len() as u8,
+            bytes,
+        })
+    }
+
+    pub fn broadcast() -> Self {
+        Self::default()
+    }
+
+    pub fn is_broadcast(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    // None unless the mac is 6 bytes (the ipv4 address and udp port of a B/IP device)
+    pub fn bip_address(&self) -> Option<BipAddress> {
+        BipAddress::from_mac(self.as_slice()).ok()
+    }
+
+    // bip_address as the older Addr type (prefer bip_address in new code)
+    pub fn addr(&self) -> Option<Addr> {
+        self.bip_address().map(Addr::from)
+    }
+
+    // encodes the length prefix followed by the mac bytes
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(self.len);
+        writer.extend_from_slice(self.as_slice());
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let len = reader.read_byte(buf)? as usize;
+        let mac = reader.read_slice(len, buf)?;
+        Self::new(mac)
+    }
+}
+
+impl From<Addr> for MacAddress {
+    fn from(value: Addr) -> Self {
+        let mut bytes = [0; MAX_MAC_LEN];
+        bytes[..4].copy_from_slice(&value.ipv4);
+        bytes[4..6].copy_from_slice(&value.port.to_be_bytes());
+        Self {
+            len: IPV4_ADDR_LEN,
+            bytes,
+        }
+    }
+}
+
 pub type SourceAddress = NetworkAddress;
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct NetworkAddress {
     pub net: u16,
-    pub addr: Option<Addr>,
+    pub mac: MacAddress,
 }
 
 #[derive(Debug, Clone)]
@@ -287,44 +536,269 @@ pub struct DestinationAddress {
 
 impl DestinationAddress {
     pub fn new(net: u16, addr: Option<Addr>) -> Self {
+        let mac = addr.map(MacAddress::from).unwrap_or_default();
+        Self::new_with_mac(net, mac)
+    }
+
+    pub fn new_with_mac(net: u16, mac: MacAddress) -> Self {
         Self {
-            network_address: NetworkAddress { net, addr },
+            network_address: NetworkAddress::new(net, mac),
             hop_count: 255,
         }
     }
+
+    // lower the hop count to stop a message circulating forever between misconfigured routers
+    pub fn with_hop_count(mut self, hop_count: u8) -> Self {
+        self.hop_count = hop_count;
+        self
+    }
 }
 
 impl NetworkAddress {
+    pub fn new(net: u16, mac: MacAddress) -> Self {
+        Self { net, mac }
+    }
+
+    // returns the B/IP address if the mac is an ip address and port
+    pub fn addr(&self) -> Option<Addr> {
+        self.mac.addr()
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         writer.extend_from_slice(&self.net.to_be_bytes());
-        match self.addr.as_ref() {
-            Some(addr) => {
-                writer.push(IPV4_ADDR_LEN);
-                writer.extend_from_slice(&addr.ipv4);
-                writer.extend_from_slice(&addr.port.to_be_bytes());
-            }
-            None => writer.push(0),
-        }
+        self.mac.encode(writer);
     }
 
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
         let net = u16::from_be_bytes(reader.read_bytes(buf)?);
-        let len = reader.read_byte(buf)?;
-        match len {
-            IPV4_ADDR_LEN => {
-                let ipv4: [u8; 4] = reader.read_bytes(buf)?;
-                let port = u16::from_be_bytes(reader.read_bytes(buf)?);
-
-                Ok(Self {
-                    net,
-                    addr: Some(Addr { ipv4, port }),
-                })
+        let mac = MacAddress::decode(reader, buf)?;
+        Ok(Self { net, mac })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{
+                ConfirmedRequest, ConfirmedRequestService, ConfirmedServiceChoice, SegmentAck,
+                SimpleAck,
+            },
+            services::{read_property::ReadProperty, who_is::WhoIs},
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{
+        BipAddress, DestinationAddress, MacAddress, MessagePriority, NetworkMessage, NetworkPdu,
+    };
+
+    #[test]
+    fn route_to_mstp_device() {
+        let mac = MacAddress::new(&[0x0A]).unwrap();
+        let dst = Some(DestinationAddress::new_with_mac(5, mac));
+        let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::WhoIs(WhoIs::new()));
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, dst, false, MessagePriority::Normal, message);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        npdu.encode(&mut writer);
+        let encoded = writer.to_bytes();
+
+        // version, control, DNET, DLEN, DADR, hop count, apdu
+        assert_eq!(
+            encoded,
+            &[0x01, 0x20, 0x00, 0x05, 0x01, 0x0A, 0xFF, 0x10, 0x08]
+        );
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = NetworkPdu::decode(&mut reader, encoded).unwrap();
+        let dst = decoded.dst.unwrap();
+        assert_eq!(dst.network_address.net, 5);
+        assert_eq!(dst.network_address.mac.as_slice(), &[0x0A]);
+        assert!(dst.network_address.addr().is_none());
+    }
+
+    #[test]
+    fn hop_count_only_when_routed() {
+        let message = || {
+            NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
+                UnconfirmedRequest::WhoIs(WhoIs::new()),
+            ))
+        };
+        let dst = Some(DestinationAddress::new(5, None));
+        let routed = NetworkPdu::new(None, dst, false, MessagePriority::Normal, message())
+            .with_hop_count(16)
+            .with_priority(MessagePriority::Urgent);
+        let local = NetworkPdu::new(None, None, false, MessagePriority::Normal, message())
+            .with_hop_count(16);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        routed.encode(&mut writer);
+        // version, control, DNET, DLEN (broadcast), hop count, apdu
+        assert_eq!(
+            writer.to_bytes(),
+            &[0x01, 0x21, 0x00, 0x05, 0x00, 0x10, 0x10, 0x08]
+        );
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        local.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &[0x01, 0x00, 0x10, 0x08]);
+    }
+
+    #[test]
+    fn control_byte_follows_setters() {
+        let message = NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
+            UnconfirmedRequest::WhoIs(WhoIs::new()),
+        ));
+        let mut npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        npdu.encode(&mut writer);
+        assert_eq!(writer.to_bytes()[1], 0x00);
+
+        npdu.set_expecting_reply(true);
+        npdu.set_priority(MessagePriority::LifeSafety);
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        npdu.encode(&mut writer);
+        assert_eq!(writer.to_bytes()[1], 0x07);
+
+        npdu.set_expecting_reply(false);
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        npdu.encode(&mut writer);
+        assert_eq!(writer.to_bytes()[1], 0x03);
+    }
+
+    #[test]
+    fn network_number_is() {
+        let message = NetworkMessage::NetworkNumberIs {
+            network: 2001,
+            configured: true,
+        };
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        npdu.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(encoded, &[0x01, 0x80, 0x13, 0x07, 0xD1, 0x01]);
+
+        let mut reader = Reader::new_with_len(encoded.len());
+        let decoded = NetworkPdu::decode(&mut reader, encoded).unwrap();
+        assert!(matches!(
+            decoded.network_message,
+            NetworkMessage::NetworkNumberIs {
+                network: 2001,
+                configured: true
             }
-            0 => Ok(Self { net, addr: None }),
-            x => Err(Error::Length((
-                "NetworkAddress decode ip len can only be 6 or 0",
-                x as u32,
-            ))),
-        }
+        ));
+
+        // learned from another router
+        let input = [0x01, 0x80, 0x13, 0x00, 0x05, 0x00];
+        let mut reader = Reader::new_with_len(input.len());
+        let decoded = NetworkPdu::decode(&mut reader, &input).unwrap();
+        assert!(matches!(
+            decoded.network_message,
+            NetworkMessage::NetworkNumberIs {
+                network: 5,
+                configured: false
+            }
+        ));
+
+        // the flag is missing
+        let mut reader = Reader::new_with_len(5);
+        assert!(NetworkPdu::decode(&mut reader, &input[..5]).is_err());
+    }
+
+    #[test]
+    fn bip_address_from_mac() {
+        let mac = [0xC0, 0xA8, 0x01, 0xF9, 0xBA, 0xC0];
+        let addr = BipAddress::from_mac(&mac).unwrap();
+        assert_eq!(addr, BipAddress::new([192, 168, 1, 249], 47808));
+        assert_eq!(addr.to_mac(), mac);
+
+        let mac_address = MacAddress::from(addr);
+        assert_eq!(mac_address.as_slice(), &mac);
+        assert_eq!(mac_address.bip_address(), Some(addr));
+
+        // an MS/TP mac is not a B/IP address
+        assert!(BipAddress::from_mac(&[0x0A]).is_err());
+        assert_eq!(MacAddress::new(&[0x0A]).unwrap().bip_address(), None);
+
+        let mut buf = [0; 8];
+        let mut writer = Writer::new(&mut buf);
+        addr.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(encoded, &mac);
+        let mut reader = Reader::new_with_len(encoded.len());
+        assert_eq!(BipAddress::decode(&mut reader, encoded).unwrap(), addr);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn bip_address_to_socket_addr() {
+        let addr = BipAddress::from_mac(&[0xC0, 0xA8, 0x01, 0xF9, 0xBA, 0xC0]).unwrap();
+        let socket_addr = addr.to_socket_addr();
+        assert_eq!(socket_addr, "192.168.1.249:47808".parse().unwrap());
+        assert_eq!(
+            BipAddress::from(
+                "192.168.1.249:47808"
+                    .parse::<std::net::SocketAddrV4>()
+                    .unwrap()
+            ),
+            addr
+        );
+    }
+
+    fn read_property_request() -> ApplicationPdu<'static> {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+        let service = ConfirmedRequestService::ReadProperty(request);
+        ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(1, service))
+    }
+
+    #[test]
+    fn expecting_reply_per_apdu_type() {
+        let control = |apdu: ApplicationPdu| {
+            let npdu = NetworkPdu::new_apdu(None, None, MessagePriority::Normal, apdu);
+            let mut buf = [0; 32];
+            let mut writer = Writer::new(&mut buf);
+            npdu.encode(&mut writer);
+            writer.to_bytes()[1]
+        };
+
+        assert_eq!(control(read_property_request()), 0x04);
+        let who_is = UnconfirmedRequest::WhoIs(WhoIs::new());
+        assert_eq!(control(ApplicationPdu::UnconfirmedRequest(who_is)), 0x00);
+        let ack = SimpleAck {
+            invoke_id: 1,
+            service_choice: ConfirmedServiceChoice::WriteProperty,
+        };
+        assert_eq!(control(ApplicationPdu::SimpleAck(ack)), 0x00);
+        let segment_ack = SegmentAck::new(1, 0, 1);
+        assert_eq!(control(ApplicationPdu::SegmentAck(segment_ack)), 0x00);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "expecting-reply")]
+    fn confirmed_request_must_expect_reply() {
+        let message = NetworkMessage::Apdu(read_property_request());
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        npdu.encode(&mut writer);
     }
 }
@@ -0,0 +1,223 @@
+use crate::common::{
+    error::Error,
+    io::{Reader, Writer},
+};
+
+// A port mapping in an Initialize-Routing-Table or Initialize-Routing-Table-Ack message.
+// In an Initialize-Routing-Table a port id of zero removes the network from the routing table.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RoutingTableEntry<'a> {
+    pub network: u16,
+    pub port_id: u8,
+    pub port_info: &'a [u8],
+}
+
+impl<'a> RoutingTableEntry<'a> {
+    pub fn new(network: u16, port_id: u8) -> Self {
+        Self {
+            network,
+            port_id,
+            port_info: &[],
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.extend_from_slice(&self.network.to_be_bytes());
+        writer.push(self.port_id);
+        writer.push(self.port_info.len() as u8);
+        writer.extend_from_slice(self.port_info);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let network = u16::from_be_bytes(reader.read_bytes(buf)?);
+        let port_id = reader.read_byte(buf)?;
+        let port_info_len = reader.read_byte(buf)?;
+        let port_info = reader.read_slice(port_info_len as usize, buf)?;
+        Ok(Self {
+            network,
+            port_id,
+            port_info,
+        })
+    }
+}
+
+// The body of an Initialize-Routing-Table (or its ack): the number of ports followed by the port mappings.
+// An Initialize-Routing-Table with no ports is a query for the complete routing table of the router.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RoutingTable<'a> {
+    pub entries: &'a [RoutingTableEntry<'a>],
+    len: u8,
+    buf: &'a [u8],
+}
+
+impl<'a> RoutingTable<'a> {
+    pub fn new(entries: &'a [RoutingTableEntry<'a>]) -> Self {
+        Self {
+            entries,
+            len: entries.len() as u8,
+            buf: &[],
+        }
+    }
+
+    pub fn query() -> Self {
+        Self::new(&[])
+    }
+
+    pub fn is_query(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(self.len);
+        if self.entries.is_empty() {
+            // decoded entries are written back out as is
+            writer.extend_from_slice(self.buf);
+        }
+        for entry in self.entries {
+            entry.encode(writer);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let len = reader.read_byte(buf)?;
+
+        // check that every entry is there so that the iterator only has to deal with the entries themselves
+        let start = reader.index;
+        for _ in 0..len {
+            RoutingTableEntry::decode(reader, buf)?;
+        }
+
+        Ok(Self {
+            entries: &[],
+            len,
+            buf: &buf[start..reader.index],
+        })
+    }
+}
+
+impl<'a> IntoIterator for &'_ RoutingTable<'a> {
+    type Item = Result<RoutingTableEntry<'a>, Error>;
+    type IntoIter = RoutingTableIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RoutingTableIter {
+            reader: Reader::new_with_len(self.buf.len()),
+            buf: self.buf,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RoutingTableIter<'a> {
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a> Iterator for RoutingTableIter<'a> {
+    type Item = Result<RoutingTableEntry<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(RoutingTableEntry::decode(&mut self.reader, self.buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::io::{Reader, Writer},
+        network_protocol::network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+    };
+
+    use super::{RoutingTable, RoutingTableEntry};
+
+    fn decode(input: &[u8]) -> NetworkPdu<'_> {
+        let mut reader = Reader::new_with_len(input.len());
+        NetworkPdu::decode(&mut reader, input).unwrap()
+    }
+
+    #[test]
+    fn query_routing_table() {
+        let message = NetworkMessage::InitRtTable(RoutingTable::query());
+        let npdu = NetworkPdu::new(None, None, true, MessagePriority::Normal, message);
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        npdu.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(encoded, &[0x01, 0x84, 0x06, 0x00]);
+
+        let decoded = decode(encoded);
+        assert!(matches!(
+            decoded.network_message,
+            NetworkMessage::InitRtTable(x) if x.is_query()
+        ));
+    }
+
+    #[test]
+    fn routing_table_ack() {
+        let entries = [
+            RoutingTableEntry::new(5, 1),
+            RoutingTableEntry {
+                network: 1000,
+                port_id: 2,
+                port_info: &[0xAA, 0xBB],
+            },
+        ];
+        let message = NetworkMessage::InitRtTableAck(RoutingTable::new(&entries));
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        npdu.encode(&mut writer);
+        let encoded = writer.to_bytes();
+        assert_eq!(
+            encoded,
+            &[0x01, 0x80, 0x07, 0x02, 0x00, 0x05, 0x01, 0x00, 0x03, 0xE8, 0x02, 0x02, 0xAA, 0xBB]
+        );
+
+        let decoded = decode(encoded);
+        let NetworkMessage::InitRtTableAck(routing_table) = &decoded.network_message else {
+            panic!("expected a routing table ack: {:?}", decoded);
+        };
+        assert!(!routing_table.is_query());
+        assert_eq!(routing_table.len(), 2);
+        let mut entries = routing_table.into_iter().map(|x| x.unwrap());
+        let entry = entries.next().unwrap();
+        assert_eq!(
+            (entry.network, entry.port_id, entry.port_info),
+            (5, 1, &[][..])
+        );
+        let entry = entries.next().unwrap();
+        assert_eq!(
+            (entry.network, entry.port_id, entry.port_info),
+            (1000, 2, &[0xAA, 0xBB][..])
+        );
+        assert!(entries.next().is_none());
+
+        // a decoded routing table encodes back to the same bytes
+        let mut buf2 = [0; 32];
+        let mut writer = Writer::new(&mut buf2);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), encoded);
+
+        // the last port is cut off
+        let truncated = &encoded[..encoded.len() - 1];
+        let mut reader = Reader::new_with_len(truncated.len());
+        assert!(NetworkPdu::decode(&mut reader, truncated).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    application_protocol::{
+        primitives::data_value::ApplicationDataValue,
+        services::{
+            read_property::ApplicationDataValueList,
+            read_property_multiple::{
+                PropertyValue, ReadAccessSpecification, ReadPropertyMultipleAck,
+            },
+        },
+    },
+    common::{error::Error, object_id::ObjectId, property_id::PropertyId},
+};
+
+// The value of PropPriorityArray - 16 slots where priority 1 is the highest and a Null slot is relinquished
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PriorityArray<'a> {
+    pub values: ApplicationDataValueList<'a>,
+}
+
+impl<'a> PriorityArray<'a> {
+    pub const LEN: u8 = 16;
+
+    pub fn new(values: ApplicationDataValueList<'a>) -> Self {
+        Self { values }
+    }
+
+    // the highest priority (1 to 16) that is commanded and its value, None if every slot is relinquished
+    pub fn active(&self) -> Result<Option<(u8, ApplicationDataValue<'a>)>, Error> {
+        for (index, value) in self.values.into_iter().enumerate() {
+            match value? {
+                ApplicationDataValue::Null => {}
+                value => return Ok(Some((index as u8 + 1, value))),
+            }
+        }
+        Ok(None)
+    }
+}
+
+// What a commandable object (e.g. an analog output) is doing and why.
+// Properties that the object does not have are None.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CommandableStatus<'a> {
+    pub object_id: ObjectId,
+    pub priority_array: Option<PriorityArray<'a>>,
+    pub relinquish_default: Option<ApplicationDataValue<'a>>,
+    pub present_value: Option<ApplicationDataValue<'a>>,
+}
+
+impl<'a> CommandableStatus<'a> {
+    pub const PROPERTY_IDS: [PropertyId; 3] = [
+        PropertyId::PropPriorityArray,
+        PropertyId::PropRelinquishDefault,
+        PropertyId::PropPresentValue,
+    ];
+
+    pub fn request(object_id: ObjectId) -> ReadAccessSpecification<'static> {
+        ReadAccessSpecification::new(object_id, &Self::PROPERTY_IDS)
+    }
+
+    // uses the results for the first object in the ack
+    pub fn from_ack(ack: &ReadPropertyMultipleAck<'a>) -> Result<Self, Error> {
+        let object = ack.first_object()?;
+        let mut status = Self {
+            object_id: object.object_id,
+            priority_array: None,
+            relinquish_default: None,
+            present_value: None,
+        };
+
+        object.visit_results(|id, value| {
+            match (id, value) {
+                (PropertyId::PropPriorityArray, PropertyValue::PropValueList(values)) => {
+                    status.priority_array = Some(PriorityArray::new(values))
+                }
+                (PropertyId::PropRelinquishDefault, PropertyValue::PropValue(value)) => {
+                    status.relinquish_default = Some(value)
+                }
+                (PropertyId::PropPresentValue, PropertyValue::PropValue(value)) => {
+                    status.present_value = Some(value)
+                }
+                _ => {}
+            }
+            Ok(())
+        })?;
+
+        Ok(status)
+    }
+
+    // the value the object should have: the highest commanded priority or the relinquish default
+    pub fn effective_value(&self) -> Result<Option<ApplicationDataValue<'a>>, Error> {
+        if let Some(priority_array) = &self.priority_array {
+            if let Some((_, value)) = priority_array.active()? {
+                return Ok(Some(value));
+            }
+        }
+        Ok(self.relinquish_default.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::ApplicationDataValue,
+            services::{
+                read_property::ApplicationDataValueList,
+                read_property_multiple::{
+                    PropertyResult, PropertyResultList, PropertyValue, ReadAccessResult,
+                    ReadPropertyMultipleAck,
+                },
+            },
+        },
+        common::{
+            io::Writer,
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::CommandableStatus;
+
+    fn encode_ack<'a>(
+        buf: &'a mut [u8],
+        object_id: ObjectId,
+        priority_array: &[ApplicationDataValue],
+    ) -> &'a [u8] {
+        let results = [
+            PropertyResult {
+                id: PropertyId::PropPriorityArray,
+                value: PropertyValue::PropValueList(ApplicationDataValueList::new(
+                    object_id,
+                    PropertyId::PropPriorityArray,
+                    priority_array,
+                )),
+            },
+            PropertyResult {
+                id: PropertyId::PropRelinquishDefault,
+                value: PropertyValue::PropValue(ApplicationDataValue::Real(18.0)),
+            },
+            PropertyResult {
+                id: PropertyId::PropPresentValue,
+                value: PropertyValue::PropValue(ApplicationDataValue::Real(50.0)),
+            },
+        ];
+        let objects = [ReadAccessResult {
+            object_id,
+            property_results: PropertyResultList::new(&results),
+        }];
+
+        let mut writer = Writer::new(buf);
+        ReadPropertyMultipleAck::new(&objects).encode(&mut writer);
+        let len = writer.to_bytes().len();
+        &buf[1..len] // skip service choice
+    }
+
+    #[test]
+    fn commanded_and_relinquished() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogOutput, 1);
+
+        // commanded at priority 8
+        let priority_array: [ApplicationDataValue; 16] = core::array::from_fn(|i| match i {
+            7 => ApplicationDataValue::Real(50.0),
+            _ => ApplicationDataValue::Null,
+        });
+        let mut buf = [0; 128];
+        let encoded = encode_ack(&mut buf, object_id, &priority_array);
+        let ack = ReadPropertyMultipleAck::new_from_buf(encoded);
+        let status = CommandableStatus::from_ack(&ack).unwrap();
+        assert_eq!(status.object_id, object_id);
+        assert!(matches!(
+            status.priority_array.as_ref().unwrap().active(),
+            Ok(Some((8, ApplicationDataValue::Real(x)))) if x == 50.0
+        ));
+        assert!(matches!(status.present_value, Some(ApplicationDataValue::Real(x)) if x == 50.0));
+        assert!(matches!(
+            status.effective_value(),
+            Ok(Some(ApplicationDataValue::Real(x))) if x == 50.0
+        ));
+
+        // every priority relinquished
+        let priority_array: [ApplicationDataValue; 16] =
+            core::array::from_fn(|_| ApplicationDataValue::Null);
+        let mut buf = [0; 128];
+        let encoded = encode_ack(&mut buf, object_id, &priority_array);
+        let ack = ReadPropertyMultipleAck::new_from_buf(encoded);
+        let status = CommandableStatus::from_ack(&ack).unwrap();
+        assert!(matches!(
+            status.priority_array.as_ref().unwrap().active(),
+            Ok(None)
+        ));
+        assert!(matches!(
+            status.effective_value(),
+            Ok(Some(ApplicationDataValue::Real(x))) if x == 18.0
+        ));
+    }
+}
@@ -0,0 +1,289 @@
+use core::{fmt::Debug, time::Duration};
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{ConfirmedRequest, ConfirmedRequestService},
+    },
+    common::{
+        error::Error,
+        io::{Reader, Writer},
+    },
+    network_protocol::{
+        data_link::{DataLink, DataLinkFunction},
+        network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+    },
+};
+
+use super::{BacnetError, NetworkIo};
+
+// Owns the socket and keeps track of in-flight confirmed requests by invoke id so that
+// several requests can be outstanding at the same time (e.g. a polling gateway).
+// Responses are routed back to the caller with the invoke id of the request they answer.
+// Frames that don't answer a pending request (broadcasts, cov notifications, etc.) are
+// handed to a separate callback.
+//
+// NOTE: there is no timer in no_std so the timeout is passed on to NetworkIo::read_timeout.
+// Use `cancel` to give up on a request that timed out.
+#[derive(Debug)]
+pub struct ResponseDispatcher<T>
+where
+    T: NetworkIo + Debug,
+{
+    io: T,
+    invoke_id: u8,
+    pending: [u32; 8], // one bit per invoke id
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Response<'a> {
+    pub invoke_id: u8,
+    pub apdu: ApplicationPdu<'a>,
+}
+
+impl<T> ResponseDispatcher<T>
+where
+    T: NetworkIo + Debug,
+{
+    pub fn new(io: T) -> Self {
+        Self {
+            io,
+            invoke_id: 0,
+            pending: [0; 8],
+        }
+    }
+
+    /// Returns the socket back to the caller and consumes self
+    pub fn take(self) -> T {
+        self.io
+    }
+
+    pub fn is_pending(&self, invoke_id: u8) -> bool {
+        let (index, mask) = Self::slot(invoke_id);
+        self.pending[index] & mask != 0
+    }
+
+    pub fn pending_count(&self) -> u32 {
+        self.pending.iter().map(|x| x.count_ones()).sum()
+    }
+
+    // stop waiting for a response (e.g. after a timeout) so that the invoke id can be reused
+    pub fn cancel(&mut self, invoke_id: u8) {
+        let (index, mask) = Self::slot(invoke_id);
+        self.pending[index] &= !mask;
+    }
+
+    // sends a confirmed request and returns the invoke id that the response will be routed with
+    #[maybe_async()]
+    pub async fn send_request(
+        &mut self,
+        buf: &mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<u8, BacnetError<T>> {
+        let invoke_id = self.next_free_invoke_id()?;
+        let apdu = ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(invoke_id, service));
+        let npdu = NetworkPdu::new_apdu(None, None, MessagePriority::Normal, apdu);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+
+        // send packet
+        let buffer = writer.to_bytes();
+        self.io.write(buffer).await.map_err(BacnetError::Io)?;
+
+        let (index, mask) = Self::slot(invoke_id);
+        self.pending[index] |= mask;
+        Ok(invoke_id)
+    }
+
+    // reads a single frame from the socket, waiting at most `timeout` (BacnetError::Timeout)
+    // returns the response if the frame answers a pending request, otherwise the frame is passed to `unmatched`
+    #[maybe_async()]
+    pub async fn recv_and_dispatch<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+        timeout: Duration,
+        unmatched: impl FnOnce(DataLink<'a>),
+    ) -> Result<Option<Response<'a>>, BacnetError<T>> {
+        let n = self
+            .io
+            .read_timeout(buf, timeout)
+            .await
+            .map_err(BacnetError::Io)?
+            .ok_or(BacnetError::Timeout)?;
+        let buf = &buf[..n];
+
+        // use the DataLink codec to decode the bytes
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
+
+        let invoke_id = message.apdu().and_then(Self::response_invoke_id);
+
+        match invoke_id {
+            Some(invoke_id) if self.is_pending(invoke_id) => {
+                // a segmented response keeps its invoke id until the last segment arrives
+                let more_follows = matches!(
+                    message.apdu(),
+                    Some(ApplicationPdu::Segment(segment)) if segment.more_follows
+                );
+                if !more_follows {
+                    self.cancel(invoke_id);
+                }
+
+                match message.npdu {
+                    Some(NetworkPdu {
+                        network_message: NetworkMessage::Apdu(apdu),
+                        ..
+                    }) => Ok(Some(Response { invoke_id, apdu })),
+                    _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                        "npdu message is not an apdu",
+                    ))),
+                }
+            }
+            _ => {
+                unmatched(message);
+                Ok(None)
+            }
+        }
+    }
+
+    fn response_invoke_id(apdu: &ApplicationPdu) -> Option<u8> {
+        match apdu {
+            ApplicationPdu::ConfirmedRequest(_) => None,
+            apdu => apdu.invoke_id(),
+        }
+    }
+
+    fn next_free_invoke_id(&mut self) -> Result<u8, BacnetError<T>> {
+        for _ in 0..=u8::MAX {
+            let invoke_id = self.invoke_id;
+            self.invoke_id = self.invoke_id.wrapping_add(1);
+            if !self.is_pending(invoke_id) {
+                return Ok(invoke_id);
+            }
+        }
+
+        Err(BacnetError::Codec(Error::InvalidValue(
+            "all invoke ids are in use by pending requests",
+        )))
+    }
+
+    fn slot(invoke_id: u8) -> (usize, u32) {
+        ((invoke_id / 32) as usize, 1 << (invoke_id % 32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{ConfirmedRequestService, ConfirmedServiceChoice, SimpleAck},
+            services::read_property::ReadProperty,
+        },
+        common::{
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+        simple::{
+            mock::{block_on, frame, MockIo},
+            BacnetError,
+        },
+    };
+
+    use super::ResponseDispatcher;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    fn read_present_value(id: u32) -> ConfirmedRequestService<'static> {
+        ConfirmedRequestService::ReadProperty(ReadProperty::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, id),
+            PropertyId::PropPresentValue,
+        ))
+    }
+
+    fn simple_ack(buf: &mut [u8], invoke_id: u8) -> usize {
+        let ack = SimpleAck {
+            invoke_id,
+            service_choice: ConfirmedServiceChoice::WriteProperty,
+        };
+        frame(buf, ApplicationPdu::SimpleAck(ack))
+    }
+
+    #[test]
+    fn interleaved_responses() {
+        // the second request is answered first
+        let mut first = [0; 32];
+        let n = simple_ack(&mut first, 1);
+        let first = &first[..n];
+        let mut second = [0; 32];
+        let n = simple_ack(&mut second, 0);
+        let second = &second[..n];
+        let frames = [first, second];
+
+        let mut dispatcher = ResponseDispatcher::new(MockIo::new(&frames));
+        let mut buf = [0; 64];
+        let a = block_on(dispatcher.send_request(&mut buf, read_present_value(1))).unwrap();
+        let b = block_on(dispatcher.send_request(&mut buf, read_present_value(2))).unwrap();
+        assert_eq!((a, b), (0, 1));
+        assert_eq!(dispatcher.pending_count(), 2);
+
+        let response = block_on(dispatcher.recv_and_dispatch(&mut buf, TIMEOUT, |x| {
+            panic!("unexpected unmatched frame {:?}", x)
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(response.invoke_id, b);
+        assert!(matches!(response.apdu, ApplicationPdu::SimpleAck(_)));
+        assert!(!dispatcher.is_pending(b));
+        assert!(dispatcher.is_pending(a));
+
+        let response = block_on(dispatcher.recv_and_dispatch(&mut buf, TIMEOUT, |x| {
+            panic!("unexpected unmatched frame {:?}", x)
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(response.invoke_id, a);
+        assert_eq!(dispatcher.pending_count(), 0);
+
+        // nothing else arrives
+        let result = block_on(dispatcher.recv_and_dispatch(&mut buf, TIMEOUT, |_| {}));
+        assert!(matches!(result, Err(BacnetError::Timeout)));
+
+        let io = dispatcher.take();
+        assert_eq!(io.write_count(), 2);
+        assert!(matches!(
+            io.written_apdu(1),
+            ApplicationPdu::ConfirmedRequest(x) if x.invoke_id == b
+        ));
+    }
+
+    #[test]
+    fn unmatched_invoke_id() {
+        let mut ack = [0; 32];
+        let n = simple_ack(&mut ack, 7);
+        let frames = [&ack[..n]];
+
+        let mut dispatcher = ResponseDispatcher::new(MockIo::new(&frames));
+        let mut buf = [0; 64];
+        let invoke_id = block_on(dispatcher.send_request(&mut buf, read_present_value(1))).unwrap();
+
+        let mut unmatched = None;
+        let response = block_on(dispatcher.recv_and_dispatch(&mut buf, TIMEOUT, |x| {
+            unmatched = x.apdu().and_then(|apdu| apdu.invoke_id())
+        }))
+        .unwrap();
+        assert!(response.is_none());
+        assert_eq!(unmatched, Some(7));
+
+        // the request is still waiting for its response
+        assert!(dispatcher.is_pending(invoke_id));
+        assert_eq!(dispatcher.pending_count(), 1);
+    }
+}
@@ -0,0 +1,142 @@
+// a NetworkIo for tests that replays canned frames and records the frames written
+// it never blocks so the async api can be driven by `block_on` without a runtime
+
+use core::time::Duration;
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::application_pdu::ApplicationPdu,
+    common::io::{Reader, Writer},
+    network_protocol::{
+        data_link::{DataLink, DataLinkFunction},
+        network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+    },
+};
+
+use super::NetworkIo;
+
+const MAX_WRITES: usize = 16;
+
+#[derive(Debug)]
+pub struct MockIo<'a> {
+    frames: &'a [&'a [u8]],
+    next_frame: usize,
+    written: [u8; 1024],
+    write_ends: [usize; MAX_WRITES],
+    write_count: usize,
+}
+
+// read after the last frame
+#[derive(Debug)]
+pub struct NoMoreFrames;
+
+impl<'a> MockIo<'a> {
+    pub fn new(frames: &'a [&'a [u8]]) -> Self {
+        Self {
+            frames,
+            next_frame: 0,
+            written: [0; 1024],
+            write_ends: [0; MAX_WRITES],
+            write_count: 0,
+        }
+    }
+
+    pub fn write_count(&self) -> usize {
+        self.write_count
+    }
+
+    pub fn written(&self, index: usize) -> &[u8] {
+        assert!(index < self.write_count, "frame {index} was not written");
+        let start = if index == 0 {
+            0
+        } else {
+            self.write_ends[index - 1]
+        };
+        &self.written[start..self.write_ends[index]]
+    }
+
+    // the apdu of a written frame
+    pub fn written_apdu(&self, index: usize) -> ApplicationPdu<'_> {
+        let frame = self.written(index);
+        let mut reader = Reader::default();
+        match DataLink::decode(&mut reader, frame).unwrap().npdu {
+            Some(NetworkPdu {
+                network_message: NetworkMessage::Apdu(apdu),
+                ..
+            }) => apdu,
+            x => panic!("expected an apdu: {:?}", x),
+        }
+    }
+}
+
+#[maybe_async(AFIT)]
+impl NetworkIo for MockIo<'_> {
+    type Error = NoMoreFrames;
+
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let frame = self.frames.get(self.next_frame).ok_or(NoMoreFrames)?;
+        self.next_frame += 1;
+        buf[..frame.len()].copy_from_slice(frame);
+        Ok(frame.len())
+    }
+
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let start = match self.write_count {
+            0 => 0,
+            x => self.write_ends[x - 1],
+        };
+        let end = start + buf.len();
+        self.written[start..end].copy_from_slice(buf);
+        self.write_ends[self.write_count] = end;
+        self.write_count += 1;
+        Ok(buf.len())
+    }
+
+    // the timeout expires as soon as there are no frames left
+    async fn read_timeout(
+        &mut self,
+        buf: &mut [u8],
+        _timeout: Duration,
+    ) -> Result<Option<usize>, Self::Error> {
+        if self.next_frame == self.frames.len() {
+            return Ok(None);
+        }
+        self.read(buf).await.map(Some)
+    }
+}
+
+// encodes an apdu the way a device on the local network would send it
+pub fn frame(buf: &mut [u8], apdu: ApplicationPdu) -> usize {
+    let npdu = NetworkPdu::new_apdu(None, None, MessagePriority::Normal, apdu);
+    let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+    let mut writer = Writer::new(buf);
+    data_link.encode(&mut writer);
+    writer.index
+}
+
+// wraps raw apdu bytes in the same bvlc and npdu headers as `frame`
+// for responses that can't be built from their types (e.g. an ack decoded from a buffer re-encodes as empty)
+pub fn raw_frame(buf: &mut [u8], apdu: &[u8]) -> usize {
+    let len = 6 + apdu.len();
+    buf[..6].copy_from_slice(&[0x81, 0x0A, (len >> 8) as u8, len as u8, 0x01, 0x00]);
+    buf[6..len].copy_from_slice(apdu);
+    len
+}
+
+#[cfg(not(feature = "is_sync"))]
+pub fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    use core::task::{Context, Poll, Waker};
+
+    let mut future = core::pin::pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    match future.as_mut().poll(&mut cx) {
+        Poll::Ready(x) => x,
+        Poll::Pending => panic!("MockIo never blocks"),
+    }
+}
+
+#[cfg(feature = "is_sync")]
+pub fn block_on<T>(value: T) -> T {
+    value
+}
@@ -1,18 +1,29 @@
-use core::fmt::Debug;
+pub mod commandable_status;
+pub mod dispatcher;
+#[cfg(test)]
+mod mock;
+pub mod object_summary;
+#[cfg(feature = "tokio")]
+pub mod tokio_client;
+
+use core::{fmt::Debug, time::Duration};
 
 use maybe_async::maybe_async;
 
 use crate::{
     application_protocol::{
-        application_pdu::ApplicationPdu,
+        application_pdu::{ApplicationPdu, MaxAdpu, MaxSegments},
         confirmed::{
-            ComplexAck, ComplexAckService, ConfirmedRequest, ConfirmedRequestService, SimpleAck,
+            response_error, ComplexAck, ComplexAckService, ConfirmedRequest,
+            ConfirmedRequestService, SegmentAck, SimpleAck,
         },
         services::{
             change_of_value::{CovNotification, SubscribeCov},
             i_am::IAm,
             read_property::{ReadProperty, ReadPropertyAck},
-            read_property_multiple::{ReadPropertyMultiple, ReadPropertyMultipleAck},
+            read_property_multiple::{
+                ReadAccessSpecification, ReadPropertyMultiple, ReadPropertyMultipleAck,
+            },
             read_range::{ReadRange, ReadRangeAck},
             time_synchronization::TimeSynchronization,
             who_is::WhoIs,
@@ -23,13 +34,18 @@ use crate::{
     common::{
         error::Error,
         io::{Reader, Writer},
+        object_id::ObjectId,
     },
     network_protocol::{
         data_link::{DataLink, DataLinkFunction},
-        network_pdu::{DestinationAddress, MessagePriority, NetworkMessage, NetworkPdu},
+        network_pdu::{
+            DestinationAddress, MessagePriority, NetworkMessage, NetworkPdu, SourceAddress,
+        },
     },
 };
 
+use self::{commandable_status::CommandableStatus, object_summary::ObjectSummary};
+
 #[allow(async_fn_in_trait)]
 #[cfg(not(feature = "defmt"))]
 #[maybe_async(AFIT)] // AFIT - Async Function In Trait
@@ -37,6 +53,17 @@ pub trait NetworkIo {
     type Error: Debug;
     async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
     async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error>;
+
+    // returns None if nothing was received within the timeout
+    // there is no timer in no_std so the default waits for the next frame, override this to support timeouts
+    async fn read_timeout(
+        &mut self,
+        buf: &mut [u8],
+        timeout: Duration,
+    ) -> Result<Option<usize>, Self::Error> {
+        let _ = timeout;
+        self.read(buf).await.map(Some)
+    }
 }
 
 #[derive(Debug)]
@@ -46,6 +73,8 @@ where
 {
     io: T,
     invoke_id: u8,
+    max_adpu: MaxAdpu,
+    auto_ack_cov: bool,
 }
 
 #[allow(async_fn_in_trait)]
@@ -55,6 +84,17 @@ pub trait NetworkIo {
     type Error: Debug + defmt::Format;
     async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
     async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error>;
+
+    // returns None if nothing was received within the timeout
+    // there is no timer in no_std so the default waits for the next frame, override this to support timeouts
+    async fn read_timeout(
+        &mut self,
+        buf: &mut [u8],
+        timeout: Duration,
+    ) -> Result<Option<usize>, Self::Error> {
+        let _ = timeout;
+        self.read(buf).await.map(Some)
+    }
 }
 
 #[cfg(feature = "defmt")]
@@ -66,6 +106,8 @@ where
 {
     io: T,
     invoke_id: u8,
+    max_adpu: MaxAdpu,
+    auto_ack_cov: bool,
 }
 
 #[derive(Debug)]
@@ -77,6 +119,7 @@ where
     Io(T::Error),
     Codec(Error),
     InvokeId(InvokeIdError),
+    Timeout,
 }
 
 impl<T: NetworkIo> From<Error> for BacnetError<T> {
@@ -92,12 +135,41 @@ pub struct InvokeIdError {
     pub actual: u8,
 }
 
+// a change of value notification received from a device we subscribed to
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReceivedCovNotification<'a> {
+    pub notification: CovNotification<'a>,
+    // the invoke id of a confirmed notification (None if it was unconfirmed)
+    pub invoke_id: Option<u8>,
+    // true if the SimpleAck for a confirmed notification has already been sent
+    pub acknowledged: bool,
+    // the network and mac of a device behind a router (None if it is on our network)
+    pub source: Option<SourceAddress>,
+}
+
 impl<T> Bacnet<T>
 where
     T: NetworkIo + Debug,
 {
     pub fn new(io: T) -> Self {
-        Self { io, invoke_id: 0 }
+        Self {
+            io,
+            invoke_id: 0,
+            max_adpu: MaxAdpu::_1476,
+            auto_ack_cov: true,
+        }
+    }
+
+    // the largest response we can accept (defaults to 1476 bytes)
+    pub fn set_max_adpu(&mut self, max_adpu: MaxAdpu) {
+        self.max_adpu = max_adpu;
+    }
+
+    // confirmed cov notifications are acknowledged as soon as they are received (defaults to true)
+    // if disabled call `ack_change_of_value` or the device will retransmit and eventually cancel the subscription
+    pub fn set_auto_ack_cov(&mut self, auto_ack_cov: bool) {
+        self.auto_ack_cov = auto_ack_cov;
     }
 
     /// Returns the socket back to the caller and consumes self
@@ -113,8 +185,7 @@ where
     ) -> Result<Option<IAm>, BacnetError<T>> {
         let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::WhoIs(request.clone()));
         let dst = Some(DestinationAddress::new(0xffff, None));
-        let message = NetworkMessage::Apdu(apdu);
-        let npdu = NetworkPdu::new(None, dst, false, MessagePriority::Normal, message);
+        let npdu = NetworkPdu::new_apdu(None, dst, MessagePriority::Normal, apdu);
         let data_link = DataLink::new(DataLinkFunction::OriginalBroadcastNpdu, Some(npdu));
 
         let mut writer = Writer::new(buf);
@@ -161,6 +232,122 @@ where
         }
     }
 
+    // reads a large set of properties in one go by accepting a segmented response
+    // the segments are reassembled into `apdu_buf` and a SegmentAck is sent for the first segment and at
+    // the end of every window with `window_size` as our proposed window size (the device may use a smaller one).
+    // a duplicate or missing segment is answered with a negative SegmentAck so the device retransmits.
+    // each segment must arrive within `timeout` (BacnetError::Timeout), see NetworkIo::read_timeout.
+    // if the device aborts with segmentation-not-supported then each object is read with its own
+    // unsegmented request and the results are appended to `apdu_buf` instead
+    #[maybe_async()]
+    pub async fn read_property_multiple_segmented<'a>(
+        &mut self,
+        buf: &mut [u8],
+        apdu_buf: &'a mut [u8],
+        request: ReadPropertyMultiple<'_>,
+        window_size: u8,
+        timeout: Duration,
+    ) -> Result<ReadPropertyMultipleAck<'a>, BacnetError<T>> {
+        let objects = request.objects();
+        let service = ConfirmedRequestService::ReadPropertyMultiple(request);
+        let invoke_id = self.send_confirmed(buf, service).await?;
+
+        let mut len = 0;
+        let mut expected_sequence_num: u8 = 0;
+        let mut window_start: u8 = 0;
+        loop {
+            let apdu = self
+                .receive_response_timeout(buf, invoke_id, timeout)
+                .await?;
+            match apdu {
+                // the response was small enough to fit in a single apdu
+                ApplicationPdu::ComplexAck(ack) => match ack.service {
+                    ComplexAckService::ReadPropertyMultiple(ack) => {
+                        len = Self::append(apdu_buf, 0, ack.body())?;
+                        break;
+                    }
+                    _ => {
+                        return Err(BacnetError::Codec(Error::ConvertDataLink(
+                            "apdu message is not a ComplexAckService ReadPropertyMultipleAck",
+                        )))
+                    }
+                },
+                ApplicationPdu::Segment(segment) => {
+                    if segment.sequence_number != expected_sequence_num {
+                        if expected_sequence_num == 0 {
+                            // the first segment was lost and there is nothing to nak so wait for the device to
+                            // retransmit it (it sends nothing else until the first segment is acked)
+                            continue;
+                        }
+                        // a duplicate or a gap so ask for everything after the last segment received in order
+                        let last_in_order = expected_sequence_num.wrapping_sub(1);
+                        let nak = SegmentAck::new_negative(invoke_id, last_in_order, window_size);
+                        self.send_apdu(buf, ApplicationPdu::SegmentAck(nak)).await?;
+                        window_start = expected_sequence_num;
+                        continue;
+                    }
+                    len = Self::append(apdu_buf, len, segment.data)?;
+
+                    let sequence_num = segment.sequence_number;
+                    let more_follows = segment.more_follows;
+                    let actual_window_size = segment.window_size.max(1);
+                    expected_sequence_num = expected_sequence_num.wrapping_add(1);
+
+                    let end_of_window =
+                        expected_sequence_num.wrapping_sub(window_start) >= actual_window_size;
+                    // the device waits for the first segment to be acked before it sends the rest of the window
+                    if sequence_num == 0 || end_of_window || !more_follows {
+                        let ack = SegmentAck::new(invoke_id, sequence_num, window_size);
+                        self.send_apdu(buf, ApplicationPdu::SegmentAck(ack)).await?;
+                        window_start = expected_sequence_num;
+                    }
+
+                    if !more_follows {
+                        break;
+                    }
+                }
+                ApplicationPdu::Abort(abort) if abort.is_segmentation_not_supported() => {
+                    return self
+                        .read_property_multiple_chunked(buf, apdu_buf, objects)
+                        .await;
+                }
+                apdu => {
+                    return Err(BacnetError::Codec(response_error(apdu).unwrap_or(
+                        Error::ConvertDataLink("apdu message is not a ComplexAck or Segment"),
+                    )))
+                }
+            }
+        }
+
+        Ok(ReadPropertyMultipleAck::new_from_buf(&apdu_buf[..len]))
+    }
+
+    // reads the name, description, present value, units and status flags of an object in one request
+    #[maybe_async()]
+    pub async fn read_object_summary<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+        object_id: ObjectId,
+    ) -> Result<ObjectSummary<'a>, BacnetError<T>> {
+        let objects = [ObjectSummary::request(object_id)];
+        let request = ReadPropertyMultiple::new(&objects);
+        let ack = self.read_property_multiple(buf, request).await?;
+        ObjectSummary::from_ack(&ack).map_err(BacnetError::Codec)
+    }
+
+    // reads the priority array, relinquish default and present value of a commandable object in one request
+    #[maybe_async()]
+    pub async fn read_commandable_status<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+        object_id: ObjectId,
+    ) -> Result<CommandableStatus<'a>, BacnetError<T>> {
+        let objects = [CommandableStatus::request(object_id)];
+        let request = ReadPropertyMultiple::new(&objects);
+        let ack = self.read_property_multiple(buf, request).await?;
+        CommandableStatus::from_ack(&ack).map_err(BacnetError::Codec)
+    }
+
     #[maybe_async()]
     pub async fn read_property<'a>(
         &mut self,
@@ -193,20 +380,74 @@ where
         &mut self,
         buf: &'a mut [u8],
     ) -> Result<Option<CovNotification<'a>>, BacnetError<T>> {
+        let received = self.receive_change_of_value(buf).await?;
+        Ok(received.map(|x| x.notification))
+    }
+
+    // waits for the next confirmed or unconfirmed cov notification
+    // confirmed notifications are acknowledged with a SimpleAck unless auto ack has been disabled
+    #[maybe_async()]
+    pub async fn receive_change_of_value<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+    ) -> Result<Option<ReceivedCovNotification<'a>>, BacnetError<T>> {
         let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
         let mut reader = Reader::default();
         let message = DataLink::decode(&mut reader, &buf[..n])?;
 
-        if let Some(npdu) = message.npdu {
-            if let NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
-                UnconfirmedRequest::CovNotification(x),
-            )) = npdu.network_message
-            {
-                return Ok(Some(x));
-            }
+        let (source, apdu) = match message.npdu {
+            Some(NetworkPdu {
+                src,
+                network_message: NetworkMessage::Apdu(apdu),
+                ..
+            }) => (src, apdu),
+            _ => return Ok(None),
         };
 
-        Ok(None)
+        match apdu {
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::CovNotification(x)) => {
+                Ok(Some(ReceivedCovNotification {
+                    notification: x,
+                    invoke_id: None,
+                    acknowledged: false,
+                    source,
+                }))
+            }
+            ApplicationPdu::ConfirmedRequest(ConfirmedRequest {
+                invoke_id,
+                service: ConfirmedRequestService::CovNotification(x),
+                ..
+            }) => {
+                if self.auto_ack_cov {
+                    // buf is borrowed by the notification and the ack is tiny
+                    let mut ack_buf = [0; 16];
+                    self.ack_change_of_value(&mut ack_buf, invoke_id, source.clone())
+                        .await?;
+                }
+                Ok(Some(ReceivedCovNotification {
+                    notification: x,
+                    invoke_id: Some(invoke_id),
+                    acknowledged: self.auto_ack_cov,
+                    source,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // acknowledges a confirmed cov notification (only needed when auto ack is disabled)
+    // pass the `source` of the notification so the ack is routed back to a device behind a router
+    #[maybe_async()]
+    pub async fn ack_change_of_value(
+        &mut self,
+        buf: &mut [u8],
+        invoke_id: u8,
+        source: Option<SourceAddress>,
+    ) -> Result<(), BacnetError<T>> {
+        let ack = CovNotification::ack(invoke_id);
+        let dst = source.map(|x| DestinationAddress::new_with_mac(x.net, x.mac));
+        self.send_apdu_to(buf, dst, ApplicationPdu::SimpleAck(ack))
+            .await
     }
 
     #[maybe_async()]
@@ -226,9 +467,9 @@ where
     }
 
     #[maybe_async()]
-    pub async fn write_property<'a>(
+    pub async fn write_property(
         &mut self,
-        buf: &'a mut [u8],
+        buf: &mut [u8],
         request: WriteProperty<'_>,
     ) -> Result<(), BacnetError<T>> {
         let service = ConfirmedRequestService::WriteProperty(request);
@@ -236,6 +477,23 @@ where
         Ok(())
     }
 
+    // sends any confirmed request and waits for the response type that the service expects
+    // returns None for services answered with a SimpleAck (e.g. WriteProperty)
+    #[maybe_async()]
+    pub async fn send_request<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<Option<ComplexAck<'a>>, BacnetError<T>> {
+        if service.expects_simple_ack() {
+            self.send_and_receive_simple_ack(buf, service).await?;
+            Ok(None)
+        } else {
+            let ack = self.send_and_receive_complex_ack(buf, service).await?;
+            Ok(Some(ack))
+        }
+    }
+
     #[maybe_async()]
     pub async fn time_sync(
         &mut self,
@@ -246,6 +504,43 @@ where
         self.send_unconfirmed(buf, service).await
     }
 
+    // one object per request without segmentation
+    // the results of an RPM ack are just a list so they can be concatenated into a single ack
+    #[maybe_async()]
+    async fn read_property_multiple_chunked<'a>(
+        &mut self,
+        buf: &mut [u8],
+        apdu_buf: &'a mut [u8],
+        objects: &[ReadAccessSpecification<'_>],
+    ) -> Result<ReadPropertyMultipleAck<'a>, BacnetError<T>> {
+        let mut len = 0;
+        for object in objects {
+            let objects = [object.clone()];
+            let service =
+                ConfirmedRequestService::ReadPropertyMultiple(ReadPropertyMultiple::new(&objects));
+            let request = ConfirmedRequest::new(self.get_then_inc_invoke_id(), service)
+                .with_max_adpu(self.max_adpu.clone())
+                .with_max_segments(MaxSegments::_0);
+            let invoke_id = request.invoke_id;
+            self.send_apdu(buf, ApplicationPdu::ConfirmedRequest(request))
+                .await?;
+
+            let ack = self.receive_complex_ack(buf, invoke_id).await?;
+            match ack.service {
+                ComplexAckService::ReadPropertyMultiple(ack) => {
+                    len = Self::append(apdu_buf, len, ack.body())?;
+                }
+                _ => {
+                    return Err(BacnetError::Codec(Error::ConvertDataLink(
+                        "apdu message is not a ComplexAckService ReadPropertyMultipleAck",
+                    )))
+                }
+            }
+        }
+
+        Ok(ReadPropertyMultipleAck::new_from_buf(&apdu_buf[..len]))
+    }
+
     #[maybe_async()]
     async fn send_and_receive_complex_ack<'a>(
         &mut self,
@@ -253,49 +548,101 @@ where
         service: ConfirmedRequestService<'_>,
     ) -> Result<ComplexAck<'a>, BacnetError<T>> {
         let invoke_id = self.send_confirmed(buf, service).await?;
+        self.receive_complex_ack(buf, invoke_id).await
+    }
 
-        // receive reply
-        let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
-        let buf = &buf[..n];
-
-        // use the DataLink codec to decode the bytes
-        let mut reader = Reader::default();
-        let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
-
-        // TODO: return bacnet error if the server returns one
+    #[maybe_async()]
+    async fn receive_complex_ack<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+        invoke_id: u8,
+    ) -> Result<ComplexAck<'a>, BacnetError<T>> {
         // return message is expected to be a ComplexAck
-        let ack: ComplexAck = message.try_into().map_err(BacnetError::Codec)?;
-
-        // return message is expected to have the same invoke_id as the request
-        Self::check_invoke_id(invoke_id, ack.invoke_id)?;
-
-        Ok(ack)
+        // an Error, Reject or Abort response becomes Error::RequestFailed, Error::Rejected or Error::Aborted
+        match self.receive_response(buf, invoke_id).await? {
+            ApplicationPdu::ComplexAck(ack) => Ok(ack),
+            apdu => Err(BacnetError::Codec(response_error(apdu).unwrap_or(
+                Error::ConvertDataLink("npdu message is not an apdu complex ack"),
+            ))),
+        }
     }
 
     #[maybe_async()]
-    async fn send_and_receive_simple_ack<'a>(
+    async fn send_and_receive_simple_ack(
         &mut self,
-        buf: &'a mut [u8],
+        buf: &mut [u8],
         service: ConfirmedRequestService<'_>,
     ) -> Result<SimpleAck, BacnetError<T>> {
         let invoke_id = self.send_confirmed(buf, service).await?;
 
-        // receive reply
+        // return message is expected to be a SimpleAck
+        // an Error, Reject or Abort response becomes Error::RequestFailed, Error::Rejected or Error::Aborted
+        match self.receive_response(buf, invoke_id).await? {
+            ApplicationPdu::SimpleAck(ack) => Ok(ack),
+            apdu => Err(BacnetError::Codec(response_error(apdu).unwrap_or(
+                Error::ConvertDataLink("npdu message is not an apdu simple ack"),
+            ))),
+        }
+    }
+
+    // receives the next apdu and checks that it is a response to the request with this invoke id
+    // (whatever its type, so an Error, Reject or Abort for some other request is not mistaken for ours)
+    #[maybe_async()]
+    async fn receive_response<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+        invoke_id: u8,
+    ) -> Result<ApplicationPdu<'a>, BacnetError<T>> {
         let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
-        let buf = &buf[..n];
+        Self::decode_response(&buf[..n], invoke_id)
+    }
+
+    // as above but waits at most `timeout` (BacnetError::Timeout)
+    #[maybe_async()]
+    async fn receive_response_timeout<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+        invoke_id: u8,
+        timeout: Duration,
+    ) -> Result<ApplicationPdu<'a>, BacnetError<T>> {
+        let n = self
+            .io
+            .read_timeout(buf, timeout)
+            .await
+            .map_err(BacnetError::Io)?
+            .ok_or(BacnetError::Timeout)?;
+        Self::decode_response(&buf[..n], invoke_id)
+    }
 
+    fn decode_response(buf: &[u8], invoke_id: u8) -> Result<ApplicationPdu<'_>, BacnetError<T>> {
         // use the DataLink codec to decode the bytes
         let mut reader = Reader::default();
         let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
-
-        // TODO: return bacnet error if the server returns one
-        // return message is expected to be a ComplexAck
-        let ack: SimpleAck = message.try_into().map_err(BacnetError::Codec)?;
+        let apdu = match message.npdu {
+            Some(NetworkPdu {
+                network_message: NetworkMessage::Apdu(apdu),
+                ..
+            }) => apdu,
+            _ => {
+                return Err(BacnetError::Codec(Error::ConvertDataLink(
+                    "npdu message is not an apdu",
+                )))
+            }
+        };
 
         // return message is expected to have the same invoke_id as the request
-        Self::check_invoke_id(invoke_id, ack.invoke_id)?;
+        match apdu.invoke_id() {
+            Some(actual) if !matches!(apdu, ApplicationPdu::ConfirmedRequest(_)) => {
+                Self::check_invoke_id(invoke_id, actual)?
+            }
+            _ => {
+                return Err(BacnetError::Codec(Error::ConvertDataLink(
+                    "apdu message is not a response to a confirmed request",
+                )))
+            }
+        }
 
-        Ok(ack)
+        Ok(apdu)
     }
 
     #[maybe_async()]
@@ -305,17 +652,7 @@ where
         service: UnconfirmedRequest<'_>,
     ) -> Result<(), BacnetError<T>> {
         let apdu = ApplicationPdu::UnconfirmedRequest(service);
-        let message = NetworkMessage::Apdu(apdu);
-        let npdu = NetworkPdu::new(None, None, true, MessagePriority::Normal, message);
-        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
-
-        let mut writer = Writer::new(buf);
-        data_link.encode(&mut writer);
-
-        // send packet
-        let buffer = writer.to_bytes();
-        self.io.write(buffer).await.map_err(BacnetError::Io)?;
-        Ok(())
+        self.send_apdu(buf, apdu).await
     }
 
     #[maybe_async()]
@@ -325,9 +662,32 @@ where
         service: ConfirmedRequestService<'_>,
     ) -> Result<u8, BacnetError<T>> {
         let invoke_id = self.get_then_inc_invoke_id();
-        let apdu = ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(invoke_id, service));
-        let message = NetworkMessage::Apdu(apdu);
-        let npdu = NetworkPdu::new(None, None, true, MessagePriority::Normal, message);
+        let request =
+            ConfirmedRequest::new(invoke_id, service).with_max_adpu(self.max_adpu.clone());
+        let apdu = ApplicationPdu::ConfirmedRequest(request);
+        self.send_apdu(buf, apdu).await?;
+
+        Ok(invoke_id)
+    }
+
+    #[maybe_async()]
+    async fn send_apdu(
+        &mut self,
+        buf: &mut [u8],
+        apdu: ApplicationPdu<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        self.send_apdu_to(buf, None, apdu).await
+    }
+
+    // dst is only needed for a device behind a router
+    #[maybe_async()]
+    async fn send_apdu_to(
+        &mut self,
+        buf: &mut [u8],
+        dst: Option<DestinationAddress>,
+        apdu: ApplicationPdu<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let npdu = NetworkPdu::new_apdu(None, dst, MessagePriority::Normal, apdu);
         let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
 
         let mut writer = Writer::new(buf);
@@ -336,8 +696,20 @@ where
         // send packet
         let buffer = writer.to_bytes();
         self.io.write(buffer).await.map_err(BacnetError::Io)?;
+        Ok(())
+    }
 
-        Ok(invoke_id)
+    // copies data to the end of the reassembly buffer and returns the new length
+    fn append(apdu_buf: &mut [u8], len: usize, data: &[u8]) -> Result<usize, BacnetError<T>> {
+        let end = len + data.len();
+        if end > apdu_buf.len() {
+            return Err(BacnetError::Codec(Error::Length((
+                "reassembled apdu does not fit in apdu_buf",
+                end as u32,
+            ))));
+        }
+        apdu_buf[len..end].copy_from_slice(data);
+        Ok(end)
     }
 
     fn check_invoke_id(expected: u8, actual: u8) -> Result<(), BacnetError<T>> {
@@ -360,3 +732,482 @@ where
         invoke_id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use crate::{
+        application_protocol::{
+            application_pdu::{ApduType, ApplicationPdu, MaxSegments},
+            confirmed::{
+                Abort, ConfirmedRequest, ConfirmedRequestService, ConfirmedServiceChoice, Reject,
+            },
+            primitives::data_value::ApplicationDataValue,
+            segment::Segment,
+            services::{
+                change_of_value::{CovNotification, CovNotificationValues, PropertyResult},
+                read_property::ReadProperty,
+                read_property_multiple::{
+                    PropertyValue, ReadAccessSpecification, ReadPropertyMultiple,
+                    ReadPropertyMultipleAck,
+                },
+                write_property::WriteProperty,
+            },
+        },
+        common::{
+            error::Error,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{AbortReason, RejectReason},
+        },
+        network_protocol::{
+            data_link::{DataLink, DataLinkFunction},
+            network_pdu::{MacAddress, MessagePriority, NetworkPdu, SourceAddress},
+        },
+    };
+
+    use super::{
+        mock::{block_on, frame, raw_frame, MockIo},
+        Bacnet, BacnetError,
+    };
+
+    const PROPERTY_IDS: [PropertyId; 1] = [PropertyId::PropPresentValue];
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    // the results of analog input 1 with a present value of 42.0
+    const BODY: [u8; 16] = [
+        0x0C, 0x00, 0x00, 0x00, 0x01, 0x1E, 0x29, 0x55, 0x4E, 0x44, 0x42, 0x28, 0x00, 0x00, 0x4F,
+        0x1F,
+    ];
+
+    // BODY split over three segments sent with a window size of 2
+    fn segments(bufs: &mut [[u8; 32]; 3]) -> [usize; 3] {
+        let data = [&BODY[..6], &BODY[6..9], &BODY[9..]];
+        let mut lens = [0; 3];
+        for (i, buf) in bufs.iter_mut().enumerate() {
+            let segment = Segment {
+                apdu_type: ApduType::ComplexAck,
+                more_follows: i < 2,
+                invoke_id: 0,
+                sequence_number: i as u8,
+                window_size: 2,
+                service_choice: ConfirmedServiceChoice::ReadPropMultiple as u8,
+                data: data[i],
+            };
+            lens[i] = frame(buf, ApplicationPdu::Segment(segment));
+        }
+        lens
+    }
+
+    // a SegmentAck from the client for invoke id 0 with a window size of 2
+    fn segment_ack(negative: bool, sequence_number: u8) -> [u8; 10] {
+        let control = if negative { 0x42 } else { 0x40 };
+        [
+            0x81,
+            0x0A,
+            0x00,
+            0x0A,
+            0x01,
+            0x00,
+            control,
+            0x00,
+            sequence_number,
+            0x02,
+        ]
+    }
+
+    fn read_segmented<'a, 'b>(
+        io: MockIo<'b>,
+        apdu_buf: &'a mut [u8],
+        objects: &[ReadAccessSpecification<'_>],
+    ) -> (ReadPropertyMultipleAck<'a>, MockIo<'b>) {
+        let mut bacnet = Bacnet::new(io);
+        let mut buf = [0; 64];
+        let request = ReadPropertyMultiple::new(objects);
+        let ack = block_on(
+            bacnet.read_property_multiple_segmented(&mut buf, apdu_buf, request, 2, TIMEOUT),
+        )
+        .unwrap();
+        (ack, bacnet.take())
+    }
+
+    fn assert_present_values(ack: &ReadPropertyMultipleAck, expected: &[(u32, f32)]) {
+        let mut count = 0;
+        for (object, (instance, value)) in ack.into_iter().zip(expected) {
+            let object = object.unwrap();
+            assert_eq!(
+                object.object_id,
+                ObjectId::new(ObjectType::ObjectAnalogInput, *instance)
+            );
+            let result = (&object.property_results).into_iter().next().unwrap();
+            let result = result.unwrap();
+            assert_eq!(result.id, PropertyId::PropPresentValue);
+            assert!(matches!(
+                result.value,
+                PropertyValue::PropValue(ApplicationDataValue::Real(x)) if x == *value
+            ));
+            count += 1;
+        }
+        assert_eq!(count, expected.len());
+    }
+
+    fn analog_inputs<const N: usize>(instances: [u32; N]) -> [ReadAccessSpecification<'static>; N] {
+        instances.map(|x| {
+            ReadAccessSpecification::new(
+                ObjectId::new(ObjectType::ObjectAnalogInput, x),
+                &PROPERTY_IDS,
+            )
+        })
+    }
+
+    #[test]
+    fn segmented_response() {
+        let mut bufs = [[0; 32]; 3];
+        let lens = segments(&mut bufs);
+        let frames = [
+            &bufs[0][..lens[0]],
+            &bufs[1][..lens[1]],
+            &bufs[2][..lens[2]],
+        ];
+
+        let mut apdu_buf = [0; 64];
+        let objects = analog_inputs([1]);
+        let (ack, io) = read_segmented(MockIo::new(&frames), &mut apdu_buf, &objects);
+        assert_present_values(&ack, &[(1, 42.0)]);
+
+        // the request then an ack for the first segment and at the end of the response
+        assert_eq!(io.write_count(), 3);
+        assert_eq!(io.written(1), segment_ack(false, 0));
+        assert_eq!(io.written(2), segment_ack(false, 2));
+    }
+
+    #[test]
+    fn first_segment_lost() {
+        let mut bufs = [[0; 32]; 3];
+        let lens = segments(&mut bufs);
+        let [first, second, third] = [
+            &bufs[0][..lens[0]],
+            &bufs[1][..lens[1]],
+            &bufs[2][..lens[2]],
+        ];
+        // the second segment arrives before the first is retransmitted
+        let frames = [second, first, second, third];
+
+        let mut apdu_buf = [0; 64];
+        let objects = analog_inputs([1]);
+        let (ack, io) = read_segmented(MockIo::new(&frames), &mut apdu_buf, &objects);
+        assert_present_values(&ack, &[(1, 42.0)]);
+
+        // no nak before the first segment
+        assert_eq!(io.write_count(), 3);
+        assert_eq!(io.written(1), segment_ack(false, 0));
+        assert_eq!(io.written(2), segment_ack(false, 2));
+    }
+
+    #[test]
+    fn last_segment_lost() {
+        let mut bufs = [[0; 32]; 3];
+        let lens = segments(&mut bufs);
+        let frames = [&bufs[0][..lens[0]], &bufs[1][..lens[1]]];
+
+        let mut bacnet = Bacnet::new(MockIo::new(&frames));
+        let mut buf = [0; 64];
+        let mut apdu_buf = [0; 64];
+        let objects = analog_inputs([1]);
+        let request = ReadPropertyMultiple::new(&objects);
+        let result = block_on(bacnet.read_property_multiple_segmented(
+            &mut buf,
+            &mut apdu_buf,
+            request,
+            2,
+            TIMEOUT,
+        ));
+        assert!(matches!(result, Err(BacnetError::Timeout)));
+    }
+
+    #[test]
+    fn segmented_response_retransmitted() {
+        let mut bufs = [[0; 32]; 3];
+        let lens = segments(&mut bufs);
+        let [first, second, third] = [
+            &bufs[0][..lens[0]],
+            &bufs[1][..lens[1]],
+            &bufs[2][..lens[2]],
+        ];
+        // the first segment is duplicated then the second is lost and the device retransmits it
+        let frames = [first, first, third, second, third];
+
+        let mut apdu_buf = [0; 64];
+        let objects = analog_inputs([1]);
+        let (ack, io) = read_segmented(MockIo::new(&frames), &mut apdu_buf, &objects);
+        assert_present_values(&ack, &[(1, 42.0)]);
+
+        assert_eq!(io.write_count(), 5);
+        assert_eq!(io.written(1), segment_ack(false, 0));
+        assert_eq!(io.written(2), segment_ack(true, 0));
+        assert_eq!(io.written(3), segment_ack(true, 0));
+        assert_eq!(io.written(4), segment_ack(false, 2));
+    }
+
+    #[test]
+    fn segmentation_not_supported_falls_back_to_chunks() {
+        let mut abort = [0; 32];
+        let abort_len = frame(
+            &mut abort,
+            ApplicationPdu::Abort(Abort {
+                server: true,
+                invoke_id: 0,
+                reason: AbortReason::SegmentationNotSupported,
+            }),
+        );
+
+        // an unsegmented ack for each object (the second with a present value of 42.5)
+        let mut acks = [[0; 32]; 2];
+        let mut ack_lens = [0; 2];
+        for (i, buf) in acks.iter_mut().enumerate() {
+            let mut apdu = [0; 19];
+            apdu[..3].copy_from_slice(&[0x30, i as u8 + 1, 0x0E]);
+            apdu[3..].copy_from_slice(&BODY);
+            apdu[7] = i as u8 + 1;
+            apdu[14] = 0x28 + 2 * i as u8;
+            ack_lens[i] = raw_frame(buf, &apdu);
+        }
+        let frames = [
+            &abort[..abort_len],
+            &acks[0][..ack_lens[0]],
+            &acks[1][..ack_lens[1]],
+        ];
+
+        let mut apdu_buf = [0; 64];
+        let objects = analog_inputs([1, 2]);
+        let (ack, io) = read_segmented(MockIo::new(&frames), &mut apdu_buf, &objects);
+        assert_present_values(&ack, &[(1, 42.0), (2, 42.5)]);
+
+        // the original request then one unsegmented request per object
+        assert_eq!(io.write_count(), 3);
+        for i in 1..3 {
+            let ApplicationPdu::ConfirmedRequest(request) = io.written_apdu(i) else {
+                panic!("expected a confirmed request");
+            };
+            assert_eq!(request.invoke_id, i as u8);
+            assert!(matches!(request.max_segments, MaxSegments::_0));
+            let ConfirmedRequestService::ReadPropertyMultiple(rpm) = request.service else {
+                panic!("expected a read property multiple request");
+            };
+            let mut objects = rpm.into_iter();
+            let object = objects.next().unwrap().unwrap();
+            assert_eq!(
+                object.object_id,
+                ObjectId::new(ObjectType::ObjectAnalogInput, i as u32)
+            );
+            assert!(objects.next().is_none());
+        }
+    }
+
+    #[test]
+    fn error_responses_are_matched_by_invoke_id() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let reject = |invoke_id| {
+            ApplicationPdu::Reject(Reject {
+                invoke_id,
+                reason: RejectReason::UnrecognizedService,
+            })
+        };
+        let mut bufs = [[0; 16]; 3];
+        let lens = [
+            frame(
+                &mut bufs[0],
+                ApplicationPdu::Abort(Abort {
+                    server: true,
+                    invoke_id: 5,
+                    reason: AbortReason::BufferOverflow,
+                }),
+            ),
+            frame(&mut bufs[1], reject(1)),
+            frame(&mut bufs[2], reject(7)),
+        ];
+        let frames = [
+            &bufs[0][..lens[0]],
+            &bufs[1][..lens[1]],
+            &bufs[2][..lens[2]],
+        ];
+        let mut bacnet = Bacnet::new(MockIo::new(&frames));
+        let mut buf = [0; 64];
+
+        // an abort for some other request is not the reason our read failed
+        let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+        let result = block_on(bacnet.read_property(&mut buf, request));
+        assert!(matches!(
+            result,
+            Err(BacnetError::InvokeId(x)) if x.expected == 0 && x.actual == 5
+        ));
+
+        let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+        let result = block_on(bacnet.read_property(&mut buf, request));
+        assert!(matches!(
+            result,
+            Err(BacnetError::Codec(Error::Rejected(
+                RejectReason::UnrecognizedService
+            )))
+        ));
+
+        let request = WriteProperty::new_array_len(object_id, PropertyId::PropPresentValue, 1);
+        let result = block_on(bacnet.write_property(&mut buf, request));
+        assert!(matches!(
+            result,
+            Err(BacnetError::InvokeId(x)) if x.expected == 2 && x.actual == 7
+        ));
+    }
+
+    const COV_RESULTS: [PropertyResult; 1] = [PropertyResult {
+        id: PropertyId::PropPresentValue,
+        array_index: None,
+        value: ApplicationDataValue::Real(21.5),
+        priority: None,
+    }];
+
+    // a cov notification from device 1234 about analog input 1
+    fn cov_notification() -> CovNotification<'static> {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        CovNotification::new(
+            4,
+            ObjectId::new(ObjectType::ObjectDevice, 1234),
+            object_id,
+            60,
+            CovNotificationValues::new(object_id, &COV_RESULTS),
+        )
+    }
+
+    fn confirmed_cov_notification(buf: &mut [u8], invoke_id: u8) -> usize {
+        let service = ConfirmedRequestService::CovNotification(cov_notification());
+        let request = ConfirmedRequest::new(invoke_id, service);
+        frame(buf, ApplicationPdu::ConfirmedRequest(request))
+    }
+
+    // a SimpleAck for a confirmed cov notification
+    fn cov_ack(invoke_id: u8) -> [u8; 9] {
+        [0x81, 0x0A, 0x00, 0x09, 0x01, 0x00, 0x20, invoke_id, 0x01]
+    }
+
+    #[test]
+    fn confirmed_cov_notification_is_acked() {
+        let mut notification = [0; 64];
+        let n = confirmed_cov_notification(&mut notification, 9);
+        let frames = [&notification[..n]];
+
+        let mut bacnet = Bacnet::new(MockIo::new(&frames));
+        let mut buf = [0; 64];
+        let received = block_on(bacnet.receive_change_of_value(&mut buf))
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.invoke_id, Some(9));
+        assert!(received.acknowledged);
+        assert_eq!(received.notification.object_id.id, 1);
+
+        let io = bacnet.take();
+        assert_eq!(io.write_count(), 1);
+        assert_eq!(io.written(0), cov_ack(9));
+        let ApplicationPdu::SimpleAck(ack) = io.written_apdu(0) else {
+            panic!("expected a simple ack");
+        };
+        assert_eq!(ack.invoke_id, 9);
+        assert!(matches!(
+            ack.service_choice,
+            ConfirmedServiceChoice::CovNotification
+        ));
+    }
+
+    #[test]
+    fn cov_notification_without_auto_ack() {
+        let mut notification = [0; 64];
+        let n = confirmed_cov_notification(&mut notification, 3);
+        let frames = [&notification[..n]];
+
+        let mut bacnet = Bacnet::new(MockIo::new(&frames));
+        bacnet.set_auto_ack_cov(false);
+        let mut buf = [0; 64];
+        let received = block_on(bacnet.receive_change_of_value(&mut buf))
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.invoke_id, Some(3));
+        assert!(!received.acknowledged);
+
+        // nothing is sent until the caller acks
+        let invoke_id = received.invoke_id.unwrap();
+        let mut ack_buf = [0; 16];
+        block_on(bacnet.ack_change_of_value(&mut ack_buf, invoke_id, received.source)).unwrap();
+        let io = bacnet.take();
+        assert_eq!(io.write_count(), 1);
+        assert_eq!(io.written(0), cov_ack(3));
+    }
+
+    #[test]
+    fn routed_cov_notification_is_acked_through_the_router() {
+        // the notification comes from mac 0x05 on network 2 behind a router
+        let service = ConfirmedRequestService::CovNotification(cov_notification());
+        let request = ConfirmedRequest::new(6, service);
+        let src = SourceAddress::new(2, MacAddress::new(&[0x05]).unwrap());
+        let npdu = NetworkPdu::new_apdu(
+            Some(src),
+            None,
+            MessagePriority::Normal,
+            ApplicationPdu::ConfirmedRequest(request),
+        );
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+        let mut notification = [0; 64];
+        let mut writer = Writer::new(&mut notification);
+        data_link.encode(&mut writer);
+        let n = writer.index;
+        let frames = [&notification[..n]];
+
+        let mut bacnet = Bacnet::new(MockIo::new(&frames));
+        let mut buf = [0; 64];
+        let received = block_on(bacnet.receive_change_of_value(&mut buf))
+            .unwrap()
+            .unwrap();
+        assert!(received.acknowledged);
+        let source = received.source.unwrap();
+        assert_eq!(source.net, 2);
+        assert_eq!(source.mac.as_slice(), [0x05]);
+
+        let io = bacnet.take();
+        assert_eq!(io.write_count(), 1);
+        assert_eq!(
+            io.written(0),
+            [0x81, 0x0A, 0x00, 0x0E, 0x01, 0x20, 0x00, 0x02, 0x01, 0x05, 0xFF, 0x20, 0x06, 0x01]
+        );
+        let mut reader = Reader::default();
+        let ack = DataLink::decode(&mut reader, io.written(0)).unwrap();
+        let dst = ack.npdu.as_ref().unwrap().dst.as_ref().unwrap();
+        assert_eq!(dst.network_address.net, 2);
+        assert_eq!(dst.network_address.mac.as_slice(), [0x05]);
+        assert!(matches!(ack.apdu(), Some(ApplicationPdu::SimpleAck(x)) if x.invoke_id == 6));
+    }
+
+    #[test]
+    fn unconfirmed_cov_notification_is_not_acked() {
+        let notification = cov_notification();
+        let request = CovNotification::new_unconfirmed(
+            notification.process_id,
+            notification.device_id,
+            notification.object_id,
+            notification.time_remaining_seconds,
+            &COV_RESULTS,
+        );
+        let mut buf = [0; 64];
+        let n = frame(&mut buf, ApplicationPdu::UnconfirmedRequest(request));
+        let frames = [&buf[..n]];
+
+        let mut bacnet = Bacnet::new(MockIo::new(&frames));
+        let mut buf = [0; 64];
+        let received = block_on(bacnet.receive_change_of_value(&mut buf))
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.invoke_id, None);
+        assert!(!received.acknowledged);
+        assert_eq!(received.notification.process_id, 4);
+        assert_eq!(bacnet.take().write_count(), 0);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    application_protocol::{
+        primitives::data_value::{ApplicationDataValue, BitString, Enumerated},
+        services::read_property_multiple::{
+            PropertyValue, ReadAccessSpecification, ReadPropertyMultipleAck,
+        },
+    },
+    common::{
+        error::Error,
+        object_id::ObjectId,
+        property_id::PropertyId,
+        spec::{EngineeringUnits, Status},
+    },
+};
+
+// The properties a discovery ui usually shows for an object.
+// Properties that the object does not have (e.g. units on a binary input) are None.
+// Strings borrow from the buffer the response was read into.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ObjectSummary<'a> {
+    pub object_id: ObjectId,
+    pub name: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub present_value: Option<ApplicationDataValue<'a>>,
+    pub units: Option<EngineeringUnits>,
+    pub status_flags: Option<Status>,
+}
+
+impl<'a> ObjectSummary<'a> {
+    pub const PROPERTY_IDS: [PropertyId; 5] = [
+        PropertyId::PropObjectName,
+        PropertyId::PropDescription,
+        PropertyId::PropPresentValue,
+        PropertyId::PropUnits,
+        PropertyId::PropStatusFlags,
+    ];
+
+    pub fn request(object_id: ObjectId) -> ReadAccessSpecification<'static> {
+        ReadAccessSpecification::new(object_id, &Self::PROPERTY_IDS)
+    }
+
+    // uses the results for the first object in the ack
+    pub fn from_ack(ack: &ReadPropertyMultipleAck<'a>) -> Result<Self, Error> {
+        let object = ack.first_object()?;
+        let mut summary = Self {
+            object_id: object.object_id,
+            name: None,
+            description: None,
+            present_value: None,
+            units: None,
+            status_flags: None,
+        };
+
+        object.visit_results(|id, value| {
+            // a property error means the object does not have this property
+            let value = match value {
+                PropertyValue::PropValue(value) => value,
+                _ => return Ok(()),
+            };
+
+            match (id, value) {
+                (PropertyId::PropObjectName, ApplicationDataValue::CharacterString(x)) => {
+                    summary.name = Some(x.inner)
+                }
+                (PropertyId::PropDescription, ApplicationDataValue::CharacterString(x)) => {
+                    summary.description = Some(x.inner)
+                }
+                (PropertyId::PropPresentValue, value) => summary.present_value = Some(value),
+                (PropertyId::PropUnits, ApplicationDataValue::Enumerated(Enumerated::Units(x))) => {
+                    summary.units = Some(x)
+                }
+                (
+                    PropertyId::PropStatusFlags,
+                    ApplicationDataValue::BitString(BitString::Status(x)),
+                ) => summary.status_flags = Some(x),
+                _ => {}
+            }
+            Ok(())
+        })?;
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::{ApplicationDataValue, CharacterString, Enumerated},
+            services::read_property_multiple::{
+                PropertyAccessError, PropertyResult, PropertyResultList, PropertyValue,
+                ReadAccessResult, ReadPropertyMultipleAck,
+            },
+        },
+        common::{
+            io::Writer,
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{EngineeringUnits, ErrorClass, ErrorCode},
+        },
+    };
+
+    use super::ObjectSummary;
+
+    #[test]
+    fn missing_description() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let results = [
+            PropertyResult {
+                id: PropertyId::PropObjectName,
+                value: PropertyValue::PropValue(ApplicationDataValue::CharacterString(
+                    CharacterString { inner: "Zone Temp" },
+                )),
+            },
+            PropertyResult {
+                id: PropertyId::PropDescription,
+                value: PropertyValue::PropError(PropertyAccessError {
+                    error_class: ErrorClass::Property,
+                    error_code: ErrorCode::UnknownProperty,
+                }),
+            },
+            PropertyResult {
+                id: PropertyId::PropPresentValue,
+                value: PropertyValue::PropValue(ApplicationDataValue::Real(21.5)),
+            },
+            PropertyResult {
+                id: PropertyId::PropUnits,
+                value: PropertyValue::PropValue(ApplicationDataValue::Enumerated(
+                    Enumerated::Units(EngineeringUnits::DegreesCelsius),
+                )),
+            },
+        ];
+        let objects = [ReadAccessResult {
+            object_id,
+            property_results: PropertyResultList::new(&results),
+        }];
+
+        // encode then decode the ack like it came off the wire
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ReadPropertyMultipleAck::new(&objects).encode(&mut writer);
+        let encoded = writer.to_bytes();
+        let ack = ReadPropertyMultipleAck::new_from_buf(&encoded[1..]); // skip service choice
+
+        let summary = ObjectSummary::from_ack(&ack).unwrap();
+        assert_eq!(summary.object_id, object_id);
+        assert_eq!(summary.name, Some("Zone Temp"));
+        assert_eq!(summary.description, None);
+        assert!(matches!(summary.present_value, Some(ApplicationDataValue::Real(x)) if x == 21.5));
+        assert!(matches!(
+            summary.units,
+            Some(EngineeringUnits::DegreesCelsius)
+        ));
+        assert!(summary.status_flags.is_none());
+    }
+}
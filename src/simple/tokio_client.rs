@@ -0,0 +1,762 @@
+use std::{
+    collections::HashMap,
+    io,
+    net::SocketAddr,
+    string::{String, ToString},
+    time::Duration,
+    vec::Vec,
+};
+
+use tokio::{net::UdpSocket, time::Instant};
+
+use crate::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{
+            response_error, ComplexAck, ComplexAckService, ConfirmedRequest,
+            ConfirmedRequestService,
+        },
+        primitives::data_value::{ApplicationDataValue, BitString},
+        services::{
+            i_am::IAm,
+            read_property::{ReadProperty, ReadPropertyAck},
+            read_property_multiple::{
+                PropertyAccessError, PropertyValue, ReadAccessSpecification, ReadPropertyMultiple,
+            },
+            who_is::WhoIs,
+            write_property::WriteProperty,
+        },
+        unconfirmed::UnconfirmedRequest,
+    },
+    common::{
+        error::Error,
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        property_id::PropertyId,
+    },
+    network_protocol::{
+        data_link::{DataLink, DataLinkFunction},
+        network_pdu::{DestinationAddress, MessagePriority, NetworkMessage, NetworkPdu},
+    },
+};
+
+// An async client for std environments built directly on a tokio UdpSocket.
+// Unlike the NetworkIo based client, frames from other addresses or with a different invoke id are
+// skipped while waiting for a response and every request is bounded by a timeout.
+#[derive(Debug)]
+pub struct AsyncBacnetClient {
+    socket: UdpSocket,
+    addr: SocketAddr, // the device that confirmed requests are sent to
+    invoke_id: u8,
+    retry_policy: RetryPolicy,
+}
+
+// How often a confirmed request is sent before giving up with ClientError::Timeout.
+// Every attempt uses a new invoke id so a late response to an earlier attempt is discarded.
+// The wait before attempt n (counting from 1 for the first retry) is backoff * n.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub attempts: u32, // including the first one
+    pub timeout: Duration,
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 1,
+            timeout: AsyncBacnetClient::DEFAULT_TIMEOUT,
+            backoff: Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    Io(io::Error),
+    Codec(Error),
+    Timeout,
+    UnexpectedResponse(&'static str),
+}
+
+// the present value of a polled object
+#[derive(Debug, Clone)]
+pub enum PolledValue {
+    Value(ApplicationDataValue<'static>),
+    Text(String), // e.g. the present value of a character string value object
+    Error(PropertyAccessError), // e.g. unknown object
+}
+
+impl From<Error> for ClientError {
+    fn from(value: Error) -> Self {
+        Self::Codec(value)
+    }
+}
+
+impl AsyncBacnetClient {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+    // 16 present values are about 250 bytes so the response fits in the 1476 byte max apdu of a B/IP device
+    // (segmented responses are not supported, a device with a smaller max apdu may need fewer objects per request)
+    pub const POLL_CHUNK_SIZE: usize = 16;
+
+    // the socket needs SO_BROADCAST set (see UdpSocket::set_broadcast) for who_is
+    pub fn new(socket: UdpSocket, addr: SocketAddr) -> Self {
+        Self {
+            socket,
+            addr,
+            invoke_id: 0,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.retry_policy.timeout = timeout;
+    }
+
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Returns the socket back to the caller and consumes self
+    pub fn take(self) -> UdpSocket {
+        self.socket
+    }
+
+    pub async fn read_property<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+        request: ReadProperty,
+    ) -> Result<ReadPropertyAck<'a>, ClientError> {
+        let service = ConfirmedRequestService::ReadProperty(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::ReadProperty(ack) => Ok(ack),
+            _ => Err(ClientError::UnexpectedResponse(
+                "apdu message is not a ComplexAckService ReadPropertyAck",
+            )),
+        }
+    }
+
+    pub async fn write_property(
+        &mut self,
+        buf: &mut [u8],
+        request: WriteProperty<'_>,
+    ) -> Result<(), ClientError> {
+        let service = ConfirmedRequestService::WriteProperty(request);
+        let n = self.send_with_retry(buf, service).await?;
+        match decode_apdu(&buf[..n])? {
+            ApplicationPdu::SimpleAck(_) => Ok(()),
+            apdu => Err(Self::to_error(apdu)),
+        }
+    }
+
+    // reads the present value of each object using ReadPropertyMultiple (POLL_CHUNK_SIZE objects per request)
+    // objects that the device returns an error for are mapped to PolledValue::Error rather than failing the poll
+    // and values that can't be held without the receive buffer (e.g. octet strings) are left out
+    pub async fn poll(
+        &mut self,
+        buf: &mut [u8],
+        object_ids: &[ObjectId],
+    ) -> Result<HashMap<ObjectId, PolledValue>, ClientError> {
+        const PROPERTY_IDS: [PropertyId; 1] = [PropertyId::PropPresentValue];
+
+        let mut values = HashMap::with_capacity(object_ids.len());
+        for chunk in object_ids.chunks(Self::POLL_CHUNK_SIZE) {
+            let objects: Vec<_> = chunk
+                .iter()
+                .map(|object_id| ReadAccessSpecification::new(*object_id, &PROPERTY_IDS))
+                .collect();
+            let service =
+                ConfirmedRequestService::ReadPropertyMultiple(ReadPropertyMultiple::new(&objects));
+            let ack = match self
+                .send_and_receive_complex_ack(buf, service)
+                .await?
+                .service
+            {
+                ComplexAckService::ReadPropertyMultiple(ack) => ack,
+                _ => {
+                    return Err(ClientError::UnexpectedResponse(
+                        "apdu message is not a ComplexAckService ReadPropertyMultipleAck",
+                    ))
+                }
+            };
+
+            for object in &ack {
+                let object = object?;
+                for result in &object.property_results {
+                    if let Some(value) = to_polled_value(result?.value) {
+                        values.insert(object.object_id, value);
+                    }
+                }
+            }
+        }
+
+        Ok(values)
+    }
+
+    // broadcasts the request and returns the I-Am responses as they arrive until the timeout expires
+    pub async fn who_is<'a>(
+        &'a mut self,
+        buf: &'a mut [u8],
+        request: WhoIs,
+        broadcast_addr: SocketAddr,
+        timeout: Duration,
+    ) -> Result<IAmStream<'a>, ClientError> {
+        let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::WhoIs(request));
+        let dst = Some(DestinationAddress::new(0xffff, None));
+        let npdu = NetworkPdu::new_apdu(None, dst, MessagePriority::Normal, apdu);
+        let data_link = DataLink::new(DataLinkFunction::OriginalBroadcastNpdu, Some(npdu));
+
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+        self.socket
+            .send_to(writer.to_bytes(), broadcast_addr)
+            .await
+            .map_err(ClientError::Io)?;
+
+        Ok(IAmStream {
+            socket: &self.socket,
+            buf,
+            deadline: Instant::now() + timeout,
+        })
+    }
+
+    // broadcasts the request and collects the devices that reply within the listen duration
+    pub async fn discover(
+        &mut self,
+        buf: &mut [u8],
+        request: WhoIs,
+        broadcast_addr: SocketAddr,
+        listen: Duration,
+    ) -> Result<DiscoverySession, ClientError> {
+        let mut session = DiscoverySession::new();
+        let mut stream = self.who_is(buf, request, broadcast_addr, listen).await?;
+        session.collect(&mut stream).await?;
+        Ok(session)
+    }
+
+    async fn send_and_receive_complex_ack<'a>(
+        &mut self,
+        buf: &'a mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<ComplexAck<'a>, ClientError> {
+        let n = self.send_with_retry(buf, service).await?;
+        match decode_apdu(&buf[..n])? {
+            ApplicationPdu::ComplexAck(ack) => Ok(ack),
+            apdu => Err(Self::to_error(apdu)),
+        }
+    }
+
+    // sends the request until a response arrives or the retry policy gives up
+    // returns the length of the response in buf
+    async fn send_with_retry(
+        &mut self,
+        buf: &mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<usize, ClientError> {
+        let policy = self.retry_policy;
+        let mut attempt = 0;
+        loop {
+            let invoke_id = self.send_confirmed(buf, service.clone()).await?;
+            match self.receive(buf, invoke_id, policy.timeout).await {
+                Err(ClientError::Timeout) if attempt + 1 < policy.attempts => {
+                    attempt += 1;
+                    tokio::time::sleep(policy.backoff * attempt).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send_confirmed(
+        &mut self,
+        buf: &mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<u8, ClientError> {
+        let invoke_id = self.invoke_id;
+        self.invoke_id = self.invoke_id.wrapping_add(1);
+
+        let data_link = DataLink::new_confirmed_req(ConfirmedRequest::new(invoke_id, service));
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+        self.socket
+            .send_to(writer.to_bytes(), self.addr)
+            .await
+            .map_err(ClientError::Io)?;
+
+        Ok(invoke_id)
+    }
+
+    // waits for the response to the request with this invoke id and returns its length
+    async fn receive(
+        &mut self,
+        buf: &mut [u8],
+        invoke_id: u8,
+        timeout: Duration,
+    ) -> Result<usize, ClientError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let (n, from) = tokio::time::timeout_at(deadline, self.socket.recv_from(buf))
+                .await
+                .map_err(|_| ClientError::Timeout)?
+                .map_err(ClientError::Io)?;
+
+            // skip anything that is not a response to our request
+            if from != self.addr {
+                continue;
+            }
+            match decode_apdu(&buf[..n]) {
+                Ok(ApplicationPdu::ConfirmedRequest(_)) => continue,
+                Ok(apdu) if apdu.invoke_id() == Some(invoke_id) => return Ok(n),
+                _ => continue,
+            }
+        }
+    }
+
+    // Error, Reject and Abort responses become Error::RequestFailed, Error::Rejected and Error::Aborted
+    fn to_error(apdu: ApplicationPdu) -> ClientError {
+        match apdu {
+            ApplicationPdu::Segment(_) => {
+                ClientError::UnexpectedResponse("segmented responses are not supported")
+            }
+            apdu => match response_error(apdu) {
+                Some(e) => ClientError::Codec(e),
+                None => ClientError::UnexpectedResponse("unexpected apdu type in response"),
+            },
+        }
+    }
+}
+
+// I-Am responses to a who_is broadcast
+#[derive(Debug)]
+pub struct IAmStream<'a> {
+    socket: &'a UdpSocket,
+    buf: &'a mut [u8],
+    deadline: Instant,
+}
+
+impl<'a> IAmStream<'a> {
+    // returns None once the timeout has expired
+    pub async fn next(&mut self) -> Option<Result<(IAm, SocketAddr), ClientError>> {
+        loop {
+            let (n, from) =
+                match tokio::time::timeout_at(self.deadline, self.socket.recv_from(self.buf)).await
+                {
+                    Err(_) => return None,
+                    Ok(Err(e)) => return Some(Err(ClientError::Io(e))),
+                    Ok(Ok(x)) => x,
+                };
+
+            // other broadcast traffic is ignored
+            if let Ok(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(iam))) =
+                decode_apdu(&self.buf[..n])
+            {
+                return Some(Ok((iam, from)));
+            }
+        }
+    }
+}
+
+// a device that replied to a who_is broadcast
+#[derive(Debug, Clone)]
+pub struct DiscoveredDevice {
+    pub i_am: IAm,
+    pub addr: SocketAddr,
+}
+
+// The devices found by a who_is broadcast, one per device id.
+// A device that replies more than once keeps the address of its latest reply.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoverySession {
+    devices: HashMap<u32, DiscoveredDevice>,
+}
+
+impl DiscoverySession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // returns true if the device had not replied before
+    pub fn insert(&mut self, i_am: IAm, addr: SocketAddr) -> bool {
+        let device_id = i_am.device_id.id;
+        self.devices
+            .insert(device_id, DiscoveredDevice { i_am, addr })
+            .is_none()
+    }
+
+    // reads I-Am responses until the stream times out
+    pub async fn collect(&mut self, stream: &mut IAmStream<'_>) -> Result<(), ClientError> {
+        while let Some(result) = stream.next().await {
+            let (i_am, addr) = result?;
+            self.insert(i_am, addr);
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    pub fn get(&self, device_id: u32) -> Option<&DiscoveredDevice> {
+        self.devices.get(&device_id)
+    }
+
+    // ordered by device id
+    pub fn devices(&self) -> Vec<&DiscoveredDevice> {
+        let mut devices: Vec<_> = self.devices.values().collect();
+        devices.sort_by_key(|x| x.i_am.device_id.id);
+        devices
+    }
+}
+
+fn to_polled_value(value: PropertyValue) -> Option<PolledValue> {
+    let value = match value {
+        PropertyValue::PropValue(x) => x,
+        PropertyValue::PropError(x) => return Some(PolledValue::Error(x)),
+        _ => return None,
+    };
+
+    let value = match value {
+        ApplicationDataValue::Null => ApplicationDataValue::Null,
+        ApplicationDataValue::Boolean(x) => ApplicationDataValue::Boolean(x),
+        ApplicationDataValue::Real(x) => ApplicationDataValue::Real(x),
+        ApplicationDataValue::Double(x) => ApplicationDataValue::Double(x),
+        ApplicationDataValue::Date(x) => ApplicationDataValue::Date(x),
+        ApplicationDataValue::Time(x) => ApplicationDataValue::Time(x),
+        ApplicationDataValue::ObjectId(x) => ApplicationDataValue::ObjectId(x),
+        ApplicationDataValue::Enumerated(x) => ApplicationDataValue::Enumerated(x),
+        ApplicationDataValue::UnsignedInt(x) => ApplicationDataValue::UnsignedInt(x),
+        ApplicationDataValue::BigUnsignedInt(x) => ApplicationDataValue::BigUnsignedInt(x),
+        ApplicationDataValue::SignedInt(x) => ApplicationDataValue::SignedInt(x),
+        ApplicationDataValue::BigSignedInt(x) => ApplicationDataValue::BigSignedInt(x),
+        ApplicationDataValue::CharacterString(x) => {
+            return Some(PolledValue::Text(x.inner.to_string()))
+        }
+        ApplicationDataValue::BitString(x) => ApplicationDataValue::BitString(match x {
+            BitString::Status(x) => BitString::Status(x),
+            BitString::LogBufferResult(x) => BitString::LogBufferResult(x),
+            BitString::LimitEnable(x) => BitString::LimitEnable(x),
+            BitString::EventEnable(x) => BitString::EventEnable(x),
+            BitString::AckedTransitions(x) => BitString::AckedTransitions(x),
+            BitString::AckRequired(x) => BitString::AckRequired(x),
+            BitString::ServicesSupported(_)
+            | BitString::ObjectTypesSupported(_)
+            | BitString::Custom(_) => return None,
+        }),
+        ApplicationDataValue::OctetString(_) | ApplicationDataValue::WeeklySchedule(_) => {
+            return None
+        }
+    };
+
+    Some(PolledValue::Value(value))
+}
+
+fn decode_apdu(buf: &[u8]) -> Result<ApplicationPdu<'_>, Error> {
+    let mut reader = Reader::default();
+    let message = DataLink::decode(&mut reader, buf)?;
+    match message.npdu {
+        Some(NetworkPdu {
+            network_message: NetworkMessage::Apdu(apdu),
+            ..
+        }) => Ok(apdu),
+        _ => Err(Error::ConvertDataLink("npdu message is not an apdu")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{ComplexAck, ComplexAckService, ConfirmedRequestService},
+            primitives::data_value::ApplicationDataValue,
+            services::{
+                i_am::IAm,
+                read_property::{ReadProperty, ReadPropertyAck, ReadPropertyValue},
+                read_property_multiple::{
+                    PropertyAccessError, PropertyResult, PropertyResultList, PropertyValue,
+                    ReadAccessResult, ReadPropertyMultipleAck,
+                },
+                who_is::WhoIs,
+            },
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::Writer,
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{ErrorClass, ErrorCode, Segmentation},
+        },
+        network_protocol::{
+            data_link::{DataLink, DataLinkFunction},
+            network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+        },
+    };
+
+    use super::{decode_apdu, AsyncBacnetClient, ClientError, PolledValue, RetryPolicy};
+
+    fn encode_i_am(buf: &mut [u8], device_id: u32) -> usize {
+        let i_am = IAm {
+            device_id: ObjectId::new(ObjectType::ObjectDevice, device_id),
+            max_apdu: 1476,
+            segmentation: Segmentation::None,
+            vendor_id: 999,
+        };
+        let apdu = ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(i_am));
+        encode_apdu(buf, apdu)
+    }
+
+    fn encode_apdu(buf: &mut [u8], apdu: ApplicationPdu) -> usize {
+        let npdu = NetworkPdu::new(
+            None,
+            None,
+            false,
+            MessagePriority::Normal,
+            NetworkMessage::Apdu(apdu),
+        );
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+        writer.index
+    }
+
+    fn encode_ack(buf: &mut [u8], invoke_id: u8) -> usize {
+        let ack = ReadPropertyAck {
+            object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            property_id: PropertyId::PropPresentValue,
+            property_value: ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Real(
+                21.5,
+            )),
+        };
+        let apdu = ApplicationPdu::ComplexAck(ComplexAck {
+            invoke_id,
+            service: ComplexAckService::ReadProperty(ack),
+        });
+        encode_apdu(buf, apdu)
+    }
+
+    #[tokio::test]
+    async fn read_property_skips_stale_responses() {
+        let device = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut client = AsyncBacnetClient::new(socket, device.local_addr().unwrap());
+        client.set_timeout(Duration::from_millis(500));
+
+        let server = tokio::spawn(async move {
+            let mut buf = [0; 1500];
+            let (n, from) = device.recv_from(&mut buf).await.unwrap();
+            let invoke_id = decode_apdu(&buf[..n]).unwrap().invoke_id().unwrap();
+
+            // a late response to an earlier request should be ignored
+            let n = encode_ack(&mut buf, invoke_id.wrapping_add(100));
+            device.send_to(&buf[..n], from).await.unwrap();
+            let n = encode_ack(&mut buf, invoke_id);
+            device.send_to(&buf[..n], from).await.unwrap();
+        });
+
+        let mut buf = [0; 1500];
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+        let ack = client.read_property(&mut buf, request).await.unwrap();
+        assert!(matches!(
+            ack.property_value,
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Real(x)) if x == 21.5
+        ));
+        server.await.unwrap();
+
+        // nobody is listening anymore
+        let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+        let result = client.read_property(&mut buf, request).await;
+        assert!(matches!(result, Err(ClientError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn retry_after_dropped_request() {
+        let device = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut client = AsyncBacnetClient::new(socket, device.local_addr().unwrap());
+        client.set_retry_policy(RetryPolicy {
+            attempts: 3,
+            timeout: Duration::from_millis(200),
+            backoff: Duration::from_millis(10),
+        });
+
+        let server = tokio::spawn(async move {
+            let mut buf = [0; 1500];
+
+            // the first request is dropped
+            let (n, _) = device.recv_from(&mut buf).await.unwrap();
+            let first_invoke_id = decode_apdu(&buf[..n]).unwrap().invoke_id().unwrap();
+
+            // the retry uses a new invoke id so the late response to the first attempt is discarded
+            let (n, from) = device.recv_from(&mut buf).await.unwrap();
+            let invoke_id = decode_apdu(&buf[..n]).unwrap().invoke_id().unwrap();
+            assert_ne!(invoke_id, first_invoke_id);
+            let n = encode_ack(&mut buf, first_invoke_id);
+            device.send_to(&buf[..n], from).await.unwrap();
+            let n = encode_ack(&mut buf, invoke_id);
+            device.send_to(&buf[..n], from).await.unwrap();
+
+            // every attempt of the next request is dropped
+            for _ in 0..3 {
+                device.recv_from(&mut buf).await.unwrap();
+            }
+        });
+
+        let mut buf = [0; 1500];
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+        let ack = client.read_property(&mut buf, request).await.unwrap();
+        assert!(matches!(
+            ack.property_value,
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Real(x)) if x == 21.5
+        ));
+
+        let request = ReadProperty::new(object_id, PropertyId::PropPresentValue);
+        let result = client.read_property(&mut buf, request).await;
+        assert!(matches!(result, Err(ClientError::Timeout)));
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn discover_deduplicates_devices() {
+        let first = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let second = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let client_addr = socket.local_addr().unwrap();
+        let mut client = AsyncBacnetClient::new(socket, first.local_addr().unwrap());
+
+        // the first device replies twice (e.g. it is reachable on two networks)
+        let server = tokio::spawn(async move {
+            let mut buf = [0; 1500];
+            first.recv_from(&mut buf).await.unwrap();
+            for (socket, device_id) in [(&first, 20), (&second, 10), (&first, 20)] {
+                let n = encode_i_am(&mut buf, device_id);
+                socket.send_to(&buf[..n], client_addr).await.unwrap();
+            }
+            (first.local_addr().unwrap(), second.local_addr().unwrap())
+        });
+
+        let mut buf = [0; 1500];
+        let broadcast_addr = client.addr;
+        let session = client
+            .discover(
+                &mut buf,
+                WhoIs::new(),
+                broadcast_addr,
+                Duration::from_millis(300),
+            )
+            .await
+            .unwrap();
+        let (first_addr, second_addr) = server.await.unwrap();
+
+        assert_eq!(session.len(), 2);
+        let devices = session.devices();
+        assert_eq!(devices[0].i_am.device_id.id, 10);
+        assert_eq!(devices[0].addr, second_addr);
+        assert_eq!(devices[1].i_am.device_id.id, 20);
+        assert_eq!(devices[1].addr, first_addr);
+        assert!(session.get(30).is_none());
+    }
+
+    #[tokio::test]
+    async fn poll_in_chunks() {
+        let device = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut client = AsyncBacnetClient::new(socket, device.local_addr().unwrap());
+        client.set_timeout(Duration::from_millis(500));
+
+        // analog input 99 does not exist and every other object has its instance number as its present value
+        let server = tokio::spawn(async move {
+            let mut requests = 0;
+            let mut buf = [0; 1500];
+            while let Ok(Ok((n, from))) =
+                tokio::time::timeout(Duration::from_millis(200), device.recv_from(&mut buf)).await
+            {
+                let mut object_ids = std::vec::Vec::new();
+                let invoke_id = match decode_apdu(&buf[..n]).unwrap() {
+                    ApplicationPdu::ConfirmedRequest(request) => {
+                        if let ConfirmedRequestService::ReadPropertyMultiple(rpm) = request.service
+                        {
+                            for object in &rpm {
+                                object_ids.push(object.unwrap().object_id);
+                            }
+                        }
+                        request.invoke_id
+                    }
+                    x => panic!("expected a confirmed request: {:?}", x),
+                };
+
+                let results: std::vec::Vec<_> = object_ids
+                    .iter()
+                    .map(|object_id| {
+                        let value = if object_id.id == 99 {
+                            PropertyValue::PropError(PropertyAccessError {
+                                error_class: ErrorClass::Object,
+                                error_code: ErrorCode::UnknownObject,
+                            })
+                        } else {
+                            PropertyValue::PropValue(ApplicationDataValue::Real(
+                                object_id.id as f32,
+                            ))
+                        };
+                        [PropertyResult {
+                            id: PropertyId::PropPresentValue,
+                            value,
+                        }]
+                    })
+                    .collect();
+                let objects: std::vec::Vec<_> = object_ids
+                    .iter()
+                    .zip(results.iter())
+                    .map(|(object_id, results)| ReadAccessResult {
+                        object_id: *object_id,
+                        property_results: PropertyResultList::new(results),
+                    })
+                    .collect();
+                let apdu = ApplicationPdu::ComplexAck(ComplexAck {
+                    invoke_id,
+                    service: ComplexAckService::ReadPropertyMultiple(ReadPropertyMultipleAck::new(
+                        &objects,
+                    )),
+                });
+                let n = encode_apdu(&mut buf, apdu);
+                device.send_to(&buf[..n], from).await.unwrap();
+                requests += 1;
+            }
+            requests
+        });
+
+        let object_ids: std::vec::Vec<_> = (0..20)
+            .map(|id| ObjectId::new(ObjectType::ObjectAnalogInput, id))
+            .chain([ObjectId::new(ObjectType::ObjectAnalogInput, 99)])
+            .collect();
+        let mut buf = [0; 1500];
+        let values = client.poll(&mut buf, &object_ids).await.unwrap();
+
+        assert_eq!(values.len(), 21);
+        for id in 0..20 {
+            let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, id);
+            assert!(matches!(
+                values[&object_id],
+                PolledValue::Value(ApplicationDataValue::Real(x)) if x == id as f32
+            ));
+        }
+        assert!(matches!(
+            &values[&ObjectId::new(ObjectType::ObjectAnalogInput, 99)],
+            PolledValue::Error(x) if matches!(x.error_code, ErrorCode::UnknownObject)
+        ));
+
+        // 21 objects is two requests
+        assert_eq!(server.await.unwrap(), 2);
+    }
+}
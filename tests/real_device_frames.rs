@@ -0,0 +1,249 @@
+// Frames captured from real controllers (with the device specific values anonymised).
+// Each frame is decoded through the public DataLink api the same way the clients do it.
+
+use embedded_bacnet::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{ComplexAckService, ConfirmedServiceChoice},
+        primitives::data_value::{ApplicationDataValue, BitString, Enumerated},
+        services::read_property_multiple::PropertyValue,
+        unconfirmed::UnconfirmedRequest,
+    },
+    common::{
+        error::Error,
+        io::Reader,
+        notification_parameters::NotificationParameters,
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+        spec::{EngineeringUnits, ErrorClass, ErrorCode, EventState, NotifyType, Segmentation},
+        time_stamp::TimeStamp,
+    },
+    network_protocol::data_link::DataLink,
+};
+
+// I-Am broadcast to the global network from device 1234
+const I_AM: [u8; 25] = [
+    0x81, 0x0B, 0x00, 0x19, // bvlc
+    0x01, 0x20, 0xFF, 0xFF, 0x00, 0xFF, // npdu
+    0x10, 0x00, // unconfirmed i-am
+    0xC4, 0x02, 0x00, 0x04, 0xD2, // device 1234
+    0x22, 0x05, 0xC4, // max apdu 1476
+    0x91, 0x03, // no segmentation
+    0x22, 0x01, 0x04, // vendor 260
+];
+
+// ReadPropertyMultiple ack for analog-input 1 with a real, a string, a bit string,
+// an enumerated and a property error
+const RPM_ACK: [u8; 57] = [
+    0x81, 0x0A, 0x00, 0x39, // bvlc
+    0x01, 0x00, // npdu
+    0x30, 0x05, 0x0E, // complex ack, invoke id 5, read property multiple
+    0x0C, 0x00, 0x00, 0x00, 0x01, // analog-input 1
+    0x1E, // results
+    0x29, 0x55, 0x4E, 0x44, 0x41, 0xAC, 0x00, 0x00, 0x4F, // present-value 21.5
+    0x29, 0x4D, 0x4E, 0x75, 0x05, 0x00, b'Z', b'o', b'n', b'e', 0x4F, // object-name "Zone"
+    0x29, 0x6F, 0x4E, 0x82, 0x04, 0x00, 0x4F, // status-flags all clear
+    0x29, 0x75, 0x4E, 0x91, 0x3E, 0x4F, // units degrees-celsius
+    0x29, 0x1C, 0x5E, 0x91, 0x02, 0x91, 0x20, 0x5F, // description: property, unknown-property
+    0x1F,
+];
+
+// unconfirmed event notification for an out-of-range alarm on analog-input 1
+const EVENT_NOTIFICATION: [u8; 60] = [
+    0x81, 0x0A, 0x00, 0x3C, // bvlc
+    0x01, 0x00, // npdu
+    0x10, 0x03, // unconfirmed event notification
+    0x09, 0x01, // process id
+    0x1C, 0x02, 0x00, 0x04, 0xD2, // initiating device 1234
+    0x2C, 0x00, 0x00, 0x00, 0x01, // analog-input 1
+    0x3E, 0x19, 0x05, 0x3F, // time stamp (sequence number)
+    0x49, 0x01, // notification class
+    0x59, 0x64, // priority
+    0x69, 0x05, // out-of-range
+    0x89, 0x00, // alarm
+    0x99, 0x01, // ack required
+    0xA9, 0x00, // from normal
+    0xB9, 0x03, // to high-limit
+    0xCE, 0x5E, // event values: out-of-range
+    0x0C, 0x41, 0xFC, 0x00, 0x00, // exceeding value 31.5
+    0x1A, 0x04, 0x80, // status flags in-alarm
+    0x2C, 0x3F, 0x80, 0x00, 0x00, // deadband 1.0
+    0x3C, 0x41, 0xF0, 0x00, 0x00, // exceeded limit 30.0
+    0x5F, 0xCF,
+];
+
+// ReadProperty of an object that doesn't exist
+const ERROR_PDU: [u8; 13] = [
+    0x81, 0x0A, 0x00, 0x0D, // bvlc
+    0x01, 0x00, // npdu
+    0x50, 0x07, 0x0C, // error, invoke id 7, read property
+    0x91, 0x01, 0x91, 0x1F, // object, unknown-object
+];
+
+fn decode(input: &[u8]) -> Result<DataLink<'_>, Error> {
+    let mut reader = Reader::default();
+    DataLink::decode(&mut reader, input)
+}
+
+#[test]
+fn i_am() {
+    let message = decode(&I_AM).unwrap();
+    let Some(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(i_am))) = message.apdu()
+    else {
+        panic!("expected an i-am: {:?}", message);
+    };
+    assert_eq!(
+        i_am.device_id,
+        ObjectId::new(ObjectType::ObjectDevice, 1234)
+    );
+    assert_eq!(i_am.max_apdu, 1476);
+    assert!(matches!(i_am.segmentation, Segmentation::None));
+    assert_eq!(i_am.vendor_id, 260);
+}
+
+#[test]
+fn read_property_multiple_ack() {
+    let message = decode(&RPM_ACK).unwrap();
+    let Some(ApplicationPdu::ComplexAck(ack)) = message.apdu() else {
+        panic!("expected a complex ack: {:?}", message);
+    };
+    assert_eq!(ack.invoke_id, 5);
+    let ComplexAckService::ReadPropertyMultiple(ack) = &ack.service else {
+        panic!("expected a read property multiple ack: {:?}", ack);
+    };
+
+    let objects: Vec<_> = ack.into_iter().collect::<Result<_, _>>().unwrap();
+    assert_eq!(objects.len(), 1);
+    assert_eq!(
+        objects[0].object_id,
+        ObjectId::new(ObjectType::ObjectAnalogInput, 1)
+    );
+
+    let results: Vec<_> = (&objects[0].property_results)
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(results.len(), 5);
+
+    assert_eq!(results[0].id, PropertyId::PropPresentValue);
+    assert!(matches!(
+        results[0].value,
+        PropertyValue::PropValue(ApplicationDataValue::Real(x)) if x == 21.5
+    ));
+
+    assert_eq!(results[1].id, PropertyId::PropObjectName);
+    assert!(matches!(
+        &results[1].value,
+        PropertyValue::PropValue(ApplicationDataValue::CharacterString(x)) if x.inner == "Zone"
+    ));
+
+    assert_eq!(results[2].id, PropertyId::PropStatusFlags);
+    assert!(matches!(
+        &results[2].value,
+        PropertyValue::PropValue(ApplicationDataValue::BitString(BitString::Status(x)))
+            if !x.in_alarm() && !x.fault() && !x.overridden() && !x.out_of_service()
+    ));
+
+    assert_eq!(results[3].id, PropertyId::PropUnits);
+    assert!(matches!(
+        results[3].value,
+        PropertyValue::PropValue(ApplicationDataValue::Enumerated(Enumerated::Units(
+            EngineeringUnits::DegreesCelsius
+        )))
+    ));
+
+    assert_eq!(results[4].id, PropertyId::PropDescription);
+    assert!(matches!(
+        &results[4].value,
+        PropertyValue::PropError(x)
+            if matches!(x.error_class, ErrorClass::Property)
+                && matches!(x.error_code, ErrorCode::UnknownProperty)
+    ));
+}
+
+#[test]
+fn event_notification() {
+    let message = decode(&EVENT_NOTIFICATION).unwrap();
+    let Some(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::EventNotification(
+        notification,
+    ))) = message.apdu()
+    else {
+        panic!("expected an event notification: {:?}", message);
+    };
+    assert_eq!(notification.device_id.id, 1234);
+    assert_eq!(
+        notification.object_id,
+        ObjectId::new(ObjectType::ObjectAnalogInput, 1)
+    );
+    assert!(matches!(
+        notification.time_stamp,
+        TimeStamp::SequenceNumber(5)
+    ));
+    assert_eq!(notification.event_type, 5);
+    assert!(matches!(notification.notify_type, NotifyType::Alarm));
+    assert_eq!(notification.ack_required, Some(true));
+    assert!(matches!(notification.from_state, Some(EventState::Normal)));
+    assert!(matches!(notification.to_state, EventState::HighLimit));
+
+    // why the alarm fired
+    let Some(NotificationParameters::OutOfRange {
+        exceeding_value,
+        status_flags,
+        deadband,
+        exceeded_limit,
+    }) = &notification.event_values
+    else {
+        panic!("expected out of range event values: {:?}", notification);
+    };
+    assert_eq!(*exceeding_value, 31.5);
+    assert!(status_flags.in_alarm());
+    assert_eq!(*deadband, 1.0);
+    assert_eq!(*exceeded_limit, 30.0);
+}
+
+#[test]
+fn error_pdu() {
+    let message = decode(&ERROR_PDU).unwrap();
+    let Some(ApplicationPdu::Error(error)) = message.apdu() else {
+        panic!("expected an error: {:?}", message);
+    };
+    assert_eq!(error.invoke_id, 7);
+    assert!(matches!(
+        error.service_choice,
+        ConfirmedServiceChoice::ReadProperty
+    ));
+    assert!(matches!(error.error_class, ErrorClass::Object));
+    assert!(matches!(error.error_code, ErrorCode::UnknownObject));
+}
+
+#[test]
+fn malformed_frames_are_errors() {
+    // i-am cut off in the middle of the device id (bvlc length matches the truncated frame)
+    let mut truncated = [0; 14];
+    truncated.copy_from_slice(&I_AM[..14]);
+    truncated[3] = 14;
+    assert!(decode(&truncated).is_err());
+
+    // bvlc length is longer than the packet
+    assert!(decode(&I_AM[..20]).is_err());
+
+    // rpm ack missing its closing tag
+    let mut truncated = [0; 56];
+    truncated.copy_from_slice(&RPM_ACK[..56]);
+    truncated[3] = 56;
+    let decoded = decode(&truncated).and_then(|message| match message.apdu() {
+        Some(ApplicationPdu::ComplexAck(ack)) => match &ack.service {
+            ComplexAckService::ReadPropertyMultiple(ack) => {
+                for object in ack {
+                    for result in &object?.property_results {
+                        result?;
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    });
+    assert!(decoded.is_err());
+}